@@ -0,0 +1,21 @@
+#![feature(test)]
+
+extern crate baimax;
+extern crate test;
+
+use baimax::data::File;
+use test::Bencher;
+
+const SPEC_EXAMPLE: &str = include_str!("../spec-example.bai");
+
+#[bench]
+fn process_owned(b: &mut Bencher) {
+    b.bytes = SPEC_EXAMPLE.len() as u64;
+    b.iter(|| File::process(SPEC_EXAMPLE.as_bytes()).unwrap());
+}
+
+#[bench]
+fn process_borrowed(b: &mut Bencher) {
+    b.bytes = SPEC_EXAMPLE.len() as u64;
+    b.iter(|| File::process_borrowed(SPEC_EXAMPLE.as_bytes()).unwrap());
+}
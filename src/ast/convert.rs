@@ -1,10 +1,12 @@
 use std::convert::{TryFrom, TryInto};
+use std::error::Error;
+use std::fmt;
 
 use chrono::NaiveDate;
 use itertools::Itertools;
 use penny;
 
-use ast::{self, ParsedRecord};
+use ast::{self, ParsedRecord, RecordField};
 use ast::data::{self, BaiDateOrTime, BaiDateTime};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -14,9 +16,21 @@ pub enum ChronoError {
     InvalidTime,
 }
 
+impl fmt::Display for ChronoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ChronoError::InvalidDate => "date doesn't exist",
+            ChronoError::InvalidTime => "time doesn't exist",
+        })
+    }
+}
+
+// Two-digit years below the pivot are in the 2000s, and the rest are in the 1900s.
+pub const DEFAULT_YEAR_PIVOT: u8 = 70;
+
 fn chrono_date(date: &ast::Date) -> Result<NaiveDate, ChronoError> {
     NaiveDate::from_ymd_opt(
-        if date.year > 70 { 1900 } else { 2000 } + date.year as i32,
+        if date.year < DEFAULT_YEAR_PIVOT { 2000 } else { 1900 } + date.year as i32,
         date.month as u32,
         date.day as u32,
     ).ok_or(ChronoError::InvalidDate)
@@ -45,71 +59,115 @@ fn chrono_date_or_time(
     }
 }
 
-pub struct Converter {
-    state: Option<ConverterState>,
+// `S` is the string type of the file being built, `String` unless borrowing from the input.
+pub struct Converter<S = String> {
+    state: Option<ConverterState<S>>,
+    // Whether finished transaction details, accounts, and groups are kept in their parents.
+    retain: bool,
+    // Otherwise the account or group the last record closed, until the next record.
+    closed_account: Option<data::Account<S>>,
+    closed_group: Option<data::Group<S>>,
+    record_counts: RecordCountConvention,
+    codes: data::CodeTable,
+    lenient_funds: bool,
 }
 
-impl Default for Converter {
+// What the number of records in 49 Account and 98 Group Trailers counts. The spec counts the
+// header and the trailer itself, but some banks only count the records between them. Neither is
+// checked by default, since files with either went through before.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum RecordCountConvention {
+    Unchecked,
+    Inclusive,
+    Exclusive,
+}
+
+impl Default for RecordCountConvention {
     fn default() -> Self {
-        Converter {
-            state: Some(ConverterState::Fresh),
+        RecordCountConvention::Unchecked
+    }
+}
+
+impl RecordCountConvention {
+    // The count a trailer should have for `records_num` physical records from the header to the
+    // trailer, or `None` if it isn't checked.
+    fn expected(self, records_num: usize) -> Option<usize> {
+        match self {
+            RecordCountConvention::Unchecked => None,
+            RecordCountConvention::Inclusive => Some(records_num),
+            RecordCountConvention::Exclusive => Some(records_num - 2),
         }
     }
 }
 
+impl Default for Converter {
+    fn default() -> Self {
+        Converter::new()
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[cfg_attr(any(feature = "clippy", feature = "cargo-clippy"), allow(large_enum_variant))]
-enum ConverterState {
+enum ConverterState<S> {
     Fresh,
-    File(FileConvState),
-    Group(FileConvState, GroupConvState),
-    Account(FileConvState, GroupConvState, AccountConvState),
+    File(FileConvState<S>),
+    Group(FileConvState<S>, GroupConvState<S>),
+    Account(FileConvState<S>, GroupConvState<S>, AccountConvState<S>),
 }
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
-struct FileConvState {
-    data: data::File,
+struct FileConvState<S> {
+    // None for a fragment, which has no file header.
+    data: Option<data::File<S>>,
+    groups: Vec<data::Group<S>>,
     records_num: usize,
     control_total: i64,
+    groups_num: usize,
 }
-impl FileConvState {
-    fn new(data: data::File, records_num: usize) -> Self {
+impl<S> FileConvState<S> {
+    fn new(data: Option<data::File<S>>, records_num: usize) -> Self {
         FileConvState {
             data,
+            groups: Vec::new(),
             records_num,
             control_total: 0,
+            groups_num: 0,
         }
     }
 }
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
-struct GroupConvState {
-    data: data::Group,
+struct GroupConvState<S> {
+    data: data::Group<S>,
     records_num: usize,
     control_total: i64,
+    accounts_num: usize,
 }
-impl GroupConvState {
-    fn new(data: data::Group, records_num: usize) -> Self {
+impl<S> GroupConvState<S> {
+    fn new(data: data::Group<S>, records_num: usize) -> Self {
         GroupConvState {
             data,
             records_num,
             control_total: 0,
+            accounts_num: 0,
         }
     }
 }
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
-struct AccountConvState {
-    data: data::Account,
+struct AccountConvState<S> {
+    data: data::Account<S>,
     records_num: usize,
     control_total: i64,
+    transactions_num: usize,
 }
 
-impl Default for ConverterState {
+impl<S> Default for ConverterState<S> {
     fn default() -> Self {
         ConverterState::Fresh
     }
@@ -124,7 +182,19 @@ pub enum ConverterProgress {
     Account,
 }
 
-impl ConverterState {
+impl ConverterProgress {
+    // The record that has to come next before any other header or trailer can.
+    pub fn closing_record(self) -> RecordField {
+        match self {
+            ConverterProgress::Fresh => RecordField::FileHeader,
+            ConverterProgress::File => RecordField::FileTrailer,
+            ConverterProgress::Group => RecordField::GroupTrailer,
+            ConverterProgress::Account => RecordField::AccountTrailer,
+        }
+    }
+}
+
+impl<S> ConverterState<S> {
     pub fn progress(&self) -> ConverterProgress {
         match *self {
             ConverterState::Fresh => ConverterProgress::Fresh,
@@ -134,31 +204,43 @@ impl ConverterState {
         }
     }
 
-    fn unwrap_file(&self) -> &FileConvState {
+    // Physical records converted so far, in every level that's open.
+    fn records_num(&self) -> usize {
+        match *self {
+            ConverterState::Fresh => 0,
+            ConverterState::File(ref f) => f.records_num,
+            ConverterState::Group(ref f, ref g) => f.records_num + g.records_num,
+            ConverterState::Account(ref f, ref g, ref a) => {
+                f.records_num + g.records_num + a.records_num
+            }
+        }
+    }
+
+    fn unwrap_file(&self) -> &FileConvState<S> {
         match *self {
             ConverterState::File(ref f) => f,
             ref s => panic!("ConverterState::{:?} is not File", s.progress()),
         }
     }
-    fn unwrap_file_move(self) -> FileConvState {
+    fn unwrap_file_move(self) -> FileConvState<S> {
         match self {
             ConverterState::File(f) => f,
             s => panic!("ConverterState::{:?} is not File", s.progress()),
         }
     }
-    fn unwrap_group(&self) -> (&FileConvState, &GroupConvState) {
+    fn unwrap_group(&self) -> (&FileConvState<S>, &GroupConvState<S>) {
         match *self {
             ConverterState::Group(ref f, ref g) => (f, g),
             ref s => panic!("ConverterState::{:?} is not Group", s.progress()),
         }
     }
-    fn unwrap_group_move(self) -> (FileConvState, GroupConvState) {
+    fn unwrap_group_move(self) -> (FileConvState<S>, GroupConvState<S>) {
         match self {
             ConverterState::Group(f, g) => (f, g),
             s => panic!("ConverterState::{:?} is not Group", s.progress()),
         }
     }
-    fn unwrap_account(&self) -> (&FileConvState, &GroupConvState, &AccountConvState) {
+    fn unwrap_account(&self) -> (&FileConvState<S>, &GroupConvState<S>, &AccountConvState<S>) {
         match *self {
             ConverterState::Account(ref f, ref g, ref a) => (f, g, a),
             ref s => panic!("ConverterState::{:?} is not Account", s.progress()),
@@ -167,16 +249,16 @@ impl ConverterState {
     fn unwrap_account_mut(
         &mut self,
     ) -> (
-        &mut FileConvState,
-        &mut GroupConvState,
-        &mut AccountConvState,
+        &mut FileConvState<S>,
+        &mut GroupConvState<S>,
+        &mut AccountConvState<S>,
     ) {
         match *self {
             ConverterState::Account(ref mut f, ref mut g, ref mut a) => (f, g, a),
             ref s => panic!("ConverterState::{:?} is not Account", s.progress()),
         }
     }
-    fn unwrap_account_move(self) -> (FileConvState, GroupConvState, AccountConvState) {
+    fn unwrap_account_move(self) -> (FileConvState<S>, GroupConvState<S>, AccountConvState<S>) {
         match self {
             ConverterState::Account(f, g, a) => (f, g, a),
             s => panic!("ConverterState::{:?} is not Account", s.progress()),
@@ -191,7 +273,18 @@ pub enum ConvertError {
         record: usize,
         progress: ConverterProgress,
     },
+    // A header or trailer where `expected` has to come first, such as a 49 Account Trailer
+    // without an 03 Account Identifier. `record` counts physical records from 1.
+    UnbalancedRecords {
+        expected: RecordField,
+        found: RecordField,
+        record: usize,
+    },
     File(FileConvError),
+    Fragment(FragmentConvError),
+    // Amounts that add up past `i64`, which no trailer's control total can match. `record`
+    // counts physical records from 1.
+    ControlTotalOverflow { record: usize },
     Group { group: usize, err: GroupConvError },
     Account {
         group: usize,
@@ -206,16 +299,59 @@ pub enum ConvertError {
     },
 }
 
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ConvertError::RecordType { record, progress } => {
+                write!(f, "Unexpected record {} while in {:?}", record, progress)
+            }
+            ConvertError::UnbalancedRecords {
+                expected,
+                found,
+                record,
+            } => write!(f, "Expected {:?} but found {:?} at record {}", expected, found, record),
+            ConvertError::File(ref err) => write!(f, "Invalid file: {}", err),
+            ConvertError::Fragment(ref err) => write!(f, "Invalid fragment: {}", err),
+            ConvertError::ControlTotalOverflow { record } => {
+                write!(f, "Amounts add up past the largest control total at record {}", record)
+            }
+            ConvertError::Group { group, ref err } => write!(f, "Invalid group {}: {}", group, err),
+            ConvertError::Account {
+                group,
+                account,
+                ref err,
+            } => write!(f, "Invalid account {} in group {}: {}", account, group, err),
+            ConvertError::TransactionDetail {
+                group,
+                account,
+                transaction,
+                ref err,
+            } => {
+                write!(
+                    f,
+                    "Invalid transaction detail {} in group {}, account {}: {}",
+                    transaction,
+                    group,
+                    account,
+                    err
+                )
+            }
+        }
+    }
+}
+
+impl Error for ConvertError {}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
-pub enum ConverterOutput {
+pub enum ConverterOutput<S = String> {
     Active,
-    Ok(data::File),
+    Ok(data::File<S>),
     Err(ConvertError),
     Done,
 }
-impl From<Option<Result<Option<data::File>, ConvertError>>> for ConverterOutput {
-    fn from(file: Option<Result<Option<data::File>, ConvertError>>) -> Self {
+impl<S> From<Option<Result<Option<data::File<S>>, ConvertError>>> for ConverterOutput<S> {
+    fn from(file: Option<Result<Option<data::File<S>>, ConvertError>>) -> Self {
         match file {
             Some(Ok(None)) => ConverterOutput::Active,
             Some(Ok(Some(file))) => ConverterOutput::Ok(file),
@@ -224,8 +360,8 @@ impl From<Option<Result<Option<data::File>, ConvertError>>> for ConverterOutput
         }
     }
 }
-impl ConverterOutput {
-    pub fn expand(self) -> Option<Result<Option<data::File>, ConvertError>> {
+impl<S> ConverterOutput<S> {
+    pub fn expand(self) -> Option<Result<Option<data::File<S>>, ConvertError>> {
         match self {
             ConverterOutput::Active => Some(Ok(None)),
             ConverterOutput::Ok(file) => Some(Ok(Some(file))),
@@ -236,7 +372,126 @@ impl ConverterOutput {
 }
 
 impl Converter {
-    pub fn process<'a>(&mut self, record: ParsedRecord<'a>) -> ConverterOutput {
+    pub fn new() -> Self {
+        Converter::with_strings()
+    }
+
+    // Starts inside a file, for group records without the file header and trailer around them.
+    pub fn fragment() -> Self {
+        Converter {
+            state: Some(ConverterState::File(FileConvState::new(None, 0))),
+            ..Converter::default()
+        }
+    }
+
+    // Doesn't keep finished transaction details, accounts, or groups, for callers that handle
+    // each as it's converted. Only the latest transaction detail of an account is kept.
+    pub fn streaming() -> Self {
+        Converter {
+            retain: false,
+            ..Converter::default()
+        }
+    }
+}
+
+impl<S> Converter<S> {
+    // For a string type other than `String`, such as `Cow<str>` to borrow from the input.
+    pub fn with_strings() -> Self {
+        Converter {
+            state: Some(ConverterState::Fresh),
+            retain: true,
+            closed_account: None,
+            closed_group: None,
+            record_counts: RecordCountConvention::default(),
+            codes: data::CodeTable::builtin(),
+            lenient_funds: false,
+        }
+    }
+
+    pub fn record_count_convention(self, record_counts: RecordCountConvention) -> Self {
+        Converter {
+            record_counts,
+            ..self
+        }
+    }
+
+    // Transaction detail type codes are looked up in `codes`, so a bank's own codes can be read.
+    pub fn code_table(self, codes: data::CodeTable) -> Self {
+        Converter { codes, ..self }
+    }
+
+    // Funds type indicators the spec doesn't define are read as Z instead of being an error.
+    pub fn lenient_funds_types(self, lenient_funds: bool) -> Self {
+        Converter {
+            lenient_funds,
+            ..self
+        }
+    }
+
+    // The file, group, and account currently being converted. A fragment has no file.
+    pub fn file(&self) -> Option<&data::File<S>> {
+        match self.state {
+            Some(ConverterState::File(ref f)) |
+            Some(ConverterState::Group(ref f, _)) |
+            Some(ConverterState::Account(ref f, _, _)) => f.data.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn group(&self) -> Option<&data::Group<S>> {
+        match self.state {
+            Some(ConverterState::Group(_, ref g)) |
+            Some(ConverterState::Account(_, ref g, _)) => Some(&g.data),
+            _ => None,
+        }
+    }
+
+    pub fn account(&self) -> Option<&data::Account<S>> {
+        match self.state {
+            Some(ConverterState::Account(_, _, ref a)) => Some(&a.data),
+            _ => None,
+        }
+    }
+
+    // The account the last record closed, with its trailer's totals, for a converter that
+    // doesn't keep finished accounts.
+    pub fn closed_account(&self) -> Option<&data::Account<S>> {
+        self.closed_account.as_ref()
+    }
+
+    // The group the last record closed, without its accounts, for a converter that doesn't keep
+    // finished groups.
+    pub fn closed_group(&self) -> Option<&data::Group<S>> {
+        self.closed_group.as_ref()
+    }
+
+    // The groups converted so far, if the converter is between groups.
+    pub fn into_groups(self) -> Option<Vec<data::Group<S>>> {
+        match self.state {
+            Some(ConverterState::File(file)) => Some(file.groups),
+            _ => None,
+        }
+    }
+
+    pub fn process<'a>(&mut self, record: ParsedRecord<'a>) -> ConverterOutput<S>
+    where
+        S: From<&'a str>,
+    {
+        self.process_physical(record, 1)
+    }
+
+    // `physical` is the number of physical records (the record itself plus any 88
+    // continuations) that make up `record`, for checking trailer record counts.
+    pub fn process_physical<'a>(
+        &mut self,
+        record: ParsedRecord<'a>,
+        physical: usize,
+    ) -> ConverterOutput<S>
+    where
+        S: From<&'a str>,
+    {
+        self.closed_account = None;
+        self.closed_group = None;
         let progress = match self.state {
             Some(ref state) => state.progress(),
             None => return ConverterOutput::Done,
@@ -247,20 +502,15 @@ impl Converter {
                     ParsedRecord::FileHeader(fh) => {
                         match fh.convert() {
                             Ok(file) => {
-                                self.state =
-                                    Some(ConverterState::File(FileConvState::new(file, 1)));
+                                self.state = Some(
+                                    ConverterState::File(FileConvState::new(Some(file), physical)),
+                                );
                                 ConverterOutput::Active
                             }
                             Err(e) => ConverterOutput::Err(ConvertError::File(e)),
                         }
                     }
-                    _ => {
-                        self.state = None;
-                        ConverterOutput::Err(ConvertError::RecordType {
-                            record: 0,
-                            progress,
-                        })
-                    }
+                    record => self.unexpected(record.field(), progress),
                 }
             },
             ConverterProgress::File => {
@@ -269,14 +519,14 @@ impl Converter {
                         match gh.convert() {
                             Ok(group) => {
                                 let file = self.state.take().unwrap().unwrap_file_move();
-                                self.state = Some(
-                                    ConverterState::Group(file, GroupConvState::new(group, 1)),
-                                );
+                                self.state = Some(ConverterState::Group(
+                                    file,
+                                    GroupConvState::new(group, physical),
+                                ));
                                 ConverterOutput::Active
                             }
                             Err(err) => {
-                                let group_num =
-                                    self.state.as_ref().unwrap().unwrap_file().data.groups.len();
+                                let group_num = self.state.as_ref().unwrap().unwrap_file().groups_num;
                                 self.state = None;
                                 ConverterOutput::Err(ConvertError::Group {
                                     group: group_num,
@@ -286,12 +536,18 @@ impl Converter {
                         }
                     }
                     ParsedRecord::FileTrailer(ft) => {
-                        let (control_total, groups_num) = {
+                        let (control_total, groups_num, fragment) = {
                             let file = self.state.as_ref().unwrap().unwrap_file();
-                            (file.control_total, file.data.groups.len())
+                            (file.control_total, file.groups_num, file.data.is_none())
                         };
-                        // TODO verify records_num
-                        if ft.control_total != control_total {
+                        // The record count is checked by `File::process_validated`, which counts
+                        // the physical records itself.
+                        if fragment {
+                            self.state = None;
+                            ConverterOutput::Err(ConvertError::Fragment(
+                                FragmentConvError::FileTrailer,
+                            ))
+                        } else if ft.control_total != control_total {
                             self.state = None;
                             ConverterOutput::Err(ConvertError::File(FileConvError::ControlTotal {
                                 expected: ft.control_total,
@@ -304,30 +560,37 @@ impl Converter {
                                 actual: groups_num,
                             }))
                         } else {
-                            let file = self.state.take().unwrap().unwrap_file_move();
-                            ConverterOutput::Ok(file.data)
+                            let state = self.state.take().unwrap().unwrap_file_move();
+                            let mut file = state.data.unwrap();
+                            file.groups = state.groups;
+                            file.declared_control_total = Some(ft.control_total);
+                            file.declared_group_count = Some(ft.groups_num);
+                            file.declared_record_count = Some(ft.records_num);
+                            file.record_count = Some(state.records_num + physical);
+                            ConverterOutput::Ok(file)
                         }
                     }
-                    _ => {
-                        let record = self.state.as_ref().unwrap().unwrap_file().records_num;
-                        self.state = None;
-                        ConverterOutput::Err(ConvertError::RecordType { record, progress })
-                    }
+                    record => self.unexpected(record.field(), progress),
                 }
             }
             ConverterProgress::Group => {
                 match record {
                     ParsedRecord::AccountIdent(ai) => {
-                        match ai.convert() {
-                            Ok((account, control_total)) => {
+                        match ai.convert(self.lenient_funds) {
+                            Ok((_, None)) => {
+                                let record = self.state.take().unwrap().records_num() + 1;
+                                ConverterOutput::Err(ConvertError::ControlTotalOverflow { record })
+                            }
+                            Ok((account, Some(control_total))) => {
                                 let (file, group) = self.state.take().unwrap().unwrap_group_move();
                                 self.state = Some(ConverterState::Account(
                                     file,
                                     group,
                                     AccountConvState {
                                         data: account,
-                                        records_num: 1,
+                                        records_num: physical,
                                         control_total,
+                                        transactions_num: 0,
                                     },
                                 ));
                                 ConverterOutput::Active
@@ -335,7 +598,7 @@ impl Converter {
                             Err(err) => {
                                 let (group_num, account_num) = {
                                     let (file, group) = self.state.as_ref().unwrap().unwrap_group();
-                                    (file.data.groups.len(), group.data.accounts.len())
+                                    (file.groups_num, group.accounts_num)
                                 };
                                 ConverterOutput::Err(ConvertError::Account {
                                     group: group_num,
@@ -346,15 +609,15 @@ impl Converter {
                         }
                     }
                     ParsedRecord::GroupTrailer(gt) => {
-                        let (group, control_total, accounts_num) = {
+                        let (group, control_total, accounts_num, records_num) = {
                             let (file, group) = self.state.as_ref().unwrap().unwrap_group();
                             (
-                                file.data.groups.len(),
+                                file.groups_num,
                                 group.control_total,
-                                group.data.accounts.len(),
+                                group.accounts_num,
+                                self.record_counts.expected(group.records_num + physical),
                             )
                         };
-                        // TODO verify records_num
                         if gt.control_total != control_total {
                             self.state = None;
                             ConverterOutput::Err(ConvertError::Group {
@@ -373,32 +636,74 @@ impl Converter {
                                     actual: accounts_num,
                                 },
                             })
+                        } else if records_num.map_or(false, |n| n != gt.records_num) {
+                            self.state = None;
+                            ConverterOutput::Err(ConvertError::Group {
+                                group,
+                                err: GroupConvError::RecordsNum {
+                                    expected: gt.records_num,
+                                    actual: records_num.unwrap(),
+                                },
+                            })
                         } else {
-                            let (mut file, group) = self.state.take().unwrap().unwrap_group_move();
-                            file.data.groups.push(group.data);
-                            file.records_num += group.records_num + 1;
-                            file.control_total += group.control_total;
+                            let (mut file, mut group) =
+                                self.state.take().unwrap().unwrap_group_move();
+                            group.data.declared_control_total = Some(gt.control_total);
+                            group.data.declared_account_count = Some(gt.accounts_num);
+                            group.data.declared_record_count = Some(gt.records_num);
+                            group.data.record_count = Some(group.records_num + physical);
+                            if self.retain {
+                                file.groups.push(group.data);
+                            } else {
+                                self.closed_group = Some(group.data);
+                            }
+                            file.groups_num += 1;
+                            file.records_num += group.records_num + physical;
+                            file.control_total =
+                                match file.control_total.checked_add(group.control_total) {
+                                    Some(control_total) => control_total,
+                                    None => {
+                                        let record = file.records_num;
+                                        return ConverterOutput::Err(
+                                            ConvertError::ControlTotalOverflow { record },
+                                        );
+                                    }
+                                };
                             self.state = Some(ConverterState::File(file));
                             ConverterOutput::Active
                         }
                     }
-                    _ => {
-                        let record = self.state.as_ref().unwrap().unwrap_group().0.records_num;
-                        self.state = None;
-                        ConverterOutput::Err(ConvertError::RecordType { record, progress })
-                    }
+                    record => self.unexpected(record.field(), progress),
                 }
             }
             ConverterProgress::Account => {
                 match record {
                     ParsedRecord::TransactionDetail(td) => {
-                        match td.convert() {
+                        match td.convert(&self.codes, self.lenient_funds) {
                             Ok((transaction_detail, control_total)) => {
+                                let control_total = {
+                                    let (_file, _group, account) =
+                                        self.state.as_ref().unwrap().unwrap_account();
+                                    account.control_total.checked_add(control_total)
+                                };
+                                let control_total = match control_total {
+                                    Some(control_total) => control_total,
+                                    None => {
+                                        let record = self.state.take().unwrap().records_num() + 1;
+                                        return ConverterOutput::Err(
+                                            ConvertError::ControlTotalOverflow { record },
+                                        );
+                                    }
+                                };
                                 let (_file, _group, account) =
                                     self.state.as_mut().unwrap().unwrap_account_mut();
+                                if !self.retain {
+                                    account.data.transaction_details.clear();
+                                }
                                 account.data.transaction_details.push(transaction_detail);
-                                account.records_num += 1;
-                                account.control_total += control_total;
+                                account.transactions_num += 1;
+                                account.records_num += physical;
+                                account.control_total = control_total;
                                 ConverterOutput::Active
                             }
                             Err(err) => {
@@ -406,9 +711,9 @@ impl Converter {
                                     let (file, group, account) =
                                         self.state.as_ref().unwrap().unwrap_account();
                                     (
-                                        file.data.groups.len(),
-                                        group.data.accounts.len(),
-                                        account.data.transaction_details.len(),
+                                        file.groups_num,
+                                        group.accounts_num,
+                                        account.transactions_num,
                                     )
                                 };
                                 self.state = None;
@@ -422,16 +727,16 @@ impl Converter {
                         }
                     }
                     ParsedRecord::AccountTrailer(at) => {
-                        let (group, account, control_total) = {
+                        let (group, account, control_total, records_num) = {
                             let (file, group, account) =
                                 self.state.as_ref().unwrap().unwrap_account();
                             (
-                                file.data.groups.len(),
-                                group.data.accounts.len(),
+                                file.groups_num,
+                                group.accounts_num,
                                 account.control_total,
+                                self.record_counts.expected(account.records_num + physical),
                             )
                         };
-                        // TODO verify records_num
                         if at.control_total != control_total {
                             self.state = None;
                             ConverterOutput::Err(ConvertError::Account {
@@ -442,36 +747,100 @@ impl Converter {
                                     actual: control_total,
                                 },
                             })
+                        } else if records_num.map_or(false, |n| n != at.records_num) {
+                            self.state = None;
+                            ConverterOutput::Err(ConvertError::Account {
+                                group,
+                                account,
+                                err: AccountConvError::RecordsNum {
+                                    expected: at.records_num,
+                                    actual: records_num.unwrap(),
+                                },
+                            })
                         } else {
-                            let (file, mut group, account) =
+                            let (file, mut group, mut account) =
                                 self.state.take().unwrap().unwrap_account_move();
-                            group.data.accounts.push(account.data);
-                            group.records_num += account.records_num + 1;
-                            group.control_total += account.control_total;
+                            account.data.declared_control_total = Some(at.control_total);
+                            account.data.declared_record_count = Some(at.records_num);
+                            account.data.record_count = Some(account.records_num + physical);
+                            if self.retain {
+                                group.data.accounts.push(account.data);
+                            } else {
+                                self.closed_account = Some(account.data);
+                            }
+                            group.accounts_num += 1;
+                            group.records_num += account.records_num + physical;
+                            group.control_total =
+                                match group.control_total.checked_add(account.control_total) {
+                                    Some(control_total) => control_total,
+                                    None => {
+                                        let record = file.records_num + group.records_num;
+                                        return ConverterOutput::Err(
+                                            ConvertError::ControlTotalOverflow { record },
+                                        );
+                                    }
+                                };
                             self.state = Some(ConverterState::Group(file, group));
                             ConverterOutput::Active
                         }
                     }
-                    _ => {
-                        let record = self.state.as_ref().unwrap().unwrap_account().0.records_num;
-                        self.state = None;
-                        ConverterOutput::Err(ConvertError::RecordType { record, progress })
-                    }
+                    record => self.unexpected(record.field(), progress),
                 }
             }
         }
     }
 
-    pub fn fold<'a, I>(iter: &mut I) -> Result<data::File, Option<ConvertError>>
+    // Headers and trailers out of place mean the records are unbalanced. Other records are
+    // just of the wrong type.
+    fn unexpected(
+        &mut self,
+        found: RecordField,
+        progress: ConverterProgress,
+    ) -> ConverterOutput<S> {
+        let state = self.state.take().unwrap();
+        ConverterOutput::Err(match found {
+            RecordField::TransactionDetail => {
+                let record = match state {
+                    ConverterState::Fresh => 0,
+                    ConverterState::File(ref f) |
+                    ConverterState::Group(ref f, _) |
+                    ConverterState::Account(ref f, _, _) => f.records_num,
+                };
+                ConvertError::RecordType { record, progress }
+            }
+            found => ConvertError::UnbalancedRecords {
+                expected: progress.closing_record(),
+                found,
+                record: state.records_num() + 1,
+            },
+        })
+    }
+
+    pub fn fold<'a, I>(iter: &mut I) -> Result<data::File<S>, Option<ConvertError>>
     where
+        S: From<&'a str>,
         I: Iterator<Item = ast::ParsedRecord<'a>>,
     {
-        let mut converter = ast::convert::Converter::default();
+        Converter::fold_with_count(&mut iter.map(|r| (r, 1)))
+    }
+
+    // `fold` with the number of physical records each record was read from, so that 88
+    // Continuations are counted towards trailer record counts.
+    pub fn fold_with_count<'a, I>(iter: &mut I) -> Result<data::File<S>, Option<ConvertError>>
+    where
+        S: From<&'a str>,
+        I: Iterator<Item = (ast::ParsedRecord<'a>, usize)>,
+    {
+        let mut converter = Converter::with_strings();
         match iter.fold(
             ConverterOutput::Active,
-            |acc, r| match converter.process(r) {
-                ConverterOutput::Done => acc,
-                o => o,
+            |acc, (r, physical)| match acc {
+                // Keep the first error instead of whatever the following records run into.
+                ConverterOutput::Err(_) => acc,
+                _ => match converter.process_physical(r, physical) {
+                    ConverterOutput::Done => acc,
+                    o => o,
+                },
             },
         ) {
             ConverterOutput::Done => unreachable!(),
@@ -481,17 +850,78 @@ impl Converter {
         }
     }
 
-    pub fn fold_results<'a, E, I, O>(iter: &mut I, op: O) -> Result<data::File, E>
+    /// Converts records from any front-end into a file, stopping at the first error.
+    ///
+    /// Errors from `iter` are returned as they are, and the converter's own are mapped with
+    /// `op`: `Some` for a record that couldn't be converted, and `None` when the records ran out
+    /// before the file trailer. `fold_results_with_count` takes the number of physical records
+    /// each record was read from as well, and `fold_results_with` uses a converter set up
+    /// beforehand.
+    ///
+    /// ```
+    /// use baimax::ast::Record;
+    /// use baimax::ast::convert::Converter;
+    /// use baimax::ast::parse::Parsed;
+    /// use baimax::data::File;
+    /// use baimax::parse;
+    ///
+    /// let input = b"01,SENDER,RECEIVER,170601,1200,1,,,2/
+    /// 02,RECEIVER,SENDER,1,170601,,,2/
+    /// 03,12345,,010,1000,,/
+    /// 16,195,500,,,,WIRE IN
+    /// 49,1500,3/
+    /// 98,1500,1,5/
+    /// 99,1500,1,7/
+    /// ";
+    /// let raw_records = parse::file(input).to_result().unwrap();
+    /// let mut records = raw_records
+    ///     .iter()
+    ///     .map(|raw| Record::parse(raw).map_err(|e| format!("Couldn't parse field: {:?}", e)));
+    /// let file: File = Converter::fold_results(&mut records, |e| match e {
+    ///     Some(e) => e.to_string(),
+    ///     None => "File ended before its trailer".to_string(),
+    /// }).unwrap();
+    /// assert_eq!(file.groups[0].accounts[0].transaction_details.len(), 1);
+    /// ```
+    pub fn fold_results<'a, E, I, O>(iter: &mut I, op: O) -> Result<data::File<S>, E>
     where
+        S: From<&'a str>,
         I: Iterator<Item = Result<ast::ParsedRecord<'a>, E>>,
         O: FnOnce(Option<ConvertError>) -> E,
     {
-        let mut converter = ast::convert::Converter::default();
+        Converter::fold_results_with_count(&mut iter.map(|r| r.map(|r| (r, 1))), op)
+    }
+
+    pub fn fold_results_with_count<'a, E, I, O>(iter: &mut I, op: O) -> Result<data::File<S>, E>
+    where
+        S: From<&'a str>,
+        I: Iterator<Item = Result<(ast::ParsedRecord<'a>, usize), E>>,
+        O: FnOnce(Option<ConvertError>) -> E,
+    {
+        Converter::with_strings().fold_results_with(iter, op)
+    }
+
+    // `fold_results_with_count` with this converter, for one that isn't fresh or has other
+    // settings.
+    pub fn fold_results_with<'a, E, I, O>(
+        mut self,
+        iter: &mut I,
+        op: O,
+    ) -> Result<data::File<S>, E>
+    where
+        S: From<&'a str>,
+        I: Iterator<Item = Result<(ast::ParsedRecord<'a>, usize), E>>,
+        O: FnOnce(Option<ConvertError>) -> E,
+    {
         match iter.fold_results(
             ConverterOutput::Active,
-            |acc, r| match converter.process(r) {
-                ConverterOutput::Done => acc,
-                o => o,
+            |acc, (r, physical)| match acc {
+                // Keep the first error instead of whatever the following records run into.
+                ConverterOutput::Err(_) => acc,
+                _ => match self.process_physical(r, physical) {
+                    ConverterOutput::Done => acc,
+                    o => o,
+                },
             },
         ) {
             Ok(ConverterOutput::Done) => unreachable!(),
@@ -512,15 +942,63 @@ pub enum FileConvError {
     RecordsNum { expected: usize, actual: usize },
 }
 
+impl fmt::Display for FileConvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FileConvError::Creation(ref e) => write!(f, "creation date: {}", e),
+            FileConvError::ControlTotal { expected, actual } => {
+                write!(f, "control total is {} but the amounts add up to {}", expected, actual)
+            }
+            FileConvError::GroupsNum { expected, actual } => {
+                write!(f, "trailer counts {} groups but found {}", expected, actual)
+            }
+            FileConvError::RecordsNum { expected, actual } => {
+                write!(f, "trailer counts {} records but found {}", expected, actual)
+            }
+        }
+    }
+}
+
+// Group records converted without the file header and trailer around them.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum FragmentConvError {
+    FileTrailer,
+    GroupsNum { expected: usize, actual: usize },
+}
+
+impl fmt::Display for FragmentConvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FragmentConvError::FileTrailer => write!(f, "file trailer without a file header"),
+            FragmentConvError::GroupsNum { expected, actual } => {
+                write!(f, "expected {} groups but found {}", expected, actual)
+            }
+        }
+    }
+}
+
 impl<'a> ast::ParsedFileHeader<'a> {
-    fn convert(&self) -> Result<data::File, FileConvError> {
+    fn convert<S: From<&'a str>>(&self) -> Result<data::File<S>, FileConvError> {
         Ok(data::File {
-            sender: data::Party(self.sender_ident.to_owned()),
-            receiver: data::Party(self.receiver_ident.to_owned()),
-            creation: chrono_date_time(&self.creation_date, &self.creation_time)
+            sender: data::Party(self.sender_ident.into()),
+            receiver: data::Party(self.receiver_ident.into()),
+            creation: self.creation_time
+                .as_ref()
+                .map_or_else(
+                    || chrono_date(&self.creation_date).map(BaiDateTime::Date),
+                    |time| chrono_date_time(&self.creation_date, time),
+                )
                 .map_err(FileConvError::Creation)?,
             ident: data::FileIdent(self.ident_num),
+            physical_record_len: self.physical_record_len,
+            block_size: self.block_size,
+            format_version: self.version_number,
             groups: Vec::new(),
+            declared_control_total: None,
+            declared_group_count: None,
+            declared_record_count: None,
+            record_count: None,
         })
     }
 }
@@ -537,12 +1015,31 @@ pub enum GroupConvError {
     RecordsNum { expected: usize, actual: usize },
 }
 
+impl fmt::Display for GroupConvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GroupConvError::Status => write!(f, "invalid group status"),
+            GroupConvError::AsOf(ref e) => write!(f, "as-of date: {}", e),
+            GroupConvError::Currency(ref currency) => write!(f, "unknown currency {:?}", currency),
+            GroupConvError::AsOfDateMod => write!(f, "invalid as-of date modifier"),
+            GroupConvError::ControlTotal { expected, actual } => {
+                write!(f, "control total is {} but the amounts add up to {}", expected, actual)
+            }
+            GroupConvError::AccountsNum { expected, actual } => {
+                write!(f, "trailer counts {} accounts but found {}", expected, actual)
+            }
+            GroupConvError::RecordsNum { expected, actual } => {
+                write!(f, "trailer counts {} records but found {}", expected, actual)
+            }
+        }
+    }
+}
+
 impl<'a> ast::ParsedGroupHeader<'a> {
-    fn convert(&self) -> Result<data::Group, GroupConvError> {
+    fn convert<S: From<&'a str>>(&self) -> Result<data::Group<S>, GroupConvError> {
         Ok(data::Group {
-            ultimate_receiver: self.ultimate_receiver_ident
-                .map(|s| data::Party(s.to_owned())),
-            originator: self.originator_ident.map(|s| data::Party(s.to_owned())),
+            ultimate_receiver: self.ultimate_receiver_ident.map(|s| data::Party(s.into())),
+            originator: self.originator_ident.map(|s| data::Party(s.into())),
             status: self.status.try_into().or(Err(GroupConvError::Status))?,
             as_of: {
                 chrono_date_or_time(&self.as_of_date, self.as_of_time.as_ref())
@@ -558,6 +1055,10 @@ impl<'a> ast::ParsedGroupHeader<'a> {
                     m.try_into().or(Err(GroupConvError::AsOfDateMod)).map(Some)
                 })?,
             accounts: Vec::new(),
+            declared_control_total: None,
+            declared_account_count: None,
+            declared_record_count: None,
+            record_count: None,
         })
     }
 }
@@ -571,12 +1072,33 @@ pub enum AccountConvError {
     RecordsNum { expected: usize, actual: usize },
 }
 
+impl fmt::Display for AccountConvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AccountConvError::Currency(ref currency) => {
+                write!(f, "unknown currency {:?}", currency)
+            }
+            AccountConvError::AccountInfo(i, ref e) => write!(f, "account info {}: {}", i, e),
+            AccountConvError::ControlTotal { expected, actual } => {
+                write!(f, "control total is {} but the amounts add up to {}", expected, actual)
+            }
+            AccountConvError::RecordsNum { expected, actual } => {
+                write!(f, "trailer counts {} records but found {}", expected, actual)
+            }
+        }
+    }
+}
+
 impl<'a> ast::ParsedAccountIdent<'a> {
-    fn convert(&self) -> Result<(data::Account, i64), AccountConvError> {
-        let (infos, control_total) = convert_infos(&self.infos)
+    // The control total is `None` if the amounts add up past `i64`.
+    fn convert<S: From<&'a str>>(
+        &self,
+        lenient_funds: bool,
+    ) -> Result<(data::Account<S>, Option<i64>), AccountConvError> {
+        let (infos, control_total) = convert_infos(&self.infos, lenient_funds)
             .map_err(|(i, e)| AccountConvError::AccountInfo(i, e))?;
         let account = data::Account {
-            customer_account: data::AccountNumber(self.customer_account_num.to_owned()),
+            customer_account: data::AccountNumber(self.customer_account_num.into()),
             currency: self.currency.map_or(Ok(None), |s| {
                 s.parse::<penny::Currency>()
                     .map(Some)
@@ -584,6 +1106,9 @@ impl<'a> ast::ParsedAccountIdent<'a> {
             })?,
             infos: infos,
             transaction_details: Vec::new(),
+            declared_control_total: None,
+            declared_record_count: None,
+            record_count: None,
         };
         Ok((account, control_total))
     }
@@ -600,14 +1125,28 @@ pub enum AccountInfoConvError {
     Funds(FundsTypeConvError),
 }
 
+impl fmt::Display for AccountInfoConvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AccountInfoConvError::NoCode => write!(f, "amount without a type code"),
+            AccountInfoConvError::InvalidCode => write!(f, "unknown type code"),
+            AccountInfoConvError::StatusItemCount => write!(f, "status with an item count"),
+            AccountInfoConvError::StatusFunds => write!(f, "status with a funds type"),
+            AccountInfoConvError::SummaryNegativeAmount => write!(f, "negative summary amount"),
+            AccountInfoConvError::Funds(ref e) => write!(f, "funds type: {}", e),
+        }
+    }
+}
+
 fn convert_infos(
     pinfos: &[ast::ParsedAccountInfo],
-) -> Result<(Vec<data::AccountInfo>, i64), (usize, AccountInfoConvError)> {
-    let mut control_total = 0;
+    lenient_funds: bool,
+) -> Result<(Vec<data::AccountInfo>, Option<i64>), (usize, AccountInfoConvError)> {
+    let mut control_total = Some(0i64);
     let mut infos = Vec::with_capacity(pinfos.len());
     for (i, pi) in pinfos.iter().enumerate() {
-        pi.convert().map_err(|e| (i, e))?.map(|(i, t)| {
-            control_total += t;
+        pi.convert(lenient_funds).map_err(|e| (i, e))?.map(|(i, t)| {
+            control_total = control_total.and_then(|total| total.checked_add(t));
             infos.push(i);
         });
     }
@@ -615,11 +1154,14 @@ fn convert_infos(
 }
 
 impl ast::ParsedAccountInfo {
-    fn convert(&self) -> Result<Option<(data::AccountInfo, i64)>, AccountInfoConvError> {
+    fn convert(
+        &self,
+        lenient_funds: bool,
+    ) -> Result<Option<(data::AccountInfo, i64)>, AccountInfoConvError> {
         use data::AccountInfo as AI;
         use self::AccountInfoConvError as CE;
 
-        let mut control_total: i64 = 0;
+        let mut control_total = 0;
         let info = match (
             self.type_code,
             self.amount,
@@ -633,7 +1175,7 @@ impl ast::ParsedAccountInfo {
                     Some(AI::Status {
                         code: code,
                         funds: funds
-                            .map_or(Ok(None), |f| f.convert().map(Some))
+                            .map_or(Ok(None), |f| f.convert(lenient_funds).map(Some))
                             .map_err(CE::Funds)?,
                         amount: {
                             if let Some(a) = amount {
@@ -653,7 +1195,7 @@ impl ast::ParsedAccountInfo {
                         })?,
                         item_count: item_count,
                         funds: funds
-                            .map_or(Ok(None), |f| f.convert().map(Some))
+                            .map_or(Ok(None), |f| f.convert(lenient_funds).map(Some))
                             .map_err(CE::Funds)?,
                     })
                 } else {
@@ -671,11 +1213,24 @@ impl ast::ParsedAccountInfo {
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub enum FundsTypeConvError {
     ValueDated(ChronoError),
-    DistributedAvailDNum(usize),
+    DistributedAvailDNum { expected: usize, actual: usize },
+    Unrecognized,
+}
+
+impl fmt::Display for FundsTypeConvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FundsTypeConvError::ValueDated(ref e) => write!(f, "value date: {}", e),
+            FundsTypeConvError::DistributedAvailDNum { expected, actual } => {
+                write!(f, "{} distributions declared but found {}", expected, actual)
+            }
+            FundsTypeConvError::Unrecognized => write!(f, "unrecognized indicator"),
+        }
+    }
 }
 
 impl ast::ParsedFundsType {
-    fn convert(&self) -> Result<data::FundsType, FundsTypeConvError> {
+    fn convert(&self, lenient: bool) -> Result<data::FundsType, FundsTypeConvError> {
         use ast::ParsedFundsType as PFT;
         use ast::data::FundsType as FT;
         use self::FundsTypeConvError as CE;
@@ -704,7 +1259,10 @@ impl ast::ParsedFundsType {
             PFT::DistributedAvailD { num, ref dists } => {
                 let ndists = dists.len();
                 if num != ndists {
-                    return Err(CE::DistributedAvailDNum(ndists));
+                    return Err(CE::DistributedAvailDNum {
+                        expected: num,
+                        actual: ndists,
+                    });
                 }
                 FT::DistributedAvailD(
                     dists
@@ -717,6 +1275,8 @@ impl ast::ParsedFundsType {
                         .collect(),
                 )
             }
+            PFT::Other(_) if lenient => FT::Unknown,
+            PFT::Other(_) => return Err(CE::Unrecognized),
         })
     }
 }
@@ -729,11 +1289,30 @@ pub enum TransactionDetailConvError {
     Funds(FundsTypeConvError),
 }
 
+impl fmt::Display for TransactionDetailConvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TransactionDetailConvError::NotTransactionDetailOrAccountTrailer => {
+                write!(f, "expected a transaction detail or account trailer")
+            }
+            TransactionDetailConvError::DetailCode(code) => {
+                write!(f, "unknown detail type code {}", code)
+            }
+            TransactionDetailConvError::Funds(ref e) => write!(f, "funds type: {}", e),
+        }
+    }
+}
+
 impl<'a> ast::ParsedTransactionDetail<'a> {
-    fn convert(self) -> Result<(data::TransactionDetail, i64), TransactionDetailConvError> {
-        let mut control_total: i64 = 0;
+    fn convert<S: From<&'a str>>(
+        self,
+        codes: &data::CodeTable,
+        lenient_funds: bool,
+    ) -> Result<(data::TransactionDetail<S>, i64), TransactionDetailConvError> {
+        let mut control_total = 0;
         let transaction_detail = data::TransactionDetail {
-            code: data::DetailCode::try_from(self.type_code)
+            code: codes
+                .detail_code(self.type_code)
                 .map_err(TransactionDetailConvError::DetailCode)?,
             amount: {
                 if let Some(a) = self.amount {
@@ -743,13 +1322,14 @@ impl<'a> ast::ParsedTransactionDetail<'a> {
             },
             funds: self.funds_type
                 .as_ref()
-                .map_or(Ok(None), |ft| ft.convert().map(Some))
+                .map_or(Ok(None), |ft| ft.convert(lenient_funds).map(Some))
                 .map_err(TransactionDetailConvError::Funds)?,
-            bank_ref_num: self.bank_ref_num.map(|s| data::ReferenceNum(s.to_owned())),
-            customer_ref_num: self.customer_ref_num
-                .map(|s| data::ReferenceNum(s.to_owned())),
+            bank_ref_num: self.bank_ref_num.map(|s| data::ReferenceNum(s.into())),
+            customer_ref_num: self.customer_ref_num.map(|s| data::ReferenceNum(s.into())),
+            extensions: Vec::new(),
             text: self.text
                 .map(|v| v.into_iter().map(String::from).collect::<Vec<_>>()),
+            raw_record: None,
         };
         Ok((transaction_detail, control_total))
     }
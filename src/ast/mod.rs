@@ -82,11 +82,12 @@ parsed! {
         pub SenderIdent(sender_ident): &'a [u8] => &'a str,
         pub ReceiverIdent(receiver_ident): &'a [u8] => &'a str,
         pub CreationDate(creation_date): &'a [u8] => Date,
-        pub CreationTime(creation_time): &'a [u8] => Time,
+        // Optional because some banks only send the date.
+        pub CreationTime(creation_time): Option<&'a [u8]> => Option<Time>,
         pub IdentNum(ident_num): &'a [u8] => u32,
         pub PhysicalRecordLen(physical_record_len): Option<&'a [u8]> => Option<u16>,
         pub BlockSize(block_size): Option<&'a [u8]> => Option<u16>,
-        pub VersionNumber(version_number): &'a [u8] => (),
+        pub VersionNumber(version_number): Option<&'a [u8]> => Option<u8>,
     }
     pub struct GroupHeader[GroupHeaderField] (RawGroupHeader<'a> => ParsedGroupHeader<'a>) {
         // 02
@@ -183,6 +184,8 @@ pub enum RawFundsType<'a> {
         num: &'a [u8],
         dists: Vec<RawDistributedAvailDistribution<'a>>,
     },
+    // An indicator the spec doesn't define.
+    Other(&'a [u8]),
 }
 #[derive(Debug, Clone)]
 pub enum ParsedFundsType {
@@ -207,13 +210,14 @@ pub enum ParsedFundsType {
         num: usize,
         dists: Vec<ParsedDistributedAvailDistribution>,
     },
+    Other(String),
 }
 
 #[derive(Debug, Clone)]
 pub struct Record<'a> {
     phantom: PhantomData<&'a ()>,
 }
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub enum RecordField {
     FileHeader,
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use super::*;
 
 pub trait Parsed {
@@ -16,6 +18,36 @@ pub enum ParseError<T: Parsed + ?Sized> {
     Format(T::Field),
     Error(T::Field, T::Err),
 }
+impl<T: Parsed + ?Sized> fmt::Display for ParseError<T>
+where
+    T::Field: fmt::Debug,
+    T::Err: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::Utf8(ref field, ref e) => write!(f, "{:?} isn't UTF-8: {}", field, e),
+            ParseError::Int(ref field, ref e) => write!(f, "{:?} isn't a number: {}", field, e),
+            ParseError::Format(ref field) => write!(f, "{:?} is badly formatted", field),
+            ParseError::Error(ref field, ref e) => write!(f, "{:?}: {}", field, e),
+        }
+    }
+}
+impl<T: Parsed + ?Sized> ParseError<T> {
+    // Rebuilds the error for another type with the same fields, which is how errors drop the
+    // lifetime of the input they were parsed from.
+    pub fn map_err<U, F>(self, f: F) -> ParseError<U>
+    where
+        U: Parsed<Field = T::Field> + ?Sized,
+        F: FnOnce(T::Err) -> U::Err,
+    {
+        match self {
+            ParseError::Utf8(field, e) => ParseError::Utf8(field, e),
+            ParseError::Int(field, e) => ParseError::Int(field, e),
+            ParseError::Format(field) => ParseError::Format(field),
+            ParseError::Error(field, e) => ParseError::Error(field, f(e)),
+        }
+    }
+}
 fn parse_str<T: Parsed>(i: &[u8], f: T::Field) -> Result<&str, ParseError<T>> {
     str::from_utf8(i).map_err(|e| ParseError::Utf8(f, e))
 }
@@ -34,7 +66,8 @@ where
     T::Field: Copy,
     P: str::FromStr<Err = num::ParseIntError>,
 {
-    i.parse::<P>().map_err(|e| ParseError::Int(f, e))
+    // Some banks pad numbers with spaces.
+    i.trim_matches(' ').parse::<P>().map_err(|e| ParseError::Int(f, e))
 }
 fn parse_strfrom<T, P, F>(i: &[u8], f: T::Field, fs: F) -> Result<P, ParseError<T>>
 where
@@ -75,7 +108,11 @@ where
     T::Field: Copy,
     P: str::FromStr<Err = num::ParseIntError>,
 {
-    i.map_or(Ok(None), |s| parse_strint(s, f).map(Some))
+    // A field holding only spaces is as absent as an empty one; an explicit "0" is still a zero.
+    match i {
+        Some(s) if s.iter().any(|&c| c != b' ') => parse_strint(s, f).map(Some),
+        _ => Ok(None),
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -88,6 +125,35 @@ pub enum RecordError<'a> {
     GroupTrailer(ParseError<GroupTrailer<'a>>),
     FileTrailer(ParseError<FileTrailer<'a>>),
 }
+impl<'a> fmt::Display for RecordError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RecordError::FileHeader(ref e) => write!(f, "{}", e),
+            RecordError::GroupHeader(ref e) => write!(f, "{}", e),
+            RecordError::AccountIdent(ref e) => write!(f, "{}", e),
+            RecordError::TransactionDetail(ref e) => write!(f, "{}", e),
+            RecordError::AccountTrailer(ref e) => write!(f, "{}", e),
+            RecordError::GroupTrailer(ref e) => write!(f, "{}", e),
+            RecordError::FileTrailer(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+impl<'a> RecordError<'a> {
+    pub fn into_static(self) -> RecordError<'static> {
+        use self::RecordError as E;
+        match self {
+            E::FileHeader(e) => E::FileHeader(e.map_err(|e| e)),
+            E::GroupHeader(e) => E::GroupHeader(e.map_err(|e| e)),
+            E::AccountIdent(e) => E::AccountIdent(e.map_err(AccountIdentError::into_static)),
+            E::TransactionDetail(e) => {
+                E::TransactionDetail(e.map_err(TransactionDetailError::into_static))
+            }
+            E::AccountTrailer(e) => E::AccountTrailer(e.map_err(|e| e)),
+            E::GroupTrailer(e) => E::GroupTrailer(e.map_err(|e| e)),
+            E::FileTrailer(e) => E::FileTrailer(e.map_err(|e| e)),
+        }
+    }
+}
 impl<'a> Parsed for Record<'a> {
     type Raw = RawRecord<'a>;
     type Parsed = ParsedRecord<'a>;
@@ -153,6 +219,14 @@ pub enum FileHeaderError {
     Date(DateError),
     Time(TimeError),
 }
+impl fmt::Display for FileHeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FileHeaderError::Date(ref e) => write!(f, "{}", e),
+            FileHeaderError::Time(ref e) => write!(f, "{}", e),
+        }
+    }
+}
 impl<'a> Parsed for FileHeader<'a> {
     type Raw = RawFileHeader<'a>;
     type Parsed = ParsedFileHeader<'a>;
@@ -160,22 +234,17 @@ impl<'a> Parsed for FileHeader<'a> {
     type Err = FileHeaderError;
 
     fn parse(raw: &Self::Raw) -> Result<Self::Parsed, ParseError<Self>> {
-        use self::ParseError as PE;
         use self::FileHeaderField as F;
         use self::FileHeaderError as E;
         Ok(ParsedFileHeader {
             sender_ident: parse_str(raw.sender_ident, F::SenderIdent)?,
             receiver_ident: parse_str(raw.receiver_ident, F::ReceiverIdent)?,
             creation_date: parse_strfrom(raw.creation_date, F::CreationDate, E::Date)?,
-            creation_time: parse_strfrom(raw.creation_time, F::CreationTime, E::Time)?,
+            creation_time: parse_optstrfrom(raw.creation_time, F::CreationTime, E::Time)?,
             ident_num: parse_strint(raw.ident_num, F::IdentNum)?,
             physical_record_len: parse_optstrint(raw.physical_record_len, F::PhysicalRecordLen)?,
             block_size: parse_optstrint(raw.block_size, F::BlockSize)?,
-            version_number: if parse_str(raw.version_number, F::VersionNumber)? == "2" {
-                ()
-            } else {
-                return Err(PE::Format(F::VersionNumber));
-            },
+            version_number: parse_optstrint(raw.version_number, F::VersionNumber)?,
         })
     }
 }
@@ -185,6 +254,14 @@ pub enum GroupHeaderError {
     Date(DateError),
     Time(TimeError),
 }
+impl fmt::Display for GroupHeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GroupHeaderError::Date(ref e) => write!(f, "{}", e),
+            GroupHeaderError::Time(ref e) => write!(f, "{}", e),
+        }
+    }
+}
 impl<'a> Parsed for GroupHeader<'a> {
     type Raw = RawGroupHeader<'a>;
     type Parsed = ParsedGroupHeader<'a>;
@@ -203,7 +280,7 @@ impl<'a> Parsed for GroupHeader<'a> {
             status: parse_strint(raw.status, F::Status)?,
             as_of_date: parse_strfrom(raw.as_of_date, F::AsOfDate, E::Date)?,
             as_of_time: parse_optstrfrom(raw.as_of_time, F::AsOfTime, E::Time)?,
-            currency: parse_optstr(raw.currency, F::AsOfTime)?,
+            currency: parse_optstr(raw.currency, F::Currency)?,
             as_of_date_mod: parse_optstrint(raw.as_of_date_mod, F::AsOfDateMod)?,
         })
     }
@@ -213,6 +290,22 @@ impl<'a> Parsed for GroupHeader<'a> {
 pub enum AccountIdentError<'a> {
     Info(usize, ParseError<AccountInfo<'a>>),
 }
+impl<'a> fmt::Display for AccountIdentError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AccountIdentError::Info(i, ref e) => write!(f, "account info {}: {}", i, e),
+        }
+    }
+}
+impl<'a> AccountIdentError<'a> {
+    pub fn into_static(self) -> AccountIdentError<'static> {
+        match self {
+            AccountIdentError::Info(i, e) => {
+                AccountIdentError::Info(i, e.map_err(AccountInfoError::into_static))
+            }
+        }
+    }
+}
 impl<'a> Parsed for AccountIdent<'a> {
     type Raw = RawAccountIdent<'a>;
     type Parsed = ParsedAccountIdent<'a>;
@@ -244,6 +337,29 @@ pub enum TransactionDetailError<'a> {
     FundsType(ParseError<FundsType<'a>>),
     Text(usize, str::Utf8Error),
 }
+impl<'a> fmt::Display for TransactionDetailError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TransactionDetailError::OversizedAmount(amount) => {
+                write!(f, "amount {} is too large", amount)
+            }
+            TransactionDetailError::FundsType(ref e) => write!(f, "{}", e),
+            TransactionDetailError::Text(i, ref e) => {
+                write!(f, "text line {} isn't UTF-8: {}", i, e)
+            }
+        }
+    }
+}
+impl<'a> TransactionDetailError<'a> {
+    pub fn into_static(self) -> TransactionDetailError<'static> {
+        use self::TransactionDetailError as E;
+        match self {
+            E::OversizedAmount(amount) => E::OversizedAmount(amount),
+            E::FundsType(e) => E::FundsType(e.map_err(FundsTypeError::into_static)),
+            E::Text(i, e) => E::Text(i, e),
+        }
+    }
+}
 impl<'a> Parsed for TransactionDetail<'a> {
     type Raw = RawTransactionDetail<'a>;
     type Parsed = ParsedTransactionDetail<'a>;
@@ -343,6 +459,16 @@ pub enum DateError {
     Month,
     Day,
 }
+impl fmt::Display for DateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            DateError::All => "not a YYMMDD date",
+            DateError::Year => "invalid year",
+            DateError::Month => "invalid month",
+            DateError::Day => "invalid day",
+        })
+    }
+}
 named!(
     date<&[u8], (&[u8], &[u8], &[u8]), DateError>,
     do_parse!(
@@ -376,6 +502,15 @@ pub enum TimeError {
     Hour,
     Minute,
 }
+impl fmt::Display for TimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            TimeError::All => "not an HHMM time",
+            TimeError::Hour => "invalid hour",
+            TimeError::Minute => "invalid minute",
+        })
+    }
+}
 named!(
     time<&[u8], (&[u8], &[u8]), TimeError>,
     do_parse!(
@@ -405,6 +540,22 @@ impl str::FromStr for Time {
 pub enum AccountInfoError<'a> {
     FundsType(ParseError<FundsType<'a>>),
 }
+impl<'a> fmt::Display for AccountInfoError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AccountInfoError::FundsType(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+impl<'a> AccountInfoError<'a> {
+    pub fn into_static(self) -> AccountInfoError<'static> {
+        match self {
+            AccountInfoError::FundsType(e) => {
+                AccountInfoError::FundsType(e.map_err(FundsTypeError::into_static))
+            }
+        }
+    }
+}
 impl<'a> Parsed for AccountInfo<'a> {
     type Raw = RawAccountInfo<'a>;
     type Parsed = ParsedAccountInfo;
@@ -434,6 +585,27 @@ pub enum FundsTypeError<'a> {
     Time(TimeError),
     DistributedAvailDDist(usize, ParseError<DistributedAvailDistribution<'a>>),
 }
+impl<'a> fmt::Display for FundsTypeError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FundsTypeError::Date(ref e) => write!(f, "{}", e),
+            FundsTypeError::Time(ref e) => write!(f, "{}", e),
+            FundsTypeError::DistributedAvailDDist(i, ref e) => {
+                write!(f, "distribution {}: {}", i, e)
+            }
+        }
+    }
+}
+impl<'a> FundsTypeError<'a> {
+    pub fn into_static(self) -> FundsTypeError<'static> {
+        use self::FundsTypeError as E;
+        match self {
+            E::Date(e) => E::Date(e),
+            E::Time(e) => E::Time(e),
+            E::DistributedAvailDDist(i, e) => E::DistributedAvailDDist(i, e.map_err(|e| e)),
+        }
+    }
+}
 impl<'a> Parsed for FundsType<'a> {
     type Raw = RawFundsType<'a>;
     type Parsed = ParsedFundsType;
@@ -490,6 +662,7 @@ impl<'a> Parsed for FundsType<'a> {
                     },
                 }
             }
+            R::Other(indicator) => P::Other(String::from_utf8_lossy(indicator).into_owned()),
         })
     }
 }
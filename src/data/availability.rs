@@ -0,0 +1,144 @@
+use std::cmp;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use penny::{Currency, Money};
+
+use super::{Account, AccountInfo, AccountStatus, FundsType, StatusCode, TransactionDetail};
+
+// How "n days" of availability are counted from the as-of date. Business days only skip
+// weekends, as bank holidays aren't known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AvailabilityDays {
+    Calendar,
+    Business,
+}
+
+impl Default for AvailabilityDays {
+    fn default() -> Self {
+        AvailabilityDays::Calendar
+    }
+}
+
+impl AvailabilityDays {
+    pub fn add(self, date: NaiveDate, days: u32) -> NaiveDate {
+        match self {
+            AvailabilityDays::Calendar => date + Duration::days(days as i64),
+            AvailabilityDays::Business => {
+                let mut date = date;
+                let mut days = days;
+                while days > 0 {
+                    date = date.succ();
+                    match date.weekday() {
+                        Weekday::Sat | Weekday::Sun => {}
+                        _ => days -= 1,
+                    }
+                }
+                date
+            }
+        }
+    }
+}
+
+impl TransactionDetail {
+    pub fn availability_schedule(&self, as_of: NaiveDate) -> Vec<(NaiveDate, i64)> {
+        self.availability_schedule_in(as_of, AvailabilityDays::default())
+    }
+
+    // Dates funds become available on, relative to the group's as-of date. Empty when there's no
+    // amount or the funds type is unknown. "Two or more days" is taken as two, and distributed
+    // availability amounts are listed as given, without checking them against the total.
+    pub fn availability_schedule_in(
+        &self,
+        as_of: NaiveDate,
+        days: AvailabilityDays,
+    ) -> Vec<(NaiveDate, i64)> {
+        match self.amount {
+            Some(amount) => schedule(amount, &self.funds, as_of, days),
+            None => Vec::new(),
+        }
+    }
+}
+
+fn schedule(
+    amount: i64,
+    funds: &Option<FundsType>,
+    as_of: NaiveDate,
+    days: AvailabilityDays,
+) -> Vec<(NaiveDate, i64)> {
+    match FundsType::effective(funds) {
+        FundsType::Unknown => Vec::new(),
+        FundsType::ImmediateAvail => vec![(as_of, amount)],
+        FundsType::OneDayAvail => vec![(days.add(as_of, 1), amount)],
+        FundsType::TwoOrMoreDaysAvail => vec![(days.add(as_of, 2), amount)],
+        FundsType::DistributedAvailS {
+            immediate,
+            one_day,
+            more_than_one_day,
+        } => vec![(0, immediate), (1, one_day), (2, more_than_one_day)]
+            .into_iter()
+            .filter_map(|(n, amount)| amount.map(|amount| (days.add(as_of, n), amount)))
+            .collect(),
+        FundsType::ValueDated(date) => vec![(date.date(), amount)],
+        FundsType::DistributedAvailD(dists) => dists
+            .iter()
+            .map(|dist| (days.add(as_of, dist.days), dist.amount))
+            .collect(),
+    }
+}
+
+// How much of an amount is available by a date. Whatever the schedule doesn't account for,
+// including all of it when the funds type is unknown, is taken as immediately available.
+fn available_by(
+    amount: i64,
+    funds: &Option<FundsType>,
+    as_of: NaiveDate,
+    date: NaiveDate,
+    days: AvailabilityDays,
+) -> i64 {
+    let schedule = schedule(amount, funds, as_of, days);
+    let scheduled = schedule.iter().fold(0i64, |sum, &(_, a)| sum.saturating_add(a));
+    schedule
+        .into_iter()
+        .filter(|&(available, _)| available <= date)
+        .fold(amount.saturating_sub(scheduled), |sum, (_, a)| sum.saturating_add(a))
+}
+
+impl<S> Account<S> {
+    pub fn available_as_of(&self, as_of: NaiveDate, date: NaiveDate, cur: Currency) -> Money {
+        self.available_as_of_in(as_of, date, cur, AvailabilityDays::default())
+    }
+
+    // The opening ledger balance plus the account's credits, each counted from when its funds
+    // type says it becomes available, less its debits, which are always counted straight away.
+    // `as_of` is the group's as-of date, which float is counted from, and dates before it are
+    // taken as the as-of date. The opening ledger's own funds type is applied too, and is zero
+    // when the account doesn't report one. Loan and non-monetary details are left out.
+    pub fn available_as_of_in(
+        &self,
+        as_of: NaiveDate,
+        date: NaiveDate,
+        cur: Currency,
+        days: AvailabilityDays,
+    ) -> Money {
+        let date = cmp::max(as_of, date);
+        let opening = self.infos.iter().filter_map(|info| match *info {
+            AccountInfo::Status {
+                code: StatusCode::Account(AccountStatus::OpeningLedger),
+                amount: Some(amount),
+                ref funds,
+            } => Some(available_by(amount, funds, as_of, date, days)),
+            _ => None,
+        }).next().unwrap_or(0);
+        let available = self.transaction_details.iter().fold(opening, |total, detail| {
+            let amount = match detail.amount {
+                Some(amount) if detail.code.is_credit() => {
+                    available_by(amount, &detail.funds, as_of, date, days)
+                }
+                Some(amount) if detail.code.is_debit() => -amount,
+                _ => 0,
+            };
+            total.saturating_add(amount)
+        });
+        Money::new(available, cur)
+    }
+}
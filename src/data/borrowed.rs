@@ -0,0 +1,103 @@
+use std::borrow::Cow;
+
+use ast::convert::Converter;
+
+use super::{Account, AccountNumber, File, FileProcessError, Group, Party, ReferenceNum,
+            TransactionDetail};
+
+// Parties, account numbers, and reference numbers borrowed from the input where they can be,
+// which saves an allocation for each of them.
+pub type BorrowedFile<'a> = File<Cow<'a, str>>;
+
+impl<'a> File<Cow<'a, str>> {
+    pub fn process_borrowed(file: &'a [u8]) -> Result<BorrowedFile<'a>, FileProcessError<'a>> {
+        super::convert(file, Converter::with_strings())
+    }
+}
+
+impl<S: Into<String>> File<S> {
+    pub fn into_owned(self) -> File {
+        File {
+            sender: self.sender.into_owned(),
+            receiver: self.receiver.into_owned(),
+            creation: self.creation,
+            ident: self.ident,
+            physical_record_len: self.physical_record_len,
+            block_size: self.block_size,
+            format_version: self.format_version,
+            groups: self.groups.into_iter().map(Group::into_owned).collect(),
+            declared_control_total: self.declared_control_total,
+            declared_group_count: self.declared_group_count,
+            declared_record_count: self.declared_record_count,
+            record_count: self.record_count,
+        }
+    }
+}
+
+impl<S: Into<String>> Group<S> {
+    pub fn into_owned(self) -> Group {
+        Group {
+            ultimate_receiver: self.ultimate_receiver.map(Party::into_owned),
+            originator: self.originator.map(Party::into_owned),
+            status: self.status,
+            as_of: self.as_of,
+            currency: self.currency,
+            as_of_date_mod: self.as_of_date_mod,
+            accounts: self.accounts.into_iter().map(Account::into_owned).collect(),
+            declared_control_total: self.declared_control_total,
+            declared_account_count: self.declared_account_count,
+            declared_record_count: self.declared_record_count,
+            record_count: self.record_count,
+        }
+    }
+}
+
+impl<S: Into<String>> Account<S> {
+    pub fn into_owned(self) -> Account {
+        Account {
+            customer_account: self.customer_account.into_owned(),
+            currency: self.currency,
+            infos: self.infos,
+            transaction_details: self.transaction_details
+                .into_iter()
+                .map(TransactionDetail::into_owned)
+                .collect(),
+            declared_control_total: self.declared_control_total,
+            declared_record_count: self.declared_record_count,
+            record_count: self.record_count,
+        }
+    }
+}
+
+impl<S: Into<String>> TransactionDetail<S> {
+    pub fn into_owned(self) -> TransactionDetail {
+        TransactionDetail {
+            code: self.code,
+            amount: self.amount,
+            funds: self.funds,
+            bank_ref_num: self.bank_ref_num.map(ReferenceNum::into_owned),
+            customer_ref_num: self.customer_ref_num.map(ReferenceNum::into_owned),
+            extensions: self.extensions,
+            text: self.text,
+            raw_record: self.raw_record,
+        }
+    }
+}
+
+impl<S: Into<String>> Party<S> {
+    pub fn into_owned(self) -> Party {
+        Party(self.0.into())
+    }
+}
+
+impl<S: Into<String>> AccountNumber<S> {
+    pub fn into_owned(self) -> AccountNumber {
+        AccountNumber(self.0.into())
+    }
+}
+
+impl<S: Into<String>> ReferenceNum<S> {
+    pub fn into_owned(self) -> ReferenceNum {
+        ReferenceNum(self.0.into())
+    }
+}
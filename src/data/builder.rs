@@ -0,0 +1,107 @@
+use penny::Currency;
+
+use super::{Account, AsOfDateModifier, BaiDateOrTime, BaiDateTime, File, FileIdent, Group,
+            GroupStatus, Party};
+
+#[derive(Debug, Clone)]
+pub struct FileBuilder {
+    file: File,
+    as_of_modifier: Option<AsOfDateModifier>,
+}
+
+impl FileBuilder {
+    pub fn new(sender: Party, receiver: Party, creation: BaiDateTime, ident: FileIdent) -> Self {
+        FileBuilder {
+            file: File {
+                sender,
+                receiver,
+                creation,
+                ident,
+                physical_record_len: None,
+                block_size: None,
+                format_version: Some(2),
+                groups: Vec::new(),
+                declared_control_total: None,
+                declared_group_count: None,
+                declared_record_count: None,
+                record_count: None,
+            },
+            as_of_modifier: None,
+        }
+    }
+
+    // Applied when building to every group without its own modifier.
+    pub fn as_of_modifier(mut self, as_of_modifier: AsOfDateModifier) -> Self {
+        self.as_of_modifier = Some(as_of_modifier);
+        self
+    }
+
+    pub fn group(mut self, group: Group) -> Self {
+        self.file.groups.push(group);
+        self
+    }
+
+    pub fn build(self) -> File {
+        let mut file = self.file;
+        if let Some(as_of_modifier) = self.as_of_modifier {
+            for group in &mut file.groups {
+                group.as_of_date_mod = group.as_of_date_mod.or(Some(as_of_modifier));
+            }
+        }
+        file
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GroupBuilder {
+    group: Group,
+}
+
+impl GroupBuilder {
+    pub fn new(status: GroupStatus, as_of: BaiDateOrTime) -> Self {
+        GroupBuilder {
+            group: Group {
+                ultimate_receiver: None,
+                originator: None,
+                status,
+                as_of,
+                currency: None,
+                as_of_date_mod: None,
+                accounts: Vec::new(),
+                declared_control_total: None,
+                declared_account_count: None,
+                declared_record_count: None,
+                record_count: None,
+            },
+        }
+    }
+
+    pub fn ultimate_receiver(mut self, ultimate_receiver: Party) -> Self {
+        self.group.ultimate_receiver = Some(ultimate_receiver);
+        self
+    }
+
+    pub fn originator(mut self, originator: Party) -> Self {
+        self.group.originator = Some(originator);
+        self
+    }
+
+    pub fn currency(mut self, currency: Currency) -> Self {
+        self.group.currency = Some(currency);
+        self
+    }
+
+    pub fn as_of_modifier(mut self, as_of_modifier: AsOfDateModifier) -> Self {
+        self.group.as_of_date_mod = Some(as_of_modifier);
+        self
+    }
+
+    pub fn account(mut self, account: Account) -> Self {
+        self.group.accounts.push(account);
+        self
+    }
+
+    pub fn build(self) -> Group {
+        self.group
+    }
+}
@@ -0,0 +1,115 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use super::{CreditDetail, DebitDetail, DetailCode, StatusCode, SummaryCode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum Direction {
+    Credit,
+    Debit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct CodeDef {
+    pub description: String,
+    // None for codes that are neither, such as statuses and loan codes.
+    pub direction: Option<Direction>,
+    // The built-in codes don't have categories.
+    pub category: Option<String>,
+}
+
+// Type code definitions, starting from the ones in the spec, which banks can replace or add to
+// with codes of their own.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct CodeTable {
+    overrides: HashMap<u16, CodeDef>,
+}
+
+impl CodeTable {
+    pub fn builtin() -> CodeTable {
+        CodeTable::default()
+    }
+
+    pub fn with_override(mut self, code: u16, def: CodeDef) -> CodeTable {
+        self.overrides.insert(code, def);
+        self
+    }
+
+    // An override, or else the built-in detail, summary, or status code, in that order, as the
+    // same number can't be more than one of them.
+    pub fn get(&self, code: u16) -> Option<Cow<CodeDef>> {
+        if let Some(def) = self.overrides.get(&code) {
+            return Some(Cow::Borrowed(def));
+        }
+        builtin(code).map(|(description, direction)| {
+            Cow::Owned(CodeDef {
+                description: description.to_owned(),
+                direction,
+                category: None,
+            })
+        })
+    }
+
+    pub fn description(&self, code: u16) -> Option<&str> {
+        match self.overrides.get(&code) {
+            Some(def) => Some(&def.description),
+            None => builtin(code).map(|(description, _)| description),
+        }
+    }
+
+    pub fn direction(&self, code: u16) -> Option<Direction> {
+        match self.overrides.get(&code) {
+            Some(def) => def.direction,
+            None => builtin(code).and_then(|(_, direction)| direction),
+        }
+    }
+
+    pub fn category(&self, code: u16) -> Option<&str> {
+        self.overrides.get(&code).and_then(|def| def.category.as_ref().map(|c| c.as_str()))
+    }
+
+    // What a 16 Transaction Detail's type code converts to. Codes the spec doesn't have become
+    // custom credits or debits if an override gives them a direction. An override can't change
+    // the direction of a code the spec has, as the code would read back with the spec's, so such
+    // codes are an error.
+    pub fn detail_code(&self, code: u16) -> Result<DetailCode, u16> {
+        let direction = self.overrides.get(&code).and_then(|def| def.direction);
+        match (direction, DetailCode::try_from(code)) {
+            (None, builtin) => builtin,
+            (Some(Direction::Credit), Ok(detail)) if detail.is_credit() => Ok(detail),
+            (Some(Direction::Debit), Ok(detail)) if detail.is_debit() => Ok(detail),
+            (Some(_), Ok(_)) => Err(code),
+            (Some(Direction::Credit), Err(_)) => {
+                Ok(DetailCode::Credit(CreditDetail::Custom(code)))
+            }
+            (Some(Direction::Debit), Err(_)) => Ok(DetailCode::Debit(DebitDetail::Custom(code))),
+        }
+    }
+}
+
+// The description and direction of a built-in detail, summary, or status code.
+fn builtin(code: u16) -> Option<(&'static str, Option<Direction>)> {
+    if let Ok(detail) = DetailCode::try_from(code) {
+        let direction = if detail.is_credit() {
+            Some(Direction::Credit)
+        } else if detail.is_debit() {
+            Some(Direction::Debit)
+        } else {
+            None
+        };
+        return Some((detail.description(), direction));
+    }
+    if let Ok(summary) = SummaryCode::try_from(code) {
+        let direction = match summary {
+            SummaryCode::Credit(_) => Some(Direction::Credit),
+            SummaryCode::Debit(_) => Some(Direction::Debit),
+            SummaryCode::Loan(_) => None,
+        };
+        return Some((summary.description(), direction));
+    }
+    StatusCode::try_from(code).ok().map(|status| (status.description(), None))
+}
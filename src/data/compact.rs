@@ -0,0 +1,430 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate, NaiveTime, Timelike};
+use penny::Currency;
+
+use super::{Account, AccountInfo, AccountNumber, BaiDateOrTime, BaiDateTime, DetailCode,
+            DistributedAvailDistribution, File, FileIdent, FundsType, Group, Party,
+            ReferenceNum, StatusCode, SummaryCode, TransactionDetail};
+
+// A smaller serde representation of the data model, for storing files in bulk. Type codes and
+// other enums are their BAI2 codes, dates are YYYYMMDD integers, and times are HHMM integers
+// with 9999 for end of day, as in the files themselves. Seconds aren't kept.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompactError {
+    TypeCode(u16),
+    GroupStatus(u8),
+    AsOfDateModifier(u8),
+    Date(u32),
+    Time(u16),
+}
+impl fmt::Display for CompactError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CompactError::TypeCode(code) => write!(f, "Invalid type code {:03}", code),
+            CompactError::GroupStatus(status) => write!(f, "Invalid group status {}", status),
+            CompactError::AsOfDateModifier(m) => write!(f, "Invalid as-of date modifier {}", m),
+            CompactError::Date(date) => write!(f, "Invalid date {}", date),
+            CompactError::Time(time) => write!(f, "Invalid time {:04}", time),
+        }
+    }
+}
+
+impl Error for CompactError {}
+
+const END_OF_DAY: u16 = 9999;
+
+fn date(date: NaiveDate) -> u32 {
+    date.year() as u32 * 10000 + date.month() * 100 + date.day()
+}
+fn from_date(date: u32) -> Result<NaiveDate, CompactError> {
+    NaiveDate::from_ymd_opt((date / 10000) as i32, date / 100 % 100, date % 100)
+        .ok_or(CompactError::Date(date))
+}
+
+fn time(time: NaiveTime) -> u16 {
+    (time.hour() * 100 + time.minute()) as u16
+}
+fn from_time(time: u16) -> Result<NaiveTime, CompactError> {
+    NaiveTime::from_hms_opt(u32::from(time / 100), u32::from(time % 100), 0)
+        .ok_or(CompactError::Time(time))
+}
+
+fn date_time(dt: &BaiDateTime) -> (u32, Option<u16>) {
+    match *dt {
+        BaiDateTime::DateTime(dt) => (date(dt.date()), Some(time(dt.time()))),
+        BaiDateTime::DateEndOfDay(d) => (date(d), Some(END_OF_DAY)),
+        BaiDateTime::Date(d) => (date(d), None),
+    }
+}
+fn from_date_time((d, t): (u32, u16)) -> Result<BaiDateTime, CompactError> {
+    let d = from_date(d)?;
+    Ok(if t == END_OF_DAY {
+        BaiDateTime::DateEndOfDay(d)
+    } else {
+        BaiDateTime::DateTime(d.and_time(from_time(t)?))
+    })
+}
+
+fn from_creation((d, t): (u32, Option<u16>)) -> Result<BaiDateTime, CompactError> {
+    match t {
+        None => from_date(d).map(BaiDateTime::Date),
+        Some(t) => from_date_time((d, t)),
+    }
+}
+
+fn date_or_time(dt: &BaiDateOrTime) -> (u32, Option<u16>) {
+    match *dt {
+        BaiDateOrTime::Date(d) => (date(d), None),
+        BaiDateOrTime::DateTime(dt) => (date(dt.date()), Some(time(dt.time()))),
+        BaiDateOrTime::DateEndOfDay(d) => (date(d), Some(END_OF_DAY)),
+    }
+}
+fn from_date_or_time((d, t): (u32, Option<u16>)) -> Result<BaiDateOrTime, CompactError> {
+    match t {
+        None => from_date(d).map(BaiDateOrTime::Date),
+        Some(t) => from_date_time((d, t)).map(BaiDateOrTime::from),
+    }
+}
+
+// Variants are named after the funds type codes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompactFunds {
+    Z,
+    #[serde(rename = "0")] Immediate,
+    #[serde(rename = "1")] OneDay,
+    #[serde(rename = "2")] TwoOrMoreDays,
+    S(Option<i64>, Option<i64>, Option<i64>),
+    V(u32, Option<u16>),
+    D(Vec<(u32, i64)>),
+}
+impl<'a> From<&'a FundsType> for CompactFunds {
+    fn from(funds: &'a FundsType) -> CompactFunds {
+        match *funds {
+            FundsType::Unknown => CompactFunds::Z,
+            FundsType::ImmediateAvail => CompactFunds::Immediate,
+            FundsType::OneDayAvail => CompactFunds::OneDay,
+            FundsType::TwoOrMoreDaysAvail => CompactFunds::TwoOrMoreDays,
+            FundsType::DistributedAvailS {
+                immediate,
+                one_day,
+                more_than_one_day,
+            } => CompactFunds::S(immediate, one_day, more_than_one_day),
+            FundsType::ValueDated(ref dt) => {
+                let (d, t) = date_or_time(dt);
+                CompactFunds::V(d, t)
+            }
+            FundsType::DistributedAvailD(ref dists) => {
+                CompactFunds::D(dists.iter().map(|d| (d.days, d.amount)).collect())
+            }
+        }
+    }
+}
+impl TryFrom<CompactFunds> for FundsType {
+    type Error = CompactError;
+    fn try_from(funds: CompactFunds) -> Result<FundsType, CompactError> {
+        Ok(match funds {
+            CompactFunds::Z => FundsType::Unknown,
+            CompactFunds::Immediate => FundsType::ImmediateAvail,
+            CompactFunds::OneDay => FundsType::OneDayAvail,
+            CompactFunds::TwoOrMoreDays => FundsType::TwoOrMoreDaysAvail,
+            CompactFunds::S(immediate, one_day, more_than_one_day) => {
+                FundsType::DistributedAvailS {
+                    immediate: immediate,
+                    one_day: one_day,
+                    more_than_one_day: more_than_one_day,
+                }
+            }
+            CompactFunds::V(d, t) => FundsType::ValueDated(from_date_or_time((d, t))?),
+            CompactFunds::D(dists) => FundsType::DistributedAvailD(
+                dists
+                    .into_iter()
+                    .map(|(days, amount)| {
+                        DistributedAvailDistribution {
+                            days: days,
+                            amount: amount,
+                        }
+                    })
+                    .collect(),
+            ),
+        })
+    }
+}
+
+fn funds(funds: &Option<FundsType>) -> Option<CompactFunds> {
+    funds.as_ref().map(CompactFunds::from)
+}
+fn from_funds(funds: Option<CompactFunds>) -> Result<Option<FundsType>, CompactError> {
+    match funds {
+        Some(funds) => FundsType::try_from(funds).map(Some),
+        None => Ok(None),
+    }
+}
+
+// Code, amount, and funds type, plus item count for summaries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompactInfo {
+    Status(u16, Option<i64>, Option<CompactFunds>),
+    Summary(u16, Option<u64>, Option<u32>, Option<CompactFunds>),
+}
+impl<'a> From<&'a AccountInfo> for CompactInfo {
+    fn from(info: &'a AccountInfo) -> CompactInfo {
+        match *info {
+            AccountInfo::Status {
+                code,
+                amount,
+                funds: ref f,
+            } => CompactInfo::Status(code.into(), amount, funds(f)),
+            AccountInfo::Summary {
+                code,
+                amount,
+                item_count,
+                funds: ref f,
+            } => CompactInfo::Summary(code.into(), amount, item_count, funds(f)),
+        }
+    }
+}
+impl TryFrom<CompactInfo> for AccountInfo {
+    type Error = CompactError;
+    fn try_from(info: CompactInfo) -> Result<AccountInfo, CompactError> {
+        Ok(match info {
+            CompactInfo::Status(code, amount, f) => AccountInfo::Status {
+                code: StatusCode::try_from(code).map_err(CompactError::TypeCode)?,
+                amount: amount,
+                funds: from_funds(f)?,
+            },
+            CompactInfo::Summary(code, amount, item_count, f) => AccountInfo::Summary {
+                code: SummaryCode::try_from(code).map_err(CompactError::TypeCode)?,
+                amount: amount,
+                item_count: item_count,
+                funds: from_funds(f)?,
+            },
+        })
+    }
+}
+
+// Code, amount, funds type, bank reference, customer reference, text, extensions, and raw
+// record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactDetail(
+    pub u16,
+    pub Option<i64>,
+    pub Option<CompactFunds>,
+    pub Option<String>,
+    pub Option<String>,
+    pub Option<Vec<String>>,
+    pub Vec<String>,
+    pub Option<String>,
+);
+impl<'a> From<&'a TransactionDetail> for CompactDetail {
+    fn from(detail: &'a TransactionDetail) -> CompactDetail {
+        CompactDetail(
+            detail.code.into(),
+            detail.amount,
+            funds(&detail.funds),
+            detail.bank_ref_num.as_ref().map(|r| r.0.clone()),
+            detail.customer_ref_num.as_ref().map(|r| r.0.clone()),
+            detail.text.clone(),
+            detail.extensions.clone(),
+            detail.raw_record.clone(),
+        )
+    }
+}
+impl TryFrom<CompactDetail> for TransactionDetail {
+    type Error = CompactError;
+    fn try_from(detail: CompactDetail) -> Result<TransactionDetail, CompactError> {
+        let CompactDetail(
+            code,
+            amount,
+            f,
+            bank_ref_num,
+            customer_ref_num,
+            text,
+            extensions,
+            raw_record,
+        ) = detail;
+        Ok(TransactionDetail {
+            code: DetailCode::try_from(code).map_err(CompactError::TypeCode)?,
+            amount: amount,
+            funds: from_funds(f)?,
+            bank_ref_num: bank_ref_num.map(ReferenceNum),
+            customer_ref_num: customer_ref_num.map(ReferenceNum),
+            extensions: extensions,
+            text: text,
+            raw_record: raw_record,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactAccount {
+    pub customer_account: String,
+    #[serde(with = "::data::penny_serde::option_currency")]
+    pub currency: Option<Currency>,
+    pub infos: Vec<CompactInfo>,
+    pub transaction_details: Vec<CompactDetail>,
+    pub declared_control_total: Option<i64>,
+    pub declared_record_count: Option<usize>,
+    #[serde(default)]
+    pub record_count: Option<usize>,
+}
+impl<'a> From<&'a Account> for CompactAccount {
+    fn from(account: &'a Account) -> CompactAccount {
+        CompactAccount {
+            customer_account: account.customer_account.0.clone(),
+            currency: account.currency,
+            infos: account.infos.iter().map(CompactInfo::from).collect(),
+            transaction_details: account
+                .transaction_details
+                .iter()
+                .map(CompactDetail::from)
+                .collect(),
+            declared_control_total: account.declared_control_total,
+            declared_record_count: account.declared_record_count,
+            record_count: account.record_count,
+        }
+    }
+}
+impl TryFrom<CompactAccount> for Account {
+    type Error = CompactError;
+    fn try_from(account: CompactAccount) -> Result<Account, CompactError> {
+        Ok(Account {
+            customer_account: AccountNumber(account.customer_account),
+            currency: account.currency,
+            infos: account
+                .infos
+                .into_iter()
+                .map(AccountInfo::try_from)
+                .collect::<Result<_, _>>()?,
+            transaction_details: account
+                .transaction_details
+                .into_iter()
+                .map(TransactionDetail::try_from)
+                .collect::<Result<_, _>>()?,
+            declared_control_total: account.declared_control_total,
+            declared_record_count: account.declared_record_count,
+            record_count: account.record_count,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactGroup {
+    pub ultimate_receiver: Option<String>,
+    pub originator: Option<String>,
+    pub status: u8,
+    pub as_of: (u32, Option<u16>),
+    #[serde(with = "::data::penny_serde::option_currency")]
+    pub currency: Option<Currency>,
+    pub as_of_date_mod: Option<u8>,
+    pub accounts: Vec<CompactAccount>,
+    pub declared_control_total: Option<i64>,
+    pub declared_account_count: Option<usize>,
+    pub declared_record_count: Option<usize>,
+    #[serde(default)]
+    pub record_count: Option<usize>,
+}
+impl<'a> From<&'a Group> for CompactGroup {
+    fn from(group: &'a Group) -> CompactGroup {
+        CompactGroup {
+            ultimate_receiver: group.ultimate_receiver.as_ref().map(|p| p.0.clone()),
+            originator: group.originator.as_ref().map(|p| p.0.clone()),
+            status: group.status.into(),
+            as_of: date_or_time(&group.as_of),
+            currency: group.currency,
+            as_of_date_mod: group.as_of_date_mod.map(u8::from),
+            accounts: group.accounts.iter().map(CompactAccount::from).collect(),
+            declared_control_total: group.declared_control_total,
+            declared_account_count: group.declared_account_count,
+            declared_record_count: group.declared_record_count,
+            record_count: group.record_count,
+        }
+    }
+}
+impl TryFrom<CompactGroup> for Group {
+    type Error = CompactError;
+    fn try_from(group: CompactGroup) -> Result<Group, CompactError> {
+        Ok(Group {
+            ultimate_receiver: group.ultimate_receiver.map(Party),
+            originator: group.originator.map(Party),
+            status: TryFrom::try_from(group.status).map_err(CompactError::GroupStatus)?,
+            as_of: from_date_or_time(group.as_of)?,
+            currency: group.currency,
+            as_of_date_mod: match group.as_of_date_mod {
+                Some(m) => Some(TryFrom::try_from(m).map_err(CompactError::AsOfDateModifier)?),
+                None => None,
+            },
+            accounts: group
+                .accounts
+                .into_iter()
+                .map(Account::try_from)
+                .collect::<Result<_, _>>()?,
+            declared_control_total: group.declared_control_total,
+            declared_account_count: group.declared_account_count,
+            declared_record_count: group.declared_record_count,
+            record_count: group.record_count,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactFile {
+    pub sender: String,
+    pub receiver: String,
+    pub creation: (u32, Option<u16>),
+    pub ident: u32,
+    #[serde(default)]
+    pub physical_record_len: Option<u16>,
+    #[serde(default)]
+    pub block_size: Option<u16>,
+    pub format_version: Option<u8>,
+    pub groups: Vec<CompactGroup>,
+    pub declared_control_total: Option<i64>,
+    pub declared_group_count: Option<usize>,
+    pub declared_record_count: Option<usize>,
+    #[serde(default)]
+    pub record_count: Option<usize>,
+}
+impl<'a> From<&'a File> for CompactFile {
+    fn from(file: &'a File) -> CompactFile {
+        CompactFile {
+            sender: file.sender.0.clone(),
+            receiver: file.receiver.0.clone(),
+            creation: date_time(&file.creation),
+            ident: file.ident.0,
+            physical_record_len: file.physical_record_len,
+            block_size: file.block_size,
+            format_version: file.format_version,
+            groups: file.groups.iter().map(CompactGroup::from).collect(),
+            declared_control_total: file.declared_control_total,
+            declared_group_count: file.declared_group_count,
+            declared_record_count: file.declared_record_count,
+            record_count: file.record_count,
+        }
+    }
+}
+impl TryFrom<CompactFile> for File {
+    type Error = CompactError;
+    fn try_from(file: CompactFile) -> Result<File, CompactError> {
+        Ok(File {
+            sender: Party(file.sender),
+            receiver: Party(file.receiver),
+            creation: from_creation(file.creation)?,
+            ident: FileIdent(file.ident),
+            physical_record_len: file.physical_record_len,
+            block_size: file.block_size,
+            format_version: file.format_version,
+            groups: file.groups
+                .into_iter()
+                .map(Group::try_from)
+                .collect::<Result<_, _>>()?,
+            declared_control_total: file.declared_control_total,
+            declared_group_count: file.declared_group_count,
+            declared_record_count: file.declared_record_count,
+            record_count: file.record_count,
+        })
+    }
+}
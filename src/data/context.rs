@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use penny::Currency;
+
+use super::{Account, File, Group, TransactionDetail};
+
+// A transaction with its file, group, and account context, for loading into tables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct FlatTransaction {
+    pub file_ident: u32,
+    pub group_originator: Option<String>,
+    pub account_number: String,
+    pub as_of_date: NaiveDate,
+    pub code: u16,
+    pub amount: Option<i64>,
+    #[cfg_attr(feature = "serde-serialize", serde(with = "::data::penny_serde::currency"))]
+    pub currency: Currency,
+    pub bank_ref: Option<String>,
+    pub customer_ref: Option<String>,
+    // Continuation lines are joined with newlines.
+    pub text: Option<String>,
+}
+
+// A transaction along with the file, group, and account it belongs to. It serializes as its
+// `FlatTransaction`, so exports get one flat record per transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionContext<'a> {
+    pub file: &'a File,
+    pub group: &'a Group,
+    pub account: &'a Account,
+    pub transaction: &'a TransactionDetail,
+}
+
+impl<'a> TransactionContext<'a> {
+    pub fn as_of_date(&self) -> NaiveDate {
+        self.group.as_of_date()
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.account.effective_currency(self.group)
+    }
+
+    pub fn to_row(&self) -> FlatTransaction {
+        FlatTransaction {
+            file_ident: self.file.ident.0,
+            group_originator: self.group.originator.as_ref().map(|o| o.0.clone()),
+            account_number: self.account.customer_account.0.clone(),
+            as_of_date: self.as_of_date(),
+            code: self.transaction.code.into(),
+            amount: self.transaction.amount,
+            currency: self.currency(),
+            bank_ref: self.transaction.bank_ref_num.as_ref().map(|r| r.0.clone()),
+            customer_ref: self.transaction.customer_ref_num.as_ref().map(|r| r.0.clone()),
+            text: self.transaction.text_joined("\n"),
+        }
+    }
+}
+
+#[cfg(feature = "serde-serialize")]
+impl<'a> ::serde::Serialize for TransactionContext<'a> {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_row().serialize(serializer)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Transactions<'a> {
+    file: &'a File,
+    group: usize,
+    account: usize,
+    transaction: usize,
+}
+
+impl<'a> Iterator for Transactions<'a> {
+    type Item = TransactionContext<'a>;
+
+    fn next(&mut self) -> Option<TransactionContext<'a>> {
+        loop {
+            let group = self.file.groups.get(self.group)?;
+            let account = match group.accounts.get(self.account) {
+                Some(account) => account,
+                None => {
+                    self.group += 1;
+                    self.account = 0;
+                    continue;
+                }
+            };
+            match account.transaction_details.get(self.transaction) {
+                Some(transaction) => {
+                    self.transaction += 1;
+                    return Some(TransactionContext {
+                        file: self.file,
+                        group,
+                        account,
+                        transaction,
+                    });
+                }
+                None => {
+                    self.account += 1;
+                    self.transaction = 0;
+                }
+            }
+        }
+    }
+}
+
+impl<S> Group<S> {
+    pub fn as_of_date(&self) -> NaiveDate {
+        self.as_of.clone().date()
+    }
+}
+
+impl File {
+    pub fn transactions(&self) -> Transactions {
+        Transactions {
+            file: self,
+            group: 0,
+            account: 0,
+            transaction: 0,
+        }
+    }
+
+    pub fn to_rows(&self) -> Vec<FlatTransaction> {
+        self.transactions().map(|t| t.to_row()).collect()
+    }
+
+    pub fn latest_as_of(&self) -> Option<NaiveDate> {
+        self.groups.iter().map(Group::as_of_date).max()
+    }
+
+    // The number of transactions with each type code and currency, and their signed amounts
+    // added up, so credits and debits net out. Loan and non-monetary details are counted but add
+    // nothing. Keyed by type code, then currency code, so it serializes as nested JSON objects.
+    pub fn code_histogram(&self) -> HashMap<u16, HashMap<&'static str, (usize, i64)>> {
+        let mut histogram = HashMap::new();
+        for t in self.transactions() {
+            let entry = histogram
+                .entry(t.transaction.code.into())
+                .or_insert_with(HashMap::new)
+                .entry(t.currency().info().code())
+                .or_insert((0, 0i64));
+            entry.0 += 1;
+            entry.1 = t.transaction.signed_amount().map_or(entry.1, |a| entry.1.saturating_add(a));
+        }
+        histogram
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::{HashMap, VecDeque};
+
+use super::{Account, AccountInfo, AccountNumber, File, TransactionDetail};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct Change<T> {
+    pub old: T,
+    pub new: T,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct FileDiff {
+    pub added_accounts: Vec<AccountNumber>,
+    pub removed_accounts: Vec<AccountNumber>,
+    pub changed_accounts: Vec<AccountDiff>,
+}
+
+impl FileDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_accounts.is_empty() && self.removed_accounts.is_empty() &&
+            self.changed_accounts.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct AccountDiff {
+    pub account: AccountNumber,
+    pub infos: Option<Change<Vec<AccountInfo>>>,
+    pub added_transactions: Vec<TransactionDetail>,
+    pub removed_transactions: Vec<TransactionDetail>,
+    pub changed_transactions: Vec<Change<TransactionDetail>>,
+}
+
+impl AccountDiff {
+    pub fn is_empty(&self) -> bool {
+        self.infos.is_none() && self.added_transactions.is_empty() &&
+            self.removed_transactions.is_empty() &&
+            self.changed_transactions.is_empty()
+    }
+}
+
+impl File {
+    // Accounts are matched by their normalized number and transactions by their bank reference
+    // number, in file order when either repeats. Transactions without a bank reference number
+    // can only be matched to an identical transaction.
+    pub fn diff(&self, other: &File) -> FileDiff {
+        let old_accounts = self.groups.iter().flat_map(|g| &g.accounts).collect::<Vec<_>>();
+        let new_accounts = other.groups.iter().flat_map(|g| &g.accounts).collect::<Vec<_>>();
+
+        let mut new_by_num = HashMap::new();
+        for (i, account) in new_accounts.iter().enumerate() {
+            new_by_num
+                .entry(account.customer_account.normalized())
+                .or_insert_with(VecDeque::new)
+                .push_back(i);
+        }
+        let mut new_matched = vec![false; new_accounts.len()];
+
+        let mut diff = FileDiff::default();
+        for old in old_accounts {
+            let matched = new_by_num
+                .get_mut(old.customer_account.normalized())
+                .and_then(VecDeque::pop_front);
+            match matched {
+                Some(i) => {
+                    new_matched[i] = true;
+                    let account_diff = diff_account(old, new_accounts[i]);
+                    if !account_diff.is_empty() {
+                        diff.changed_accounts.push(account_diff);
+                    }
+                }
+                None => diff.removed_accounts.push(old.customer_account.clone()),
+            }
+        }
+        for (new, matched) in new_accounts.into_iter().zip(new_matched) {
+            if !matched {
+                diff.added_accounts.push(new.customer_account.clone());
+            }
+        }
+        diff
+    }
+}
+
+fn diff_account(old: &Account, new: &Account) -> AccountDiff {
+    let mut diff = AccountDiff {
+        account: new.customer_account.clone(),
+        infos: if old.infos == new.infos {
+            None
+        } else {
+            Some(Change {
+                old: old.infos.clone(),
+                new: new.infos.clone(),
+            })
+        },
+        added_transactions: Vec::new(),
+        removed_transactions: Vec::new(),
+        changed_transactions: Vec::new(),
+    };
+
+    let mut new_by_ref = HashMap::new();
+    for (i, td) in new.transaction_details.iter().enumerate() {
+        if let Some(ref bank_ref_num) = td.bank_ref_num {
+            new_by_ref
+                .entry(bank_ref_num.0.as_str())
+                .or_insert_with(VecDeque::new)
+                .push_back(i);
+        }
+    }
+    let mut new_matched = vec![false; new.transaction_details.len()];
+
+    for old_td in &old.transaction_details {
+        let matched = match old_td.bank_ref_num {
+            Some(ref bank_ref_num) => {
+                new_by_ref
+                    .get_mut(bank_ref_num.0.as_str())
+                    .and_then(VecDeque::pop_front)
+            }
+            None => {
+                new.transaction_details
+                    .iter()
+                    .enumerate()
+                    .position(|(i, new_td)| {
+                        !new_matched[i] && new_td.bank_ref_num.is_none() && new_td == old_td
+                    })
+            }
+        };
+        match matched {
+            Some(i) => {
+                new_matched[i] = true;
+                let new_td = &new.transaction_details[i];
+                if new_td != old_td {
+                    diff.changed_transactions.push(Change {
+                        old: old_td.clone(),
+                        new: new_td.clone(),
+                    });
+                }
+            }
+            None => diff.removed_transactions.push(old_td.clone()),
+        }
+    }
+    for (new_td, matched) in new.transaction_details.iter().zip(new_matched) {
+        if !matched {
+            diff.added_transactions.push(new_td.clone());
+        }
+    }
+    diff
+}
@@ -0,0 +1,81 @@
+use ast;
+use ast::RecordField;
+use ast::parse::Parsed;
+use ast::convert::{Converter, ConverterOutput};
+use parse::Dialect;
+
+use super::{Account, File, FileProcessError, Group, TransactionDetail};
+
+// Push-based alternative to `File::process`. The file and group passed in don't have their
+// groups or accounts, and accounts don't have their transaction details, so a whole file is
+// never held in memory. Trailers are handled with what they close, declared totals included.
+pub trait BaiHandler {
+    fn on_file_header(&mut self, _file: &File) {}
+    fn on_group_header(&mut self, _group: &Group) {}
+    fn on_account(&mut self, _account: &Account) {}
+    fn on_transaction(&mut self, _transaction: &TransactionDetail) {}
+    fn on_account_trailer(&mut self, _account: &Account) {}
+    fn on_group_trailer(&mut self, _group: &Group) {}
+    fn on_file_trailer(&mut self, _file: &File) {}
+}
+
+// Records are checked the same way as by `File::process`, and handled as soon as they're read
+// and converted, so the handler may have seen part of a file that then fails.
+pub fn parse_callbacks<'a, H: BaiHandler>(
+    input: &'a [u8],
+    handler: &mut H,
+) -> Result<(), FileProcessError<'a>> {
+    let mut raw_records = super::RawRecords::new(input, Dialect::Standard);
+    let mut converter = Converter::streaming();
+    while let Some(raw_record) = raw_records.next() {
+        let (r, physical) = raw_record?;
+        let record = ast::Record::parse(&r).map_err(FileProcessError::FieldParse)?;
+        let field = record.field();
+        match converter.process_physical(record, physical) {
+            ConverterOutput::Err(e) => return Err(e.into()),
+            ConverterOutput::Ok(file) => {
+                // Only errors can follow the file trailer.
+                if let Some(Err(e)) = raw_records.next() {
+                    return Err(e);
+                }
+                handler.on_file_trailer(&file);
+                return Ok(());
+            }
+            _ => {}
+        }
+        match field {
+            RecordField::FileHeader => {
+                if let Some(file) = converter.file() {
+                    handler.on_file_header(file);
+                }
+            }
+            RecordField::GroupHeader => {
+                if let Some(group) = converter.group() {
+                    handler.on_group_header(group);
+                }
+            }
+            RecordField::AccountIdent => {
+                if let Some(account) = converter.account() {
+                    handler.on_account(account);
+                }
+            }
+            RecordField::TransactionDetail => {
+                if let Some(td) = converter.account().and_then(|a| a.transaction_details.last()) {
+                    handler.on_transaction(td);
+                }
+            }
+            RecordField::AccountTrailer => {
+                if let Some(account) = converter.closed_account() {
+                    handler.on_account_trailer(account);
+                }
+            }
+            RecordField::GroupTrailer => {
+                if let Some(group) = converter.closed_group() {
+                    handler.on_group_trailer(group);
+                }
+            }
+            RecordField::FileTrailer => {}
+        }
+    }
+    Err(FileProcessError::UnfinishedConversion)
+}
@@ -0,0 +1,124 @@
+use std::error::Error;
+use std::fmt;
+
+use super::{File, Group};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum MergeError {
+    Empty,
+    // The index of the file that doesn't match the first file.
+    Sender { file: usize },
+    Receiver { file: usize },
+    CreationDate { file: usize },
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MergeError::Empty => write!(f, "No files to merge"),
+            MergeError::Sender { file } => write!(f, "File {} has a different sender", file),
+            MergeError::Receiver { file } => write!(f, "File {} has a different receiver", file),
+            MergeError::CreationDate { file } => {
+                write!(f, "File {} was created on a different date", file)
+            }
+        }
+    }
+}
+
+impl Error for MergeError {}
+
+impl File {
+    // Groups with the same header (originator, as-of, and the rest) are combined, keeping their
+    // accounts in order.
+    // The header of the first file is kept. Trailers are recomputed when the result is written,
+    // and declared totals are dropped where files or groups are combined.
+    pub fn merge(files: Vec<File>) -> Result<File, MergeError> {
+        let mut merged = match files.first() {
+            Some(first) => {
+                File {
+                    groups: Vec::new(),
+                    declared_control_total: None,
+                    declared_group_count: None,
+                    declared_record_count: None,
+                    record_count: None,
+                    ..first.clone()
+                }
+            }
+            None => return Err(MergeError::Empty),
+        };
+
+        for (i, file) in files.into_iter().enumerate() {
+            if file.sender != merged.sender {
+                return Err(MergeError::Sender { file: i });
+            }
+            if file.receiver != merged.receiver {
+                return Err(MergeError::Receiver { file: i });
+            }
+            if file.creation.date() != merged.creation.date() {
+                return Err(MergeError::CreationDate { file: i });
+            }
+
+            for group in file.groups {
+                let existing = merged.groups.iter().position(|m| {
+                    m.originator == group.originator && m.as_of == group.as_of &&
+                        m.ultimate_receiver == group.ultimate_receiver &&
+                        m.status == group.status && m.currency == group.currency &&
+                        m.as_of_date_mod == group.as_of_date_mod
+                });
+                match existing {
+                    Some(m) => {
+                        let merged = &mut merged.groups[m];
+                        merged.accounts.extend(group.accounts);
+                        merged.declared_control_total = None;
+                        merged.declared_account_count = None;
+                        merged.declared_record_count = None;
+                        merged.record_count = None;
+                    }
+                    None => merged.groups.push(group),
+                }
+            }
+        }
+        Ok(merged)
+    }
+
+    // One file per account, each with the file header and the header of the group the account
+    // was in. Accounts of the same group end up in different files, so each can go to its own
+    // recipient. Trailers are recomputed when the files are written. Transaction details keep
+    // their raw records.
+    pub fn split_by_account(&self) -> Vec<File> {
+        let mut files = Vec::new();
+        for group in &self.groups {
+            for account in &group.accounts {
+                let group = Group {
+                    ultimate_receiver: group.ultimate_receiver.clone(),
+                    originator: group.originator.clone(),
+                    status: group.status,
+                    as_of: group.as_of.clone(),
+                    currency: group.currency,
+                    as_of_date_mod: group.as_of_date_mod,
+                    accounts: vec![account.clone()],
+                    declared_control_total: None,
+                    declared_account_count: None,
+                    declared_record_count: None,
+                    record_count: None,
+                };
+                files.push(File {
+                    sender: self.sender.clone(),
+                    receiver: self.receiver.clone(),
+                    creation: self.creation.clone(),
+                    ident: self.ident,
+                    physical_record_len: self.physical_record_len,
+                    block_size: self.block_size,
+                    format_version: self.format_version,
+                    groups: vec![group],
+                    declared_control_total: None,
+                    declared_group_count: None,
+                    declared_record_count: None,
+                    record_count: None,
+                });
+            }
+        }
+        files
+    }
+}
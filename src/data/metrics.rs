@@ -0,0 +1,76 @@
+use std::time::{Duration, Instant};
+
+use ast;
+use ast::parse::Parsed;
+
+use super::{raw_records, File, FileProcessError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ParseMetrics {
+    pub bytes: usize,
+    // Continuations are counted with the record they continue in `records`, and on their own in
+    // `physical_records`.
+    pub records: usize,
+    pub physical_records: usize,
+    // Splitting the input into records and fields.
+    pub tokenize: Duration,
+    // Reading numbers, dates and the rest out of the fields.
+    pub field_parse: Duration,
+    // Building and checking the `File`.
+    pub conversion: Duration,
+}
+
+impl ParseMetrics {
+    pub fn total(&self) -> Duration {
+        self.tokenize + self.field_parse + self.conversion
+    }
+}
+
+impl File {
+    // Like `process`, but with each stage run to completion before the next so they can be timed
+    // separately. Stages after an error are left at zero.
+    pub fn process_instrumented<'a>(
+        file: &'a [u8],
+    ) -> (Result<File, FileProcessError<'a>>, ParseMetrics) {
+        let mut metrics = ParseMetrics {
+            bytes: file.len(),
+            ..ParseMetrics::default()
+        };
+
+        let start = Instant::now();
+        let raw_records = raw_records(file);
+        metrics.tokenize = start.elapsed();
+        let raw_records = match raw_records {
+            Ok(raw_records) => raw_records,
+            Err(e) => return (Err(e), metrics),
+        };
+        metrics.records = raw_records.len();
+        metrics.physical_records = raw_records.iter().map(|&(_, physical)| physical).sum();
+
+        let start = Instant::now();
+        let parsed_records = raw_records
+            .iter()
+            .map(|&(ref r, physical)| {
+                ast::Record::parse(r)
+                    .map(|r| (r, physical))
+                    .map_err(FileProcessError::FieldParse)
+            })
+            .collect::<Result<Vec<_>, _>>();
+        metrics.field_parse = start.elapsed();
+        let parsed_records = match parsed_records {
+            Ok(parsed_records) => parsed_records,
+            Err(e) => return (Err(e), metrics),
+        };
+
+        let start = Instant::now();
+        let file = ast::convert::Converter::fold_results_with_count(
+            &mut parsed_records.into_iter().map(Ok),
+            |e| match e {
+                Some(e) => e.into(),
+                None => FileProcessError::UnfinishedConversion,
+            },
+        );
+        metrics.conversion = start.elapsed();
+        (file, metrics)
+    }
+}
@@ -1,16 +1,71 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::error::Error;
 use std::fmt::{self, Write};
-use std::io::Read;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::slice;
+use std::str::FromStr;
+use std::sync::Arc;
 
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
 use nom;
 use penny::{Currency, Money};
 
 use ast;
+use ast::convert::Converter;
 use ast::parse::Parsed;
 use parse;
+use write;
 
 mod type_codes;
 pub use self::type_codes::*;
+mod availability;
+pub use self::availability::*;
+mod borrowed;
+pub use self::borrowed::*;
+mod builder;
+pub use self::builder::*;
+mod code_table;
+pub use self::code_table::*;
+#[cfg(feature = "serde-serialize")]
+pub mod penny_serde;
+#[cfg(feature = "serde-serialize")]
+mod compact;
+#[cfg(feature = "serde-serialize")]
+pub use self::compact::*;
+mod context;
+pub use self::context::*;
+mod diff;
+pub use self::diff::*;
+mod handler;
+pub use self::handler::*;
+mod merge;
+pub use self::merge::*;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "metrics")]
+pub use self::metrics::*;
+mod money;
+pub use self::money::*;
+#[cfg(feature = "mt940")]
+mod mt940;
+mod pretty;
+pub use self::pretty::*;
+mod redact;
+pub use self::redact::*;
+mod rescale;
+pub use self::rescale::*;
+mod statement;
+pub use self::statement::*;
+mod validate;
+pub use self::validate::*;
+mod visit;
+pub use self::visit::*;
+mod warning;
+pub use self::warning::*;
 
 // From std::fmt::builders (MIT/Apache-2.0)
 struct PadAdapter<'a, 'b: 'a> {
@@ -56,17 +111,20 @@ impl<'a, 'b: 'a> Write for PadAdapter<'a, 'b> {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub enum BaiDateTime {
     DateTime(NaiveDateTime),
     DateEndOfDay(NaiveDate),
+    // No time was given, which is unlike end of day in that nothing is known about the time.
+    Date(NaiveDate),
 }
 impl fmt::Display for BaiDateTime {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             BaiDateTime::DateTime(ref dt) => write!(f, "{}", dt),
             BaiDateTime::DateEndOfDay(ref d) => write!(f, "{}Teod", d),
+            BaiDateTime::Date(ref d) => write!(f, "{}", d),
         }
     }
 }
@@ -85,18 +143,31 @@ impl BaiDateTime {
     pub fn date(&self) -> NaiveDate {
         match *self {
             BaiDateTime::DateTime(dt) => dt.date(),
-            BaiDateTime::DateEndOfDay(d) => d.clone(),
+            BaiDateTime::DateEndOfDay(d) | BaiDateTime::Date(d) => d,
         }
     }
     pub fn time(&self) -> Option<NaiveTime> {
         match *self {
             BaiDateTime::DateTime(dt) => Some(dt.time()),
-            BaiDateTime::DateEndOfDay(_d) => None,
+            BaiDateTime::DateEndOfDay(_) | BaiDateTime::Date(_) => None,
         }
     }
 }
 
-#[derive(Debug, Clone)]
+// Chronological. A date without a time sorts as the start of its day, before midnight, and end
+// of day sorts after every time on its day, which is when the data is as of.
+impl Ord for BaiDateTime {
+    fn cmp(&self, other: &Self) -> Ordering {
+        BaiDateOrTime::from(self.clone()).cmp(&other.clone().into())
+    }
+}
+impl PartialOrd for BaiDateTime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub enum BaiDateOrTime {
     Date(NaiveDate),
@@ -122,6 +193,7 @@ impl From<BaiDateTime> for BaiDateOrTime {
         match datetime {
             BaiDateTime::DateTime(dt) => BaiDateOrTime::DateTime(dt),
             BaiDateTime::DateEndOfDay(d) => BaiDateOrTime::DateEndOfDay(d),
+            BaiDateTime::Date(d) => BaiDateOrTime::Date(d),
         }
     }
 }
@@ -141,47 +213,206 @@ impl BaiDateOrTime {
             BaiDateOrTime::DateEndOfDay(d) => Some(d.into()),
         }
     }
+
+    fn sort_key(&self) -> (NaiveDate, u8, Option<NaiveTime>) {
+        match *self {
+            BaiDateOrTime::Date(d) => (d, 0, None),
+            BaiDateOrTime::DateTime(dt) => (dt.date(), 1, Some(dt.time())),
+            BaiDateOrTime::DateEndOfDay(d) => (d, 2, None),
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+// Ordered the same way as `BaiDateTime`.
+impl Ord for BaiDateOrTime {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+impl PartialOrd for BaiDateOrTime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
-pub struct File {
-    pub sender: Party,
-    pub receiver: Party,
+pub struct File<S = String> {
+    pub sender: Party<S>,
+    pub receiver: Party<S>,
     pub creation: BaiDateTime,
     pub ident: FileIdent,
-    pub groups: Vec<Group>,
+    // For files sent as fixed-width records without line endings. `process_with_options` splits
+    // such files up by this length with `ParseOptions::detect_record_width`.
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub physical_record_len: Option<u16>,
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub block_size: Option<u16>,
+    // 2 for BAI2. None if the header left it empty.
+    pub format_version: Option<u8>,
+    pub groups: Vec<Group<S>>,
+    // From the 99 File Trailer as read, and None for files that weren't parsed.
+    pub declared_control_total: Option<i64>,
+    pub declared_group_count: Option<usize>,
+    pub declared_record_count: Option<usize>,
+    // Physical records read from the header to the trailer, continuations included, to check the
+    // declared count against. None for files that weren't parsed.
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub record_count: Option<usize>,
+}
+
+impl<'a, S> IntoIterator for &'a File<S> {
+    type Item = &'a Group<S>;
+    type IntoIter = slice::Iter<'a, Group<S>>;
+    fn into_iter(self) -> slice::Iter<'a, Group<S>> {
+        self.groups.iter()
+    }
+}
+
+// The `Display` tree of a file or part of one, with account numbers, references and text
+// redacted as `File::redact` would when `redacted` is set.
+struct Tree<'a, T: 'a> {
+    node: &'a T,
+    redacted: bool,
+}
+
+impl<'a, T> Tree<'a, T> {
+    fn child<U>(&self, node: &'a U) -> Tree<'a, U> {
+        Tree {
+            node,
+            redacted: self.redacted,
+        }
+    }
 }
 
+// `{:#}` writes the file in BAI2 format.
 impl fmt::Display for File {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            // `fmt::Error` can't say why, so control totals that overflow are just an error.
+            return write::file(f, self).map_err(|_| fmt::Error);
+        }
+        Tree {
+            node: self,
+            redacted: false,
+        }.fmt(f)
+    }
+}
+
+impl<'a> fmt::Display for Tree<'a, File> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let file = self.node;
         write!(
             f,
             "File: {sender} to {receiver} at {creation} ({ident}) {{\n",
-            sender = self.sender,
-            receiver = self.receiver,
-            creation = self.creation,
-            ident = self.ident
+            sender = file.sender,
+            receiver = file.receiver,
+            creation = file.creation,
+            ident = file.ident
         ).and_then(|()| {
             let mut f = PadAdapter::new(f);
-            for group in &self.groups {
-                write!(f, "{},\n", group)?
+            for group in &file.groups {
+                write!(f, "{},\n", self.child(group))?
             };
             Ok(())
         }).and_then(|()| write!(f, "}}"))
     }
 }
 
-#[derive(Debug, Clone)]
+impl File {
+    // Net of credits less debits in each currency. `Currency` isn't `Hash`, so totals are keyed
+    // by currency code. Loan and non-monetary details count towards neither.
+    pub fn totals_by_currency(&self) -> HashMap<&'static str, Money> {
+        let mut totals = HashMap::new();
+        for group in &self.groups {
+            for account in &group.accounts {
+                let cur = account.effective_currency(group);
+                let amount = account
+                    .transaction_details
+                    .iter()
+                    .filter_map(|td| td.signed_amount())
+                    .sum::<i64>();
+                let total = totals
+                    .entry(cur.info().code())
+                    .or_insert_with(|| Money::new(0, cur));
+                *total = Money::new(total.amount() + amount, cur);
+            }
+        }
+        totals
+    }
+
+    // A single line for logs, unlike the `Display` tree and `Debug` output. Party identifiers
+    // are cut to `LOG_PARTY_LEN` characters, so only the number of currencies affects its size.
+    pub fn log_line(&self) -> String {
+        fn party(id: &str) -> &str {
+            match id.char_indices().nth(LOG_PARTY_LEN) {
+                Some((end, _)) => &id[..end],
+                None => id,
+            }
+        }
+
+        let transactions = self.groups
+            .iter()
+            .flat_map(|g| &g.accounts)
+            .map(|a| a.transaction_details.len())
+            .sum::<usize>();
+        let mut line = String::with_capacity(2 * 4 * LOG_PARTY_LEN + 128);
+        let _ = write!(
+            line,
+            "file={} sender={} receiver={} groups={} transactions={} total=",
+            self.ident.0,
+            party(&self.sender.0),
+            party(&self.receiver.0),
+            self.groups.len(),
+            transactions
+        );
+        let totals = self.totals_by_currency();
+        let mut codes = totals.keys().collect::<Vec<_>>();
+        codes.sort();
+        for (i, code) in codes.into_iter().enumerate() {
+            if i > 0 {
+                line.push(',');
+            }
+            let _ = write!(line, "{}:{}", code, totals[code].amount());
+        }
+        line
+    }
+
+    // Intentionally reorders records for canonical output: accounts within each group by account
+    // number, and transactions within each account by type code then bank reference number.
+    // Groups and ties keep their file order.
+    pub fn normalized(&self) -> File {
+        let mut file = self.clone();
+        for group in &mut file.groups {
+            group.accounts.sort_by(|a, b| {
+                a.customer_account
+                    .normalized()
+                    .cmp(b.customer_account.normalized())
+                    .then_with(|| a.customer_account.0.cmp(&b.customer_account.0))
+            });
+            for account in &mut group.accounts {
+                account.transaction_details.sort_by(|a, b| {
+                    let key = |td: &TransactionDetail| {
+                        (u16::from(td.code), td.bank_ref_num.as_ref().map(|r| r.0.clone()))
+                    };
+                    key(a).cmp(&key(b))
+                });
+            }
+        }
+        file
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
-pub struct Party(pub String);
-impl fmt::Display for Party {
+pub struct Party<S = String>(pub S);
+impl<S: AsRef<str>> fmt::Display for Party<S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "\"{}\"", self.0)
+        write!(f, "\"{}\"", self.0.as_ref())
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub struct FileIdent(pub u32);
 impl fmt::Display for FileIdent {
@@ -190,52 +421,82 @@ impl fmt::Display for FileIdent {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
-pub struct Group {
-    pub ultimate_receiver: Option<Party>,
+pub struct Group<S = String> {
+    pub ultimate_receiver: Option<Party<S>>,
     // Optional because banks seem to treat it as such ( :( )
-    pub originator: Option<Party>,
+    pub originator: Option<Party<S>>,
     pub status: GroupStatus,
     pub as_of: BaiDateOrTime,
+    #[cfg_attr(feature = "serde-serialize", serde(with = "::data::penny_serde::option_currency"))]
     pub currency: Option<Currency>,
     pub as_of_date_mod: Option<AsOfDateModifier>,
-    pub accounts: Vec<Account>,
+    pub accounts: Vec<Account<S>>,
+    // From the 98 Group Trailer as read.
+    pub declared_control_total: Option<i64>,
+    pub declared_account_count: Option<usize>,
+    pub declared_record_count: Option<usize>,
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub record_count: Option<usize>,
 }
 
-impl Group {
+impl<S> Group<S> {
     pub fn currency_def(&self) -> Currency {
         self.currency.unwrap_or(Currency::USD)
     }
+
+    // Aggregators may say which bank originated each group; otherwise it's the file's sender.
+    pub fn effective_originator<'a>(&'a self, file: &'a File<S>) -> &'a Party<S> {
+        self.originator.as_ref().unwrap_or(&file.sender)
+    }
+}
+
+impl<'a, S> IntoIterator for &'a Group<S> {
+    type Item = &'a Account<S>;
+    type IntoIter = slice::Iter<'a, Account<S>>;
+    fn into_iter(self) -> slice::Iter<'a, Account<S>> {
+        self.accounts.iter()
+    }
 }
 
 impl fmt::Display for Group {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Group {}: ", self.status).and_then(|()| {
-            match self.originator {
+        Tree {
+            node: self,
+            redacted: false,
+        }.fmt(f)
+    }
+}
+
+impl<'a> fmt::Display for Tree<'a, Group> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let group = self.node;
+        write!(f, "Group {}: ", group.status).and_then(|()| {
+            match group.originator {
                 Some(ref originator) => write!(f, "{}", originator),
                 None => write!(f, "Unknown originator"),
             }
         }).and_then(|()| {
-            match self.ultimate_receiver {
+            match group.ultimate_receiver {
                 None => Ok(()),
                 Some(ref ultimate_receiver) => write!(f, " to {}", ultimate_receiver),
             }
         }).and_then(|()| {
-            write!(f, " at {}", self.as_of)
+            write!(f, " at {}", group.as_of)
         }).and_then(|()| {
-            match self.as_of_date_mod {
+            match group.as_of_date_mod {
                 None => Ok(()),
                 Some(ref as_of_date_mod) => write!(f, " ({})", as_of_date_mod),
             }
         }).and_then(|()| {
-            write!(f, " in {}", self.currency_def())
+            write!(f, " in {}", group.currency_def())
         }).and_then(|()| {
             write!(f, " {{\n")
         }).and_then(|()| {
             let mut f = PadAdapter::new(f);
-            for account in &self.accounts {
-                write!(f, "{},\n", account)?;
+            for account in &group.accounts {
+                write!(f, "{},\n", self.child(account))?;
             }
             Ok(())
         }).and_then(|()| write!(f, "}}"))
@@ -243,68 +504,261 @@ impl fmt::Display for Group {
 }
 
 enum_mapping! {
-    #[derive(Debug, Clone, Copy)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     #[cfg_attr(feature="serde-serialize", derive(Serialize, Deserialize))]
     pub GroupStatus(u8) {
-        Update(1),
-        Deletion(2),
-        Correction(3),
-        TestOnly(4),
+        Update(1, "Update"),
+        Deletion(2, "Deletion"),
+        Correction(3, "Correction"),
+        TestOnly(4, "Test Only"),
     }
 }
 impl fmt::Display for GroupStatus {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use self::GroupStatus as GS;
-        match *self {
-            GS::Update => write!(f, "Update"),
-            GS::Deletion => write!(f, "Deletion"),
-            GS::Correction => write!(f, "Correction"),
-            GS::TestOnly => write!(f, "Test Only"),
-        }
+        f.write_str(self.description())
     }
 }
 
 enum_mapping! {
-    #[derive(Debug, Clone, Copy)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     #[cfg_attr(feature="serde-serialize", derive(Serialize, Deserialize))]
     pub AsOfDateModifier(u8) {
-        InterimPrevious(1),
-        FinalPrevious(2),
-        InterimSame(3),
-        FinalSame(4),
+        InterimPrevious(1, "Interim previous-day data"),
+        FinalPrevious(2, "Final previous-day data"),
+        InterimSame(3, "Interim same-day data"),
+        FinalSame(4, "Final same-day data"),
     }
 }
 impl fmt::Display for AsOfDateModifier {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use self::AsOfDateModifier as AODM;
-        match *self {
-            AODM::InterimPrevious => write!(f, "Interim previous-day data"),
-            AODM::FinalPrevious => write!(f, "Final previous-day data"),
-            AODM::InterimSame => write!(f, "Interim same-day data"),
-            AODM::FinalSame => write!(f, "Final same-day data"),
+        f.write_str(self.description())
+    }
+}
+
+impl AsOfDateModifier {
+    pub fn is_final(self) -> bool {
+        match self {
+            AsOfDateModifier::FinalPrevious | AsOfDateModifier::FinalSame => true,
+            _ => false,
         }
     }
+    pub fn is_interim(self) -> bool {
+        !self.is_final()
+    }
 }
 
-#[derive(Debug, Clone)]
+// Whether a file is final or interim data for its day, going by its groups' as-of-date
+// modifiers. Groups without a modifier are `Unknown`.
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
-pub struct Account {
-    pub customer_account: AccountNumber,
+pub enum Finality {
+    Final,
+    Intraday,
+    Unknown,
+    // The groups disagree, so each group's modifier is given, in order.
+    Mixed(Vec<Option<AsOfDateModifier>>),
+}
+
+impl<S> File<S> {
+    pub fn finality(&self) -> Finality {
+        let group_finality = |group: &Group<S>| match group.as_of_date_mod {
+            Some(m) if m.is_final() => Finality::Final,
+            Some(_) => Finality::Intraday,
+            None => Finality::Unknown,
+        };
+        let mut groups = self.groups.iter().map(group_finality);
+        let first = groups.next().unwrap_or(Finality::Unknown);
+        if groups.all(|f| f == first) {
+            first
+        } else {
+            Finality::Mixed(self.groups.iter().map(|g| g.as_of_date_mod).collect())
+        }
+    }
+
+    // Only true when every group is final.
+    pub fn is_final(&self) -> bool {
+        self.finality() == Finality::Final
+    }
+
+    // Only true when every group is interim.
+    pub fn is_intraday(&self) -> bool {
+        self.finality() == Finality::Intraday
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct Account<S = String> {
+    pub customer_account: AccountNumber<S>,
+    #[cfg_attr(feature = "serde-serialize", serde(with = "::data::penny_serde::option_currency"))]
     pub currency: Option<Currency>,
     pub infos: Vec<AccountInfo>,
-    pub transaction_details: Vec<TransactionDetail>,
+    pub transaction_details: Vec<TransactionDetail<S>>,
+    // From the 49 Account Trailer as read.
+    pub declared_control_total: Option<i64>,
+    pub declared_record_count: Option<usize>,
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub record_count: Option<usize>,
 }
 
-impl Account {
+impl<S> Account<S> {
     pub fn currency_def(&self, group_cur: Currency) -> Currency {
         self.currency.unwrap_or(group_cur)
     }
+
+    // The account's own currency takes precedence, then the group's, then USD.
+    pub fn effective_currency(&self, group: &Group<S>) -> Currency {
+        self.currency_def(group.currency_def())
+    }
+
+    // The amount of the first status with this code.
+    pub fn status_amount(&self, status: AccountStatus) -> Option<i64> {
+        self.infos.iter().filter_map(|i| match *i {
+            AccountInfo::Status {
+                code: StatusCode::Account(code),
+                amount,
+                ..
+            } if code == status => amount,
+            _ => None,
+        }).next()
+    }
+
+    // Every status and summary, in the order the file gave them.
+    pub fn balances(&self, cur: Currency) -> Vec<(u16, &'static str, Option<Money>)> {
+        self.infos
+            .iter()
+            .map(|info| (info.numeric_code(), info.description(), info.amount_money(cur)))
+            .collect()
+    }
+
+    pub fn opening_ledger(&self, cur: Currency) -> Option<Money> {
+        self.status_amount(AccountStatus::OpeningLedger).map(|a| Money::new(a, cur))
+    }
+
+    pub fn closing_ledger(&self, cur: Currency) -> Option<Money> {
+        self.status_amount(AccountStatus::ClosingLedger).map(|a| Money::new(a, cur))
+    }
+
+    pub fn opening_available(&self, cur: Currency) -> Option<Money> {
+        self.status_amount(AccountStatus::OpeningAvail).map(|a| Money::new(a, cur))
+    }
+
+    pub fn closing_available(&self, cur: Currency) -> Option<Money> {
+        self.status_amount(AccountStatus::ClosingAvail).map(|a| Money::new(a, cur))
+    }
+}
+
+// How `Account::dedup_infos` combines statuses or summaries with the same type code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum DedupStrategy {
+    // Amounts and item counts are added up, and the first funds type is kept.
+    Sum,
+    FirstWins,
+    LastWins,
+}
+
+impl<S> Account<S> {
+    // Leaves one status or summary per type code, where the first of them was. This changes the
+    // account in place, and whatever the strategy drops is gone, so the account may no longer
+    // match its trailer's control total. When summing an amount or item count would overflow,
+    // that status or summary is kept as its own entry after the others instead of being merged.
+    pub fn dedup_infos(&mut self, strategy: DedupStrategy) {
+        fn sum<T>(
+            total: Option<T>,
+            amount: Option<T>,
+            add: fn(T, T) -> Option<T>,
+        ) -> Option<Option<T>> {
+            match (total, amount) {
+                (Some(total), Some(amount)) => add(total, amount).map(Some),
+                (total, amount) => Some(total.or(amount)),
+            }
+        }
+
+        let mut deduped: Vec<AccountInfo> = Vec::with_capacity(self.infos.len());
+        for info in self.infos.drain(..) {
+            let existing = deduped.iter().position(|d| {
+                d.numeric_code() == info.numeric_code() && d.is_summary() == info.is_summary()
+            });
+            let i = match existing {
+                Some(i) => i,
+                None => {
+                    deduped.push(info);
+                    continue;
+                }
+            };
+            let overflowed = match strategy {
+                DedupStrategy::FirstWins => false,
+                DedupStrategy::LastWins => {
+                    deduped[i] = info;
+                    continue;
+                }
+                DedupStrategy::Sum => match (&mut deduped[i], &info) {
+                    (
+                        &mut AccountInfo::Status { amount: ref mut total, .. },
+                        &AccountInfo::Status { amount, .. },
+                    ) => match sum(*total, amount, i64::checked_add) {
+                        Some(sum) => {
+                            *total = sum;
+                            false
+                        }
+                        None => true,
+                    },
+                    (
+                        &mut AccountInfo::Summary {
+                            amount: ref mut total,
+                            item_count: ref mut count,
+                            ..
+                        },
+                        &AccountInfo::Summary { amount, item_count, .. },
+                    ) => match (
+                        sum(*total, amount, u64::checked_add),
+                        sum(*count, item_count, u32::checked_add),
+                    ) {
+                        (Some(amount), Some(item_count)) => {
+                            *total = amount;
+                            *count = item_count;
+                            false
+                        }
+                        _ => true,
+                    },
+                    _ => false,
+                },
+            };
+            if overflowed {
+                deduped.push(info);
+            }
+        }
+        self.infos = deduped;
+    }
+}
+
+impl<'a, S> IntoIterator for &'a Account<S> {
+    type Item = &'a TransactionDetail<S>;
+    type IntoIter = slice::Iter<'a, TransactionDetail<S>>;
+    fn into_iter(self) -> slice::Iter<'a, TransactionDetail<S>> {
+        self.transaction_details.iter()
+    }
 }
 
 impl fmt::Display for Account {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Account {} ", self.customer_account).and_then(|()| {
-            match self.currency {
+        Tree {
+            node: self,
+            redacted: false,
+        }.fmt(f)
+    }
+}
+
+impl<'a> fmt::Display for Tree<'a, Account> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let account = self.node;
+        if self.redacted {
+            write!(f, "Account {} ", account.customer_account.masked())
+        } else {
+            write!(f, "Account {} ", account.customer_account)
+        }.and_then(|()| {
+            match account.currency {
                 None => Ok(()),
                 Some(c) => write!(f, "({}) ", c),
             }
@@ -312,7 +766,7 @@ impl fmt::Display for Account {
             write!(f, "{{\n    Infos: [\n")
         }).and_then(|()| {
             let mut f = PadAdapter::new_levels(f, 2);
-            for info in &self.infos {
+            for info in &account.infos {
                 write!(f, "{},\n", info)?;
             }
             Ok(())
@@ -320,8 +774,8 @@ impl fmt::Display for Account {
             write!(f, "    ],\n    Transaction Details: [\n")
         }).and_then(|()| {
             let mut f = PadAdapter::new_levels(f, 2);
-            for details in &self.transaction_details {
-                write!(f, "{},\n", details)?;
+            for details in &account.transaction_details {
+                write!(f, "{},\n", self.child(details))?;
             }
             Ok(())
         }).and_then(|()| {
@@ -330,7 +784,7 @@ impl fmt::Display for Account {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub enum AccountInfo {
     Summary {
@@ -347,20 +801,52 @@ pub enum AccountInfo {
 }
 
 impl AccountInfo {
-    pub fn amount_money(&self, account_cur: Currency) -> Option<Money> {
-        use self::AccountInfo as AI;
+    pub fn is_summary(&self) -> bool {
         match *self {
-            AI::Summary {
-                amount: Some(amount),
-                ..
-            } => Some(Money::new(amount as i64, account_cur)),
-            AI::Status {
-                amount: Some(amount),
-                ..
-            } => Some(Money::new(amount, account_cur)),
-            _ => None,
+            AccountInfo::Summary { .. } => true,
+            AccountInfo::Status { .. } => false,
         }
     }
+
+    pub fn numeric_code(&self) -> u16 {
+        match *self {
+            AccountInfo::Summary { code, .. } => code.into(),
+            AccountInfo::Status { code, .. } => code.into(),
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match *self {
+            AccountInfo::Summary { code, .. } => code.description(),
+            AccountInfo::Status { code, .. } => code.description(),
+        }
+    }
+
+    pub fn amount(&self) -> Option<i64> {
+        match *self {
+            AccountInfo::Summary { amount, .. } => amount.map(|amount| amount as i64),
+            AccountInfo::Status { amount, .. } => amount,
+        }
+    }
+
+    // The spec sets aside no type code for "unavailable" (999 is just the last of the codes banks
+    // define themselves). A bank that couldn't provide a balance leaves its amount out, and that's
+    // kept as None rather than zero.
+    pub fn is_unavailable(&self) -> bool {
+        self.amount().is_none()
+    }
+
+    // Statuses don't have item counts.
+    pub fn item_count(&self) -> Option<u32> {
+        match *self {
+            AccountInfo::Summary { item_count, .. } => item_count,
+            AccountInfo::Status { .. } => None,
+        }
+    }
+
+    pub fn amount_money(&self, account_cur: Currency) -> Option<Money> {
+        self.amount().map(|amount| Money::new(amount, account_cur))
+    }
 }
 
 impl fmt::Display for AccountInfo {
@@ -399,17 +885,30 @@ impl fmt::Display for AccountInfo {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
-pub struct AccountNumber(pub String);
-impl fmt::Display for AccountNumber {
+pub struct AccountNumber<S = String>(pub S);
+impl<S: AsRef<str>> fmt::Display for AccountNumber<S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "a#{:?}", self.0)
+        write!(f, "a#{:?}", self.0.as_ref())
     }
 }
 
-#[derive(Debug, Clone)]
+impl AccountNumber {
+    // Banks aren't consistent about padding account numbers, so surrounding whitespace and
+    // leading zeros are dropped when matching accounts across files.
+    pub fn normalized(&self) -> &str {
+        let trimmed = self.0.trim();
+        match trimmed.trim_start_matches('0') {
+            "" if !trimmed.is_empty() => "0",
+            normalized => normalized,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum FundsType {
     Unknown, // Z (default)
     ImmediateAvail, // 0
@@ -469,7 +968,16 @@ impl fmt::Display for FundsType {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+impl FundsType {
+    // `funds` is None when the record left the funds type field empty, which the spec treats the
+    // same as an explicit Z. The distinction is kept in the data so files can be written back as
+    // they were read.
+    pub fn effective(funds: &Option<FundsType>) -> FundsType {
+        funds.clone().unwrap_or(FundsType::Unknown)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub struct DistributedAvailDistribution {
     pub days: u32,
@@ -482,41 +990,148 @@ impl DistributedAvailDistribution {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
-pub struct TransactionDetail {
+pub struct TransactionDetail<S = String> {
     pub code: DetailCode,
     pub amount: Option<i64>,
     pub funds: Option<FundsType>,
-    pub bank_ref_num: Option<ReferenceNum>,
-    pub customer_ref_num: Option<ReferenceNum>,
+    pub bank_ref_num: Option<ReferenceNum<S>>,
+    pub customer_ref_num: Option<ReferenceNum<S>>,
+    // Coded fields between the customer reference number and the text, for dialects that have
+    // them. Only split from the text when parsed with `ParseOptions::detail_extensions`.
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub extensions: Vec<String>,
+    // One line per physical record, in order: the 16's own text when it has any, then one for
+    // each 88 Continuation. Lines are kept as sent, so fixed-format memos can rely on them.
     pub text: Option<Vec<String>>,
+    // The 16 record and its continuations as parsed, with the line breaks between them. Only
+    // kept when parsed with `ParseOptions::keep_raw_records`.
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub raw_record: Option<String>,
 }
 
-impl TransactionDetail {
+impl<S> TransactionDetail<S> {
+    // Everything else starts out absent, to be filled in with the `with_` methods.
+    pub fn new(code: DetailCode, amount: Option<i64>) -> Self {
+        TransactionDetail {
+            code,
+            amount,
+            funds: None,
+            bank_ref_num: None,
+            customer_ref_num: None,
+            extensions: Vec::new(),
+            text: None,
+            raw_record: None,
+        }
+    }
+
+    pub fn with_funds(mut self, funds: FundsType) -> Self {
+        self.funds = Some(funds);
+        self
+    }
+
+    pub fn with_bank_ref(mut self, bank_ref_num: ReferenceNum<S>) -> Self {
+        self.bank_ref_num = Some(bank_ref_num);
+        self
+    }
+
+    pub fn with_customer_ref(mut self, customer_ref_num: ReferenceNum<S>) -> Self {
+        self.customer_ref_num = Some(customer_ref_num);
+        self
+    }
+
+    // Each line of `text` becomes a line of its own, as `text_joined("\n")` would give back.
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text = Some(text.split('\n').map(String::from).collect());
+        self
+    }
+
     pub fn amount_money(&self, account_cur: Currency) -> Option<Money> {
         self.amount.map(|amount| Money::new(amount, account_cur))
     }
+
+    // Credits are positive and debits negative. Loan and non-monetary details have no sign.
+    pub fn signed_amount(&self) -> Option<i64> {
+        self.amount.and_then(|amount| if self.code.is_credit() {
+            Some(amount)
+        } else if self.code.is_debit() {
+            Some(-amount)
+        } else {
+            None
+        })
+    }
+
+    // Takes the first `count` fields of the first line of text as extensions. Text left empty
+    // is None, as it would have been without the extensions.
+    fn split_extensions(&mut self, count: usize) {
+        let mut text = match self.text.take() {
+            Some(text) => text,
+            None => return,
+        };
+        if !text.is_empty() {
+            let first = text.remove(0);
+            let mut fields = first.splitn(count + 1, ',');
+            self.extensions = fields.by_ref().take(count).map(String::from).collect();
+            match fields.next() {
+                Some("") | Some("/") if text.is_empty() => {}
+                Some(rest) => text.insert(0, rest.to_owned()),
+                None => {}
+            }
+        }
+        if !text.is_empty() {
+            self.text = Some(text);
+        }
+    }
+
+    pub fn text_joined(&self, sep: &str) -> Option<String> {
+        self.text.as_ref().map(|text| text.join(sep))
+    }
+
+    // Each line trimmed, without the lines left empty.
+    pub fn text_trimmed(&self) -> Option<Vec<&str>> {
+        self.text.as_ref().map(|text| {
+            text.iter()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty())
+                .collect()
+        })
+    }
 }
 impl fmt::Display for TransactionDetail {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Transaction: {}", self.code)?;
-        if let Some(amount) = self.amount {
+        Tree {
+            node: self,
+            redacted: false,
+        }.fmt(f)
+    }
+}
+
+impl<'a> fmt::Display for Tree<'a, TransactionDetail> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let detail = self.node;
+        write!(f, "Transaction: {}", detail.code)?;
+        if let Some(amount) = detail.amount {
             write!(f, ": {}", amount)?;
         }
         write!(f, " {{\n")?;
         {
             let mut f = PadAdapter::new(f);
-            if let Some(funds) = self.funds.as_ref() {
+            if let Some(funds) = detail.funds.as_ref() {
                 write!(f, "Funds: {},\n", funds)?;
             }
-            if let Some(bank_ref_num) = self.bank_ref_num.as_ref() {
-                write!(f, "Bank: {},\n", bank_ref_num)?;
+            if !self.redacted {
+                if let Some(bank_ref_num) = detail.bank_ref_num.as_ref() {
+                    write!(f, "Bank: {},\n", bank_ref_num)?;
+                }
+                if let Some(customer_ref_num) = detail.customer_ref_num.as_ref() {
+                    write!(f, "Customer: {},\n", customer_ref_num)?;
+                }
             }
-            if let Some(customer_ref_num) = self.customer_ref_num.as_ref() {
-                write!(f, "Customer: {},\n", customer_ref_num)?;
+            if !detail.extensions.is_empty() {
+                write!(f, "Extensions: {:?},\n", detail.extensions)?;
             }
-            if let Some(text) = self.text.as_ref() {
+            if let Some(text) = detail.text.as_ref().filter(|_| !self.redacted) {
                 write!(f, "Text: {:#?},\n", text)?;
             }
         }
@@ -524,37 +1139,504 @@ impl fmt::Display for TransactionDetail {
     }
 }
 
-#[derive(Debug, Clone)]
+/// A bank or customer reference number, kept verbatim from the source record.
+///
+/// Leading zeros and whitespace are significant for matching, so they're never trimmed.
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
-pub struct ReferenceNum(pub String);
-impl fmt::Display for ReferenceNum {
+pub struct ReferenceNum<S = String>(pub S);
+impl<S: AsRef<str>> fmt::Display for ReferenceNum<S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "r#{:?}", self.0)
+        write!(f, "r#{:?}", self.0.as_ref())
     }
 }
 
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum FileProcessError<'a> {
-    Parse(nom::ErrorKind),
+    // `offset` is in bytes from the start of the input, and `snippet` is the start of the line
+    // the parser stopped on.
+    Parse {
+        kind: nom::ErrorKind,
+        offset: usize,
+        snippet: String,
+    },
+    // A line that isn't a record.
+    UnparsedRecord { record_number: usize, offset: usize },
+    // An 88 Continuation with no record before it to continue, such as one after a record that
+    // has already ended.
+    OrphanContinuation { record_number: usize },
+    // A record after the 99 File Trailer. Blank lines and padding after it are fine.
+    AfterTrailer { record_number: usize },
+    // A header or trailer where `expected` has to come first, such as a 98 Group Trailer without
+    // its 02 Group Header.
+    UnbalancedRecords {
+        expected: ast::RecordField,
+        found: ast::RecordField,
+        record_number: usize,
+    },
     FieldParse(ast::parse::ParseError<ast::Record<'a>>),
     UnfinishedConversion,
+    // The file trailer's group or physical record count, from `File::process_validated`.
+    RecordCountMismatch { expected: usize, actual: usize },
     Conversion(ast::convert::ConvertError),
+    // An amount too large for an `i64` once `AmountScale::MajorUnits` converts it to minor units.
+    AmountOverflow,
+    // A date that doesn't exist in the century `ParseOptions::year_pivot` moves it to, such as a
+    // February 29th.
+    YearPivot { date: NaiveDate, pivot: u8 },
+    // Reading the input failed. `io::Error` can't be cloned, so it's shared.
+    Io(Arc<io::Error>),
+    // The input is gzipped, going by its magic bytes or a .gz extension.
+    Gzipped,
+}
+
+impl<'a> fmt::Display for FileProcessError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FileProcessError::Parse {
+                ref kind,
+                offset,
+                ref snippet,
+            } => write!(f, "Couldn't parse records at byte {} ({:?}): {:?}", offset, snippet, kind),
+            FileProcessError::UnparsedRecord {
+                record_number,
+                offset,
+            } => write!(f, "Couldn't parse record {} at byte {}", record_number, offset),
+            FileProcessError::OrphanContinuation { record_number } => {
+                write!(f, "Record {} continues nothing", record_number)
+            }
+            FileProcessError::AfterTrailer { record_number } => {
+                write!(f, "Record {} is after the file trailer", record_number)
+            }
+            FileProcessError::UnbalancedRecords {
+                expected,
+                found,
+                record_number,
+            } => {
+                write!(
+                    f,
+                    "Expected {:?} but found {:?} at record {}",
+                    expected,
+                    found,
+                    record_number
+                )
+            }
+            FileProcessError::FieldParse(ref err) => write!(f, "Couldn't parse field {}", err),
+            FileProcessError::UnfinishedConversion => write!(f, "File ended before its trailer"),
+            FileProcessError::RecordCountMismatch { expected, actual } => {
+                write!(f, "File trailer counts {} records but found {}", expected, actual)
+            }
+            FileProcessError::AmountOverflow => write!(f, "Amount overflows in minor units"),
+            FileProcessError::YearPivot { date, pivot } => {
+                write!(f, "Date {} doesn't exist with year pivot {}", date, pivot)
+            }
+            FileProcessError::Conversion(ref err) => write!(f, "{}", err),
+            FileProcessError::Io(ref err) => write!(f, "Couldn't read input: {}", err),
+            FileProcessError::Gzipped => write!(f, "Input is gzipped, so decompress it first"),
+        }
+    }
+}
+
+impl<'a> Error for FileProcessError<'a> {
+    fn source(&self) -> Option<&(Error + 'static)> {
+        match *self {
+            FileProcessError::Conversion(ref err) => Some(err),
+            FileProcessError::Io(ref err) => Some(&**err),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> FileProcessError<'a> {
+    // Nothing in the error actually borrows the input, so it can outlive it, such as in a
+    // `Box<Error>`.
+    pub fn into_owned(self) -> FileProcessError<'static> {
+        match self {
+            FileProcessError::Parse {
+                kind,
+                offset,
+                snippet,
+            } => FileProcessError::Parse {
+                kind: kind,
+                offset: offset,
+                snippet: snippet,
+            },
+            FileProcessError::UnparsedRecord {
+                record_number,
+                offset,
+            } => FileProcessError::UnparsedRecord {
+                record_number: record_number,
+                offset: offset,
+            },
+            FileProcessError::OrphanContinuation { record_number } => {
+                FileProcessError::OrphanContinuation { record_number: record_number }
+            }
+            FileProcessError::AfterTrailer { record_number } => {
+                FileProcessError::AfterTrailer { record_number: record_number }
+            }
+            FileProcessError::UnbalancedRecords {
+                expected,
+                found,
+                record_number,
+            } => FileProcessError::UnbalancedRecords {
+                expected: expected,
+                found: found,
+                record_number: record_number,
+            },
+            FileProcessError::FieldParse(err) => {
+                FileProcessError::FieldParse(err.map_err(ast::parse::RecordError::into_static))
+            }
+            FileProcessError::UnfinishedConversion => FileProcessError::UnfinishedConversion,
+            FileProcessError::RecordCountMismatch { expected, actual } => {
+                FileProcessError::RecordCountMismatch {
+                    expected: expected,
+                    actual: actual,
+                }
+            }
+            FileProcessError::AmountOverflow => FileProcessError::AmountOverflow,
+            FileProcessError::Io(err) => FileProcessError::Io(err),
+            FileProcessError::Gzipped => FileProcessError::Gzipped,
+            FileProcessError::YearPivot { date, pivot } => FileProcessError::YearPivot {
+                date: date,
+                pivot: pivot,
+            },
+            FileProcessError::Conversion(err) => FileProcessError::Conversion(err),
+        }
+    }
+}
+
+impl<'a> From<io::Error> for FileProcessError<'a> {
+    fn from(err: io::Error) -> Self {
+        FileProcessError::Io(Arc::new(err))
+    }
+}
+
+impl<'a> From<ast::convert::ConvertError> for FileProcessError<'a> {
+    fn from(err: ast::convert::ConvertError) -> Self {
+        match err {
+            ast::convert::ConvertError::UnbalancedRecords {
+                expected,
+                found,
+                record,
+            } => FileProcessError::UnbalancedRecords {
+                expected,
+                found,
+                record_number: record,
+            },
+            err => FileProcessError::Conversion(err),
+        }
+    }
+}
+
+const SNIPPET_LEN: usize = 32;
+const LOG_PARTY_LEN: usize = 32;
+fn snippet(input: &[u8]) -> String {
+    let end = input
+        .iter()
+        .take(SNIPPET_LEN)
+        .position(|&c| c == b'\r' || c == b'\n')
+        .unwrap_or_else(|| input.len().min(SNIPPET_LEN));
+    String::from_utf8_lossy(&input[..end]).into_owned()
+}
+
+// `nom`'s simple errors don't carry a position, so the records are walked again one by one to
+// find where parsing stopped.
+fn parse_error(file: &[u8], dialect: parse::Dialect, kind: nom::ErrorKind) -> FileProcessError {
+    let mut rest = parse::skip_preamble(file);
+    while let nom::IResult::Done(next, _) = parse::dialect_file_record(rest, dialect) {
+        if next.len() == rest.len() {
+            break;
+        }
+        rest = next;
+    }
+    FileProcessError::Parse {
+        kind: kind,
+        offset: file.len() - rest.len(),
+        snippet: snippet(rest),
+    }
+}
+
+// The records are as parsed, so after any deblocking.
+fn raw_transactions(file: &[u8], dialect: parse::Dialect) -> Vec<String> {
+    let mut raw = Vec::new();
+    let mut rest = parse::skip_preamble(file);
+    while let nom::IResult::Done(next, (record, _)) = parse::dialect_file_record(rest, dialect) {
+        if next.len() == rest.len() {
+            break;
+        }
+        if let ast::RawRecord::TransactionDetail(_) = record {
+            let text = String::from_utf8_lossy(&rest[..rest.len() - next.len()]);
+            raw.push(text.trim_end_matches(|c| c == '\r' || c == '\n').to_owned());
+        }
+        rest = next;
+    }
+    raw
+}
+
+// Anything left after the last record other than whitespace or padding is an error, rather than
+// being dropped, and so is any record after the file trailer.
+fn raw_records(file: &[u8]) -> Result<Vec<(ast::RawRecord, usize)>, FileProcessError> {
+    dialect_raw_records(file, parse::Dialect::Standard)
+}
+
+fn dialect_raw_records(
+    file: &[u8],
+    dialect: parse::Dialect,
+) -> Result<Vec<(ast::RawRecord, usize)>, FileProcessError> {
+    RawRecords::new(file, dialect).collect()
+}
+
+// Reads one record at a time with the same checks as `raw_records`, so each can be handled
+// before the rest of the file is read. Nothing is read after an error.
+struct RawRecords<'a> {
+    file: &'a [u8],
+    dialect: parse::Dialect,
+    rest: &'a [u8],
+    // Physical records read so far, and how many there were up to the file trailer.
+    parsed: usize,
+    trailer: Option<usize>,
+    done: bool,
+}
+
+impl<'a> RawRecords<'a> {
+    fn new(file: &'a [u8], dialect: parse::Dialect) -> RawRecords<'a> {
+        RawRecords {
+            file,
+            dialect,
+            rest: parse::skip_preamble(file),
+            parsed: 0,
+            trailer: None,
+            done: false,
+        }
+    }
+
+    fn read(&mut self) -> Option<Result<(ast::RawRecord<'a>, usize), FileProcessError<'a>>> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        match parse::dialect_file_record(self.rest, self.dialect) {
+            nom::IResult::Done(next, _) if next.len() == self.rest.len() => {
+                Some(Err(parse_error(self.file, self.dialect, nom::ErrorKind::Many0)))
+            }
+            nom::IResult::Done(next, (record, physical)) => {
+                if let Some(before) = self.trailer {
+                    return Some(Err(FileProcessError::AfterTrailer { record_number: before + 1 }));
+                }
+                self.rest = next;
+                self.parsed += physical;
+                if let ast::RawRecord::FileTrailer(_) = record {
+                    self.trailer = Some(self.parsed);
+                }
+                Some(Ok((record, physical)))
+            }
+            nom::IResult::Error(_) => self.leftover().map(Err),
+            nom::IResult::Incomplete(_) => {
+                Some(Err(parse_error(self.file, self.dialect, nom::ErrorKind::Complete)))
+            }
+        }
+    }
+
+    // What's left once no more records can be read.
+    fn leftover(&self) -> Option<FileProcessError<'a>> {
+        // Transfers in fixed-size blocks can leave NUL or SUB (DOS end of file) padding.
+        if self.rest.iter().all(|&c| match c {
+            b' ' | b'\t' | b'\r' | b'\n' | b'\0' | b'\x1a' => true,
+            _ => false,
+        }) {
+            None
+        } else if self.rest.starts_with(b"88,") {
+            // Continuations are only parsed as part of the record they continue.
+            Some(FileProcessError::OrphanContinuation {
+                record_number: self.parsed + 1,
+            })
+        } else {
+            Some(FileProcessError::UnparsedRecord {
+                record_number: self.parsed + 1,
+                offset: self.file.len() - self.rest.len(),
+            })
+        }
+    }
+}
+
+impl<'a> Iterator for RawRecords<'a> {
+    type Item = Result<(ast::RawRecord<'a>, usize), FileProcessError<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let record = self.read();
+        match record {
+            Some(Ok(_)) => {}
+            _ => self.done = true,
+        }
+        record
+    }
+}
+
+fn convert<'a, S: From<&'a str>>(
+    file: &'a [u8],
+    converter: Converter<S>,
+) -> Result<File<S>, FileProcessError<'a>> {
+    convert_records(&raw_records(file)?, converter)
+}
+
+fn convert_records<'a, S: From<&'a str>>(
+    raw_records: &[(ast::RawRecord<'a>, usize)],
+    converter: Converter<S>,
+) -> Result<File<S>, FileProcessError<'a>> {
+    let mut parsed_records = raw_records.iter().map(|&(ref r, physical)| {
+        ast::Record::parse(r)
+            .map(|r| (r, physical))
+            .map_err(FileProcessError::FieldParse)
+    });
+    converter.fold_results_with(&mut parsed_records, |e| match e {
+        Some(e) => e.into(),
+        None => FileProcessError::UnfinishedConversion,
+    })
 }
 
 impl File {
     pub fn process<'a>(file: &'a [u8]) -> Result<File, FileProcessError<'a>> {
-        parse::file(file)
-            .to_result()
-            .map_err(FileProcessError::Parse)
-            .and_then(|raw_records| {
-                let mut parsed_records = raw_records.iter().map(|r| {
-                    ast::Record::parse(r).map_err(FileProcessError::FieldParse)
-                });
-                ast::convert::Converter::fold_results(&mut parsed_records, |e| match e {
-                    Some(e) => FileProcessError::Conversion(e),
-                    None => FileProcessError::UnfinishedConversion,
+        convert(file, Converter::new())
+    }
+
+    // Also checks the trailers' counts against the groups and physical records (88
+    // Continuations included) actually sent, which catches a transmission cut short with a
+    // trailer still on the end. Account and group trailers count records as the spec does.
+    pub fn process_validated<'a>(file: &'a [u8]) -> Result<File, FileProcessError<'a>> {
+        use ast::convert::{ConvertError, FileConvError, RecordCountConvention};
+
+        let raw_records = raw_records(file)?;
+        let actual = raw_records.iter().map(|&(_, physical)| physical).sum();
+        let converter = Converter::new().record_count_convention(RecordCountConvention::Inclusive);
+        match convert_records(&raw_records, converter) {
+            Ok(ref file) if file.declared_record_count.map_or(false, |e| e != actual) => {
+                Err(FileProcessError::RecordCountMismatch {
+                    expected: file.declared_record_count.unwrap(),
+                    actual: actual,
                 })
-            })
+            }
+            Err(FileProcessError::Conversion(ConvertError::File(FileConvError::GroupsNum {
+                expected,
+                actual,
+            }))) => Err(FileProcessError::RecordCountMismatch {
+                expected: expected,
+                actual: actual,
+            }),
+            result => result,
+        }
+    }
+
+    pub fn process_str<'a>(file: &'a str) -> Result<File, FileProcessError<'a>> {
+        File::process(file.as_bytes())
+    }
+
+    // The errors are owned, since the options can have the input rewritten before parsing.
+    pub fn process_with_options(
+        file: &[u8],
+        options: &parse::ParseOptions,
+    ) -> Result<File, FileProcessError<'static>> {
+        File::process_prepared(&options.prepare(file), options)
+    }
+
+    // Input already through `ParseOptions::prepare`.
+    fn process_prepared(
+        input: &[u8],
+        options: &parse::ParseOptions,
+    ) -> Result<File, FileProcessError<'static>> {
+        let file = dialect_raw_records(input, options.dialect)
+            .and_then(|raw_records| convert_records(&raw_records, options.converter()))
+            .map_err(FileProcessError::into_owned)?;
+        File::finish_prepared(file, input, options)
+    }
+
+    // What the options do after conversion, with `input` the file was converted from.
+    fn finish_prepared(
+        mut file: File,
+        input: &[u8],
+        options: &parse::ParseOptions,
+    ) -> Result<File, FileProcessError<'static>> {
+        if options.keep_raw_records {
+            let mut raw = raw_transactions(input, options.dialect).into_iter();
+            for group in &mut file.groups {
+                for account in &mut group.accounts {
+                    for detail in &mut account.transaction_details {
+                        detail.raw_record = raw.next();
+                    }
+                }
+            }
+        }
+        if options.detail_extensions > 0 {
+            for group in &mut file.groups {
+                for account in &mut group.accounts {
+                    for detail in &mut account.transaction_details {
+                        detail.split_extensions(options.detail_extensions);
+                    }
+                }
+            }
+        }
+        if options.amount_scale == parse::AmountScale::MajorUnits {
+            file.major_to_minor_units()?;
+        }
+        if options.year_pivot != ast::convert::DEFAULT_YEAR_PIVOT {
+            file.repivot_years(options.year_pivot)?;
+        }
+        Ok(file)
+    }
+
+    // Dates are converted with the default pivot first, then moved to the other century where
+    // the pivot differs.
+    fn repivot_years(&mut self, pivot: u8) -> Result<(), FileProcessError<'static>> {
+        type Result<T> = ::std::result::Result<T, FileProcessError<'static>>;
+        fn date(date: NaiveDate, pivot: u8) -> Result<NaiveDate> {
+            let year = (date.year() % 100) as u8;
+            let century = if year < pivot { 2000 } else { 1900 };
+            date.with_year(century + i32::from(year))
+                .ok_or(FileProcessError::YearPivot { date, pivot })
+        }
+        fn date_time(dt: &mut BaiDateTime, pivot: u8) -> Result<()> {
+            *dt = match *dt {
+                BaiDateTime::DateTime(dt) => date(dt.date(), pivot)?.and_time(dt.time()).into(),
+                BaiDateTime::DateEndOfDay(d) => date(d, pivot)?.into(),
+                BaiDateTime::Date(d) => BaiDateTime::Date(date(d, pivot)?),
+            };
+            Ok(())
+        }
+        fn date_or_time(dt: &mut BaiDateOrTime, pivot: u8) -> Result<()> {
+            *dt = match *dt {
+                BaiDateOrTime::Date(d) => BaiDateOrTime::Date(date(d, pivot)?),
+                BaiDateOrTime::DateTime(dt) => {
+                    BaiDateOrTime::DateTime(date(dt.date(), pivot)?.and_time(dt.time()))
+                }
+                BaiDateOrTime::DateEndOfDay(d) => BaiDateOrTime::DateEndOfDay(date(d, pivot)?),
+            };
+            Ok(())
+        }
+        fn funds(funds: &mut Option<FundsType>, pivot: u8) -> Result<()> {
+            match *funds {
+                Some(FundsType::ValueDated(ref mut dt)) => date_or_time(dt, pivot),
+                _ => Ok(()),
+            }
+        }
+
+        date_time(&mut self.creation, pivot)?;
+        for group in &mut self.groups {
+            date_or_time(&mut group.as_of, pivot)?;
+            for account in &mut group.accounts {
+                for info in &mut account.infos {
+                    match *info {
+                        AccountInfo::Status { funds: ref mut f, .. } |
+                        AccountInfo::Summary { funds: ref mut f, .. } => funds(f, pivot)?,
+                    }
+                }
+                for detail in &mut account.transaction_details {
+                    funds(&mut detail.funds, pivot)?;
+                }
+            }
+        }
+        Ok(())
     }
 
     pub fn from_source<T: Read>(source: &mut T) -> Result<File, String> {
@@ -562,6 +1644,81 @@ impl File {
         source
             .read_to_end(&mut file)
             .map_err(|e| format!("{:?}", e))?;
-        File::process(&file).map_err(|e| format!("{:?}", e))
+        File::process(&file).map_err(|e| e.to_string())
+    }
+
+    // Gzipped files, going by their magic bytes or a .gz extension, are an error instead of being
+    // parsed as garbage.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<File, FileProcessError<'static>> {
+        const GZIP_MAGIC: &[u8] = b"\x1f\x8b";
+        let path = path.as_ref();
+        let mut file = Vec::new();
+        fs::File::open(path)?.read_to_end(&mut file)?;
+        if file.starts_with(GZIP_MAGIC) || path.extension().map_or(false, |ext| ext == "gz") {
+            return Err(FileProcessError::Gzipped);
+        }
+        File::process(&file).map_err(FileProcessError::into_owned)
+    }
+
+    // Stops reading once the source goes past `max_bytes`, so an endless or oversized source is
+    // an error instead of being read into memory.
+    pub fn from_source_limited<T: Read>(source: &mut T, max_bytes: u64) -> Result<File, String> {
+        let mut file = Vec::new();
+        source
+            .take(max_bytes.saturating_add(1))
+            .read_to_end(&mut file)
+            .map_err(|e| format!("{:?}", e))?;
+        if file.len() as u64 > max_bytes {
+            return Err(format!("Input is larger than {} bytes", max_bytes));
+        }
+        File::process(&file).map_err(|e| e.to_string())
+    }
+}
+
+impl Group {
+    pub fn process_fragments<'a>(fragment: &'a [u8]) -> Result<Vec<Group>, FileProcessError<'a>> {
+        let raw_records = raw_records(fragment)?;
+        let mut converter = ast::convert::Converter::fragment();
+        for &(ref r, physical) in &raw_records {
+            let record = ast::Record::parse(r).map_err(FileProcessError::FieldParse)?;
+            if let ast::convert::ConverterOutput::Err(e) =
+                converter.process_physical(record, physical)
+            {
+                return Err(e.into());
+            }
+        }
+        converter
+            .into_groups()
+            .ok_or(FileProcessError::UnfinishedConversion)
+    }
+
+    pub fn process_fragment<'a>(fragment: &'a [u8]) -> Result<Group, FileProcessError<'a>> {
+        let mut groups = Group::process_fragments(fragment)?;
+        if groups.len() == 1 {
+            Ok(groups.remove(0))
+        } else {
+            Err(FileProcessError::Conversion(
+                ast::convert::ConvertError::Fragment(
+                    ast::convert::FragmentConvError::GroupsNum {
+                        expected: 1,
+                        actual: groups.len(),
+                    },
+                ),
+            ))
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for File {
+    type Error = FileProcessError<'static>;
+    fn try_from(file: &'a [u8]) -> Result<File, FileProcessError<'static>> {
+        File::process(file).map_err(FileProcessError::into_owned)
+    }
+}
+
+impl FromStr for File {
+    type Err = FileProcessError<'static>;
+    fn from_str(file: &str) -> Result<File, FileProcessError<'static>> {
+        File::process_str(file).map_err(FileProcessError::into_owned)
     }
 }
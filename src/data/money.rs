@@ -0,0 +1,68 @@
+use penny::{Currency, Money};
+
+struct MoneyStyle {
+    symbol: &'static str,
+    grouping: char,
+    decimal: char,
+}
+
+// penny has no symbols or separators, so the common currencies are listed here. Everything else is
+// written with its code.
+fn money_style(currency: Currency) -> Option<MoneyStyle> {
+    let (symbol, grouping, decimal) = match currency {
+        Currency::USD => ("$", ',', '.'),
+        Currency::EUR => ("€", '.', ','),
+        Currency::GBP => ("£", ',', '.'),
+        Currency::JPY => ("¥", ',', '.'),
+        _ => return None,
+    };
+    Some(MoneyStyle {
+        symbol,
+        grouping,
+        decimal,
+    })
+}
+
+fn group_digits(digits: &str, grouping: char) -> String {
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(grouping);
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+pub fn format_money(money: &Money) -> String {
+    let currency = money.currency();
+    let minor_units = currency.info().minor_units().unwrap_or(0) as u32;
+    let style = money_style(currency);
+    let (grouping, decimal) = style.as_ref().map_or((',', '.'), |s| (s.grouping, s.decimal));
+
+    let amount = money.amount();
+    // Also works for i64::MIN.
+    let abs = (amount as u64).wrapping_neg().min(amount as u64);
+    let scale = 10u64.pow(minor_units);
+    let mut formatted = String::new();
+    if amount < 0 {
+        formatted.push('-');
+    }
+    match style {
+        Some(ref style) => formatted.push_str(style.symbol),
+        None => {
+            formatted.push_str(currency.info().code());
+            formatted.push(' ');
+        }
+    }
+    formatted.push_str(&group_digits(&(abs / scale).to_string(), grouping));
+    if minor_units > 0 {
+        formatted.push(decimal);
+        formatted.push_str(&format!(
+            "{:0width$}",
+            abs % scale,
+            width = minor_units as usize
+        ));
+    }
+    formatted
+}
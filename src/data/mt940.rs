@@ -0,0 +1,189 @@
+use std::fmt::{self, Write};
+
+use chrono::NaiveDate;
+use penny::Currency;
+
+use write::WriteError;
+
+use super::{Account, AccountStatus, DetailCode, File, FundsType, Group, TransactionDetail};
+
+// SWIFT transaction type identification codes, written after the "N" in :61: lines.
+fn transaction_type(code: DetailCode) -> &'static str {
+    match u16::from(code) {
+        // Lockbox
+        115...118 | 415 => "LBX",
+        // Returned items
+        255 | 257 | 266 | 268 | 555 | 557 | 566 | 568 => "RTI",
+        // Checks
+        175 | 176 | 221 | 222 | 395 | 472 | 474 | 475 | 522 => "CHK",
+        // Cash letters
+        187 | 189 | 379...388 | 487 | 489 | 679...688 => "CLR",
+        // Direct debits
+        451 | 455 | 462 => "DDT",
+        // ACH, EDI, money and book transfers
+        121...123 | 142...169 | 191...208 | 216 | 421...423 | 445...469 | 491...507 | 516 => "TRF",
+        // Zero balance accounts
+        274...278 | 574...578 => "CMZ",
+        // Sweeps
+        346 | 347 | 656 => "CMS",
+        227 | 527 => "STO",
+        214 | 514 => "FEX",
+        // Letters of credit
+        212 | 213 | 512 | 513 => "DCR",
+        // Collections
+        218 | 237 | 362 | 363 | 518 | 533 | 543 | 662 | 663 | 696 => "COL",
+        238 => "DIV",
+        242 | 354 | 359 | 654 | 659 => "INT",
+        // Securities
+        232...236 | 240...249 | 531...549 => "SEC",
+        224 | 524 => "COM",
+        // Fees and charges
+        398 | 564 | 567 | 661 | 698 => "CHG",
+        // Loans
+        171 | 364 | 481 | 664 | 700...799 => "LDP",
+        _ => "MSC",
+    }
+}
+
+fn date<W: Write>(w: &mut W, date: NaiveDate) -> fmt::Result {
+    write!(w, "{}", date.format("%y%m%d"))
+}
+
+// Without the sign, and always with a decimal comma, even when there are no minor units.
+fn amount<W: Write>(w: &mut W, amount: i64, currency: Currency) -> fmt::Result {
+    let minor_units = u32::from(currency.info().minor_units().unwrap_or(0));
+    // Also works for i64::MIN.
+    let abs = (amount as u64).wrapping_neg().min(amount as u64);
+    let scale = 10u64.pow(minor_units);
+    write!(w, "{},", abs / scale)?;
+    if minor_units > 0 {
+        write!(w, "{:0width$}", abs % scale, width = minor_units as usize)?;
+    }
+    Ok(())
+}
+
+fn mark(amount: i64) -> &'static str {
+    if amount < 0 { "D" } else { "C" }
+}
+
+fn balance<W: Write>(
+    w: &mut W,
+    tag: &str,
+    balance: i64,
+    as_of: NaiveDate,
+    currency: Currency,
+) -> fmt::Result {
+    write!(w, ":{}:{}", tag, mark(balance))?;
+    date(w, as_of)?;
+    write!(w, "{}", currency)?;
+    amount(w, balance, currency)?;
+    w.write_char('\n')
+}
+
+// References are at most 16 characters.
+fn reference(reference: &str) -> &str {
+    match reference.char_indices().nth(16) {
+        Some((i, _)) => &reference[..i],
+        None => reference,
+    }
+}
+
+fn transaction<W: Write>(
+    w: &mut W,
+    detail: &TransactionDetail,
+    signed: i64,
+    as_of: NaiveDate,
+    currency: Currency,
+) -> fmt::Result {
+    let value_date = match detail.funds {
+        Some(FundsType::ValueDated(ref value_dated)) => value_dated.clone().date(),
+        _ => as_of,
+    };
+    w.write_str(":61:")?;
+    date(w, value_date)?;
+    w.write_str(mark(signed))?;
+    amount(w, signed, currency)?;
+    write!(w, "N{}", transaction_type(detail.code))?;
+    match detail.customer_ref_num {
+        Some(ref num) => w.write_str(reference(&num.0))?,
+        None => w.write_str("NONREF")?,
+    }
+    if let Some(ref num) = detail.bank_ref_num {
+        write!(w, "//{}", reference(&num.0))?;
+    }
+    w.write_char('\n')?;
+
+    // Information to account owner is up to 6 lines of 65 characters.
+    if let Some(text) = detail.text_joined(" ") {
+        let chars = text.trim().chars().collect::<Vec<_>>();
+        for (i, line) in chars.chunks(65).take(6).enumerate() {
+            w.write_str(if i == 0 { ":86:" } else { "" })?;
+            w.write_str(&line.iter().collect::<String>())?;
+            w.write_char('\n')?;
+        }
+    }
+    Ok(())
+}
+
+fn statement<W: Write>(
+    w: &mut W,
+    file: &File,
+    group: &Group,
+    account: &Account,
+    seq: usize,
+) -> Result<(), WriteError> {
+    let currency = account.effective_currency(group);
+    let as_of = group.as_of_date();
+    // Loan and non-monetary details have no direction, so they can't be written as :61: lines.
+    let signed = account
+        .transaction_details
+        .iter()
+        .filter_map(|td| td.signed_amount().map(|a| (td, a)))
+        .collect::<Vec<_>>();
+    let net = signed
+        .iter()
+        .fold(Some(0i64), |net, &(_, a)| net.and_then(|net| net.checked_add(a)))
+        .ok_or(WriteError::BalanceOverflow)?;
+    // MT940 requires both ledger balances, so a missing one is worked out from the other.
+    let opening = account.status_amount(AccountStatus::OpeningLedger);
+    let closing = account.status_amount(AccountStatus::ClosingLedger);
+    let opening = match (opening, closing) {
+        (Some(opening), _) => opening,
+        (None, Some(closing)) => closing.checked_sub(net).ok_or(WriteError::BalanceOverflow)?,
+        (None, None) => 0,
+    };
+    let closing = match closing {
+        Some(closing) => closing,
+        None => opening.checked_add(net).ok_or(WriteError::BalanceOverflow)?,
+    };
+
+    write!(w, ":20:{}\n", file.ident.0)?;
+    write!(w, ":25:{}\n", account.customer_account.0)?;
+    // Statement numbers are at most 5 digits.
+    write!(w, ":28C:{}/{}\n", file.ident.0 % 100_000, seq)?;
+    balance(w, "60F", opening, as_of, currency)?;
+    for &(detail, amount) in &signed {
+        transaction(w, detail, amount, as_of, currency)?;
+    }
+    balance(w, "62F", closing, as_of, currency)?;
+    if let Some(available) = account.status_amount(AccountStatus::ClosingAvail) {
+        balance(w, "64", available, as_of, currency)?;
+    }
+    w.write_str("-\n")?;
+    Ok(())
+}
+
+impl File {
+    // One MT940 statement per account, each ending with a "-" line. Balances worked out from
+    // amounts that overflow are an error.
+    pub fn to_mt940<W: Write>(&self, w: &mut W) -> Result<(), WriteError> {
+        let mut seq = 0;
+        for group in &self.groups {
+            for account in &group.accounts {
+                seq += 1;
+                statement(w, self, group, account, seq)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+// `#[serde(with = "...")]` adapters for `penny` types, so serialization doesn't depend on
+// `penny`'s own serde support. Currencies are their ISO 4217 codes, and money is
+// `{ amount, currency }`.
+
+pub mod currency {
+    use std::str::FromStr;
+
+    use penny::Currency;
+    use serde::de::{self, Deserialize, Deserializer};
+    use serde::ser::Serializer;
+
+    pub fn serialize<S: Serializer>(currency: &Currency, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(currency.info().code())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Currency, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Currency::from_str(&code)
+            .map_err(|()| de::Error::custom(format!("unknown currency code {:?}", code)))
+    }
+}
+
+pub mod option_currency {
+    use penny::Currency;
+    use serde::de::{Deserialize, Deserializer};
+    use serde::ser::Serializer;
+
+    use super::currency;
+
+    #[derive(Deserialize)]
+    struct Code(#[serde(with = "currency")] Currency);
+
+    pub fn serialize<S: Serializer>(
+        currency: &Option<Currency>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match *currency {
+            Some(ref c) => serializer.serialize_some(c.info().code()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Currency>, D::Error> {
+        Option::<Code>::deserialize(deserializer).map(|c| c.map(|Code(c)| c))
+    }
+}
+
+pub mod money {
+    use penny::{Currency, Money};
+    use serde::de::{Deserialize, Deserializer};
+    use serde::ser::{Serialize, Serializer};
+
+    use super::currency;
+
+    #[derive(Serialize, Deserialize)]
+    struct Repr {
+        amount: i64,
+        #[serde(with = "currency")]
+        currency: Currency,
+    }
+
+    pub fn serialize<S: Serializer>(money: &Money, serializer: S) -> Result<S::Ok, S::Error> {
+        Repr {
+            amount: money.amount(),
+            currency: money.currency(),
+        }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Money, D::Error> {
+        Repr::deserialize(deserializer).map(|m| Money::new(m.amount, m.currency))
+    }
+}
@@ -0,0 +1,238 @@
+use std::fmt::{self, Write};
+
+use super::{Account, AccountInfo, File, Group, TransactionDetail};
+
+// A configurable take on the `Display` tree, for tools printing files to a terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyPrinter {
+    // Spaces per nesting level.
+    pub indent: usize,
+    // Print missing optional fields as "none" instead of leaving them out.
+    pub show_empty: bool,
+    // Transaction text longer than this many characters is cut off with "...".
+    pub max_text_len: Option<usize>,
+    // Highlight headers and amounts with ANSI escapes. Ignored without the `color` feature.
+    pub color: bool,
+}
+
+impl Default for PrettyPrinter {
+    fn default() -> Self {
+        PrettyPrinter {
+            indent: 4,
+            show_empty: false,
+            max_text_len: None,
+            color: false,
+        }
+    }
+}
+
+const BOLD: &str = "1";
+const GREEN: &str = "32";
+const RED: &str = "31";
+
+struct Printer<'a, W: Write + 'a> {
+    options: &'a PrettyPrinter,
+    w: &'a mut W,
+    depth: usize,
+}
+
+impl<'a, W: Write + 'a> Printer<'a, W> {
+    fn pad(&mut self) -> fmt::Result {
+        for _ in 0..self.depth * self.options.indent {
+            self.w.write_char(' ')?;
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "color")]
+    fn paint<T: fmt::Display>(&mut self, style: &str, value: T) -> fmt::Result {
+        if self.options.color {
+            write!(self.w, "\x1b[{}m{}\x1b[0m", style, value)
+        } else {
+            write!(self.w, "{}", value)
+        }
+    }
+
+    #[cfg(not(feature = "color"))]
+    fn paint<T: fmt::Display>(&mut self, _style: &str, value: T) -> fmt::Result {
+        write!(self.w, "{}", value)
+    }
+
+    fn amount(&mut self, amount: i64) -> fmt::Result {
+        self.paint(if amount < 0 { RED } else { GREEN }, amount)
+    }
+
+    fn field<T: fmt::Display>(&mut self, name: &str, value: Option<T>) -> fmt::Result {
+        match value {
+            Some(value) => {
+                self.pad()?;
+                write!(self.w, "{}: {},\n", name, value)
+            }
+            None if self.options.show_empty => {
+                self.pad()?;
+                write!(self.w, "{}: none,\n", name)
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn open(&mut self) -> fmt::Result {
+        self.depth += 1;
+        self.w.write_str(" {\n")
+    }
+
+    fn close(&mut self) -> fmt::Result {
+        self.depth -= 1;
+        self.pad()?;
+        if self.depth == 0 {
+            self.w.write_str("}\n")
+        } else {
+            self.w.write_str("},\n")
+        }
+    }
+
+    fn list(&mut self, name: &str) -> fmt::Result {
+        self.pad()?;
+        self.depth += 1;
+        write!(self.w, "{}: [\n", name)
+    }
+
+    fn close_list(&mut self) -> fmt::Result {
+        self.depth -= 1;
+        self.pad()?;
+        self.w.write_str("],\n")
+    }
+
+    fn file(&mut self, file: &File) -> fmt::Result {
+        self.pad()?;
+        self.paint(BOLD, format_args!("File: {} to {}", file.sender, file.receiver))?;
+        write!(self.w, " at {} ({})", file.creation, file.ident)?;
+        self.open()?;
+        for group in &file.groups {
+            self.group(group)?;
+        }
+        self.close()
+    }
+
+    fn group(&mut self, group: &Group) -> fmt::Result {
+        self.pad()?;
+        self.paint(BOLD, format_args!("Group {}", group.status))?;
+        match group.originator {
+            Some(ref originator) => write!(self.w, ": {}", originator)?,
+            None => write!(self.w, ": Unknown originator")?,
+        }
+        if let Some(ref ultimate_receiver) = group.ultimate_receiver {
+            write!(self.w, " to {}", ultimate_receiver)?;
+        }
+        write!(self.w, " at {}", group.as_of)?;
+        if let Some(ref as_of_date_mod) = group.as_of_date_mod {
+            write!(self.w, " ({})", as_of_date_mod)?;
+        }
+        write!(self.w, " in {}", group.currency_def())?;
+        self.open()?;
+        for account in &group.accounts {
+            self.account(account)?;
+        }
+        self.close()
+    }
+
+    fn account(&mut self, account: &Account) -> fmt::Result {
+        self.pad()?;
+        self.paint(BOLD, format_args!("Account {}", account.customer_account))?;
+        if let Some(currency) = account.currency {
+            write!(self.w, " ({})", currency)?;
+        }
+        self.open()?;
+        self.list("Infos")?;
+        for info in &account.infos {
+            self.info(info)?;
+        }
+        self.close_list()?;
+        self.list("Transaction Details")?;
+        for detail in &account.transaction_details {
+            self.transaction(detail)?;
+        }
+        self.close_list()?;
+        self.close()
+    }
+
+    fn info(&mut self, info: &AccountInfo) -> fmt::Result {
+        self.pad()?;
+        match *info {
+            AccountInfo::Status { code, amount, .. } => {
+                write!(self.w, "{}", code)?;
+                if let Some(amount) = amount {
+                    self.w.write_str(": ")?;
+                    self.amount(amount)?;
+                }
+                self.w.write_str(",\n")
+            }
+            AccountInfo::Summary {
+                code,
+                amount,
+                item_count,
+                ref funds,
+            } => {
+                write!(self.w, "{}", code)?;
+                if let Some(amount) = amount {
+                    write!(self.w, ": ")?;
+                    self.paint(GREEN, amount)?;
+                }
+                self.open()?;
+                self.field("Item count", item_count)?;
+                self.field("Funds", funds.as_ref())?;
+                self.close()
+            }
+        }
+    }
+
+    fn transaction(&mut self, detail: &TransactionDetail) -> fmt::Result {
+        self.pad()?;
+        write!(self.w, "Transaction: {}", detail.code)?;
+        if let Some(amount) = detail.amount {
+            self.w.write_str(": ")?;
+            self.amount(amount)?;
+        }
+        self.open()?;
+        self.field("Funds", detail.funds.as_ref())?;
+        self.field("Bank", detail.bank_ref_num.as_ref())?;
+        self.field("Customer", detail.customer_ref_num.as_ref())?;
+        if !detail.extensions.is_empty() {
+            self.field("Extensions", Some(format!("{:?}", detail.extensions)))?;
+        }
+        let text = detail.text_joined(" ").map(|text| self.options.truncate(text));
+        self.field("Text", text.as_ref().map(|text| format!("{:?}", text)))?;
+        self.close()
+    }
+}
+
+impl PrettyPrinter {
+    pub fn new() -> Self {
+        PrettyPrinter::default()
+    }
+
+    fn truncate(&self, text: String) -> String {
+        match self.max_text_len {
+            Some(max) if text.chars().count() > max => {
+                let mut cut: String = text.chars().take(max).collect();
+                cut.push_str("...");
+                cut
+            }
+            _ => text,
+        }
+    }
+
+    pub fn print<W: Write>(&self, file: &File, w: &mut W) -> fmt::Result {
+        Printer {
+            options: self,
+            w: w,
+            depth: 0,
+        }.file(file)
+    }
+
+    pub fn render(&self, file: &File) -> String {
+        let mut s = String::new();
+        self.print(file, &mut s).expect("writing to a String can't fail");
+        s
+    }
+}
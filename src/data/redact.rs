@@ -0,0 +1,78 @@
+use std::fmt;
+
+use super::{Account, AccountNumber, File, FileVisitorMut, TransactionDetail, Tree};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RedactOptions {
+    // Masks all but the last 4 characters.
+    pub account_numbers: bool,
+    pub text: bool,
+    pub reference_nums: bool,
+}
+
+impl Default for RedactOptions {
+    fn default() -> Self {
+        RedactOptions {
+            account_numbers: true,
+            text: true,
+            reference_nums: true,
+        }
+    }
+}
+
+impl AccountNumber {
+    pub fn masked(&self) -> AccountNumber {
+        let len = self.0.chars().count();
+        AccountNumber(
+            self.0
+                .chars()
+                .enumerate()
+                .map(|(i, c)| if i + 4 < len { '*' } else { c })
+                .collect(),
+        )
+    }
+}
+
+impl FileVisitorMut for RedactOptions {
+    fn visit_account(&mut self, account: &mut Account) {
+        if self.account_numbers {
+            account.customer_account = account.customer_account.masked();
+        }
+    }
+
+    fn visit_transaction(&mut self, transaction: &mut TransactionDetail) {
+        // The raw record has the text and reference numbers too.
+        if self.text || self.reference_nums {
+            transaction.raw_record = None;
+        }
+        if self.text {
+            transaction.text = None;
+        }
+        if self.reference_nums {
+            transaction.bank_ref_num = None;
+            transaction.customer_ref_num = None;
+        }
+    }
+}
+
+impl File {
+    pub fn redact(&self, mut opts: RedactOptions) -> File {
+        let mut file = self.clone();
+        file.visit_mut(&mut opts);
+        file
+    }
+}
+
+// Prints the `Display` tree of `file.redact(RedactOptions::default())` without copying the file,
+// for logging.
+#[derive(Debug, Clone, Copy)]
+pub struct Redacted<'a>(pub &'a File);
+
+impl<'a> fmt::Display for Redacted<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Tree {
+            node: self.0,
+            redacted: true,
+        }.fmt(f)
+    }
+}
@@ -0,0 +1,187 @@
+use std::error::Error;
+use std::fmt;
+
+use super::{Account, AccountInfo, File, FileProcessError, FileVisitorMut, FundsType, Group};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum RescaleError {
+    // Summary amounts can't be negative.
+    NegativeFactor(i64),
+    Overflow,
+}
+
+impl fmt::Display for RescaleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RescaleError::NegativeFactor(factor) => {
+                write!(f, "Can't rescale amounts by negative factor {}", factor)
+            }
+            RescaleError::Overflow => write!(f, "Rescaled amount overflows"),
+        }
+    }
+}
+
+impl Error for RescaleError {}
+
+// Scales amounts by `factor`, noting any that overflow rather than stopping at them.
+struct Rescale {
+    factor: i64,
+    overflowed: bool,
+}
+
+impl Rescale {
+    fn new(factor: i64) -> Rescale {
+        Rescale {
+            factor,
+            overflowed: false,
+        }
+    }
+
+    fn signed(&mut self, amount: Option<&mut i64>) {
+        if let Some(amount) = amount {
+            match amount.checked_mul(self.factor) {
+                Some(scaled) => *amount = scaled,
+                None => self.overflowed = true,
+            }
+        }
+    }
+
+    fn unsigned(&mut self, amount: Option<&mut u64>) {
+        if let Some(amount) = amount {
+            match amount.checked_mul(self.factor as u64) {
+                Some(scaled) => *amount = scaled,
+                None => self.overflowed = true,
+            }
+        }
+    }
+
+    fn funds(&mut self, funds: &mut Option<FundsType>) {
+        match *funds {
+            Some(FundsType::DistributedAvailS {
+                ref mut immediate,
+                ref mut one_day,
+                ref mut more_than_one_day,
+            }) => {
+                self.signed(immediate.as_mut());
+                self.signed(one_day.as_mut());
+                self.signed(more_than_one_day.as_mut());
+            }
+            Some(FundsType::DistributedAvailD(ref mut dists)) => for dist in dists {
+                self.signed(Some(&mut dist.amount));
+            },
+            _ => {}
+        }
+    }
+
+    // The account's own amounts, its transaction details, and its declared control total.
+    fn account(&mut self, account: &mut Account) {
+        for info in &mut account.infos {
+            match *info {
+                AccountInfo::Status {
+                    ref mut amount,
+                    ref mut funds,
+                    ..
+                } => {
+                    self.signed(amount.as_mut());
+                    self.funds(funds);
+                }
+                AccountInfo::Summary {
+                    ref mut amount,
+                    ref mut funds,
+                    ..
+                } => {
+                    self.unsigned(amount.as_mut());
+                    self.funds(funds);
+                }
+            }
+        }
+        for detail in &mut account.transaction_details {
+            self.signed(detail.amount.as_mut());
+            self.funds(&mut detail.funds);
+        }
+        self.signed(account.declared_control_total.as_mut());
+    }
+}
+
+impl FileVisitorMut for Rescale {
+    fn visit_file(&mut self, file: &mut File) {
+        self.signed(file.declared_control_total.as_mut());
+    }
+
+    fn visit_group(&mut self, group: &mut Group) {
+        self.signed(group.declared_control_total.as_mut());
+    }
+
+    fn visit_account(&mut self, account: &mut Account) {
+        self.account(account);
+    }
+}
+
+// The factor all of `factors` share, if there are any and they agree.
+fn common_factor<I: IntoIterator<Item = Option<i64>>>(factors: I) -> Option<i64> {
+    let mut factors = factors.into_iter();
+    let first = factors.next()??;
+    if factors.all(|factor| factor == Some(first)) {
+        Some(first)
+    } else {
+        None
+    }
+}
+
+impl File {
+    // Multiplies every amount, including funds availability and the declared trailer totals, by
+    // `factor`. This can't be undone where amounts no longer fit after dividing back, such as
+    // with a factor of 0.
+    // On error the file is left unchanged.
+    pub fn rescale_amounts(&mut self, factor: i64) -> Result<(), RescaleError> {
+        if factor < 0 {
+            return Err(RescaleError::NegativeFactor(factor));
+        }
+        let mut rescale = Rescale::new(factor);
+        let mut file = self.clone();
+        file.visit_mut(&mut rescale);
+        if rescale.overflowed {
+            return Err(RescaleError::Overflow);
+        }
+        *self = file;
+        Ok(())
+    }
+
+    // Scales each account by the minor units of its currency. Control totals are checked before
+    // scaling, in the units the file was sent in. Declared group and file control totals are
+    // scaled where every account under them is scaled alike, and left as read where they
+    // aren't, since no one factor fits their mix of currencies.
+    pub(super) fn major_to_minor_units(&mut self) -> Result<(), FileProcessError<'static>> {
+        let mut overflowed = false;
+        let mut group_factors = Vec::with_capacity(self.groups.len());
+        for group in &mut self.groups {
+            let group_cur = group.currency_def();
+            let mut account_factors = Vec::with_capacity(group.accounts.len());
+            for account in &mut group.accounts {
+                let minor_units = account.currency_def(group_cur).info().minor_units().unwrap_or(0);
+                let mut rescale = Rescale::new(10i64.pow(u32::from(minor_units)));
+                rescale.account(account);
+                overflowed |= rescale.overflowed;
+                account_factors.push(Some(rescale.factor));
+            }
+            let group_factor = common_factor(account_factors);
+            if let Some(factor) = group_factor {
+                let mut rescale = Rescale::new(factor);
+                rescale.signed(group.declared_control_total.as_mut());
+                overflowed |= rescale.overflowed;
+            }
+            group_factors.push(group_factor);
+        }
+        if let Some(factor) = common_factor(group_factors) {
+            let mut rescale = Rescale::new(factor);
+            rescale.signed(self.declared_control_total.as_mut());
+            overflowed |= rescale.overflowed;
+        }
+        if overflowed {
+            Err(FileProcessError::AmountOverflow)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::NaiveDate;
+use penny::Currency;
+
+use super::{Account, AccountStatus, FundsType, Group};
+
+// An account's statement laid out for rendering, whatever it's rendered to. Amounts are in minor
+// units of `currency`, with credits positive and debits negative.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct StatementView {
+    pub as_of: NaiveDate,
+    #[cfg_attr(feature = "serde-serialize", serde(with = "::data::penny_serde::currency"))]
+    pub currency: Currency,
+    pub opening_balance: i64,
+    pub lines: Vec<StatementLine>,
+    // The opening balance with every line added.
+    pub closing_balance: i64,
+    // The closing ledger the file reports, to reconcile against.
+    pub reported_closing: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct StatementLine {
+    // The value date for value-dated funds, and otherwise the group's as-of date.
+    pub date: NaiveDate,
+    pub code: u16,
+    // The transaction's text, or the type code's description when it has none.
+    pub description: String,
+    pub amount: i64,
+    // After this line.
+    pub balance: i64,
+}
+
+impl StatementView {
+    pub fn reconciles(&self) -> bool {
+        self.reported_closing.map_or(true, |closing| closing == self.closing_balance)
+    }
+}
+
+impl<S> Account<S> {
+    // Loan and non-monetary details have no direction, so they're left out. Lines are in date
+    // order, and otherwise in the order the file gave them. Without an opening ledger, the
+    // opening balance is worked out from the closing ledger, as for MT940.
+    pub fn statement_view(&self, group: &Group<S>, cur: Currency) -> StatementView {
+        let as_of = group.as_of_date();
+        let mut signed = self.transaction_details
+            .iter()
+            .filter_map(|td| td.signed_amount().map(|a| (td, a)))
+            .map(|(td, amount)| {
+                let date = match td.funds {
+                    Some(FundsType::ValueDated(ref value_dated)) => value_dated.clone().date(),
+                    _ => as_of,
+                };
+                (date, td, amount)
+            })
+            .collect::<Vec<_>>();
+        signed.sort_by_key(|&(date, _, _)| date);
+
+        let net = signed.iter().fold(0i64, |net, &(_, _, a)| net.saturating_add(a));
+        let reported_closing = self.status_amount(AccountStatus::ClosingLedger);
+        let opening_balance = self.status_amount(AccountStatus::OpeningLedger)
+            .or_else(|| reported_closing.map(|c| c.saturating_sub(net)))
+            .unwrap_or(0);
+
+        let mut balance = opening_balance;
+        let lines = signed
+            .into_iter()
+            .map(|(date, td, amount)| {
+                balance = balance.saturating_add(amount);
+                let description = match td.text_trimmed() {
+                    Some(ref text) if !text.is_empty() => text.join(" "),
+                    _ => td.code.description().to_string(),
+                };
+                StatementLine {
+                    date,
+                    code: td.code.into(),
+                    description,
+                    amount,
+                    balance,
+                }
+            })
+            .collect();
+        StatementView {
+            as_of,
+            currency: cur,
+            opening_balance,
+            lines,
+            closing_balance: balance,
+            reported_closing,
+        }
+    }
+}
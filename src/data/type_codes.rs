@@ -3,7 +3,7 @@
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub enum TypeCode {
     Status(StatusCode),
@@ -31,8 +31,18 @@ impl From<TypeCode> for u16 {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+// Codes in the spec's uniform ranges, 000-899, that aren't on its list of uniform type codes,
+// which the enums below follow. The spec only accepts listed codes, and says codes get deleted
+// from the list as it's revised, so these can only be read with a `CodeTable` override.
+// Customized codes, 900-999, are for banks to define and aren't deprecated.
+pub fn is_deprecated_code(code: u16) -> bool {
+    code < 900 && StatusCode::try_from(code).is_err() && SummaryCode::try_from(code).is_err() &&
+        DetailCode::try_from(code).is_err()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum StatusCode {
     // 001-099, 900-919
     Account(AccountStatus),
@@ -58,6 +68,14 @@ impl From<StatusCode> for u16 {
         }
     }
 }
+impl StatusCode {
+    pub fn description(&self) -> &'static str {
+        match *self {
+            StatusCode::Account(c) => c.description(),
+            StatusCode::Loan(c) => c.description(),
+        }
+    }
+}
 impl fmt::Display for StatusCode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Status(")?;
@@ -68,8 +86,9 @@ impl fmt::Display for StatusCode {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum SummaryCode {
     // 100-399, 920-959
     Credit(CreditSummary),
@@ -99,6 +118,47 @@ impl From<SummaryCode> for u16 {
         }
     }
 }
+impl SummaryCode {
+    pub fn description(&self) -> &'static str {
+        match *self {
+            SummaryCode::Credit(c) => c.description(),
+            SummaryCode::Debit(c) => c.description(),
+            SummaryCode::Loan(c) => c.description(),
+        }
+    }
+
+    // Whether a detail code is counted by this summary's item count. None when the summary isn't
+    // known to correspond to a family of detail codes.
+    pub fn counts_detail(&self, code: DetailCode) -> Option<bool> {
+        use self::CreditDetail as CD;
+        use self::CreditSummary as CS;
+        use self::DebitDetail as DD;
+        use self::DebitSummary as DS;
+        match *self {
+            SummaryCode::Credit(CS::TotalCredits) => Some(code.is_credit()),
+            SummaryCode::Debit(DS::TotalDebits) => Some(code.is_debit()),
+            SummaryCode::Credit(summary) => {
+                let detail = match summary {
+                    CS::TotalLockboxDeposits => CD::LockboxDeposit,
+                    CS::TotalAchCredits => CD::AchCreditReceived,
+                    CS::TotalIncomingMoneyTransfers => CD::IncomingMoneyTransfer,
+                    _ => return None,
+                };
+                Some(code == DetailCode::Credit(detail))
+            }
+            SummaryCode::Debit(summary) => {
+                let detail = match summary {
+                    DS::TotalAchDebits => DD::AchDebitRecieved,
+                    DS::TotalCheckPaid => DD::CheckPaid,
+                    DS::TotalOutgoingMoneyTransfers => DD::OutgoingMoneyTransfer,
+                    _ => return None,
+                };
+                Some(code == DetailCode::Debit(detail))
+            }
+            SummaryCode::Loan(_) => None,
+        }
+    }
+}
 impl fmt::Display for SummaryCode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Summary(")?;
@@ -110,8 +170,9 @@ impl fmt::Display for SummaryCode {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum DetailCode {
     // 100-399, 920-959
     Credit(CreditDetail),
@@ -145,6 +206,29 @@ impl From<DetailCode> for u16 {
         }
     }
 }
+impl DetailCode {
+    pub fn description(&self) -> &'static str {
+        match *self {
+            DetailCode::Credit(c) => c.description(),
+            DetailCode::Debit(c) => c.description(),
+            DetailCode::Loan(c) => c.description(),
+            DetailCode::NonMonetary => "Non-Monetary Information",
+        }
+    }
+
+    pub fn is_credit(&self) -> bool {
+        match *self {
+            DetailCode::Credit(_) => true,
+            _ => false,
+        }
+    }
+    pub fn is_debit(&self) -> bool {
+        match *self {
+            DetailCode::Debit(_) => true,
+            _ => false,
+        }
+    }
+}
 impl fmt::Display for DetailCode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Summary(")?;
@@ -158,58 +242,61 @@ impl fmt::Display for DetailCode {
 }
 
 enum_mapping! {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     #[cfg_attr(feature="serde-serialize", derive(Serialize, Deserialize))]
     pub AccountStatus(u16) {
-        OpeningLedger(10),
-        AvgOpeningLedgerMtd(11),
-        AvgOpeningLedgerYtd(12),
-        ClosingLedger(15),
-        AvgClosingLedgerMtd(20),
-        AvgClosingLedgerPrevMonth(21),
-        AggregateBalanceAdjustments(22),
-        AvgClosingLedgerYtdPrevMonth(24),
-        AvgClosingLedgerYtd(25),
-        CurrentLedger(30),
-        AchNetPosition(37),
-        OpeningAvailPlusTotalSameDayAchDtcDeposit(39),
-        OpeningAvail(40),
-        AvgOpeningAvailMtd(41),
-        AvgOpeningAvailYtd(42),
-        AvgAvailPrevMonth(43),
-        DisbursingOpeningAvailBalance(44),
-        ClosingAvail(45),
-        AvgClosingAvailMtd(50),
-        AvgClosingAvailLastMonth(51),
-        AvgClosingAvailYtdLastMonth(54),
-        AvgClosingAvailYtd(55),
-        LoanBalance(56),
-        TotalInvestmentPosition(57),
-        CurrentAvailCrsSupressed(59),
-        CurrentAvail(60),
-        AvgCurrentAvailMtd(61),
-        AvgCurrentAvailYtd(62),
-        TotalFloat(63),
-        TargetBalance(65),
-        AdjustedBalance(66),
-        AdjustedBalanceMtd(67),
-        AdjustedBalanceYtd(68),
-        ZeroDayFloat(70),
-        OneDayFloat(72),
-        FloatAdjustment(73),
-        TwoOrMoreDaysFloat(74),
-        ThreeOrMoreDaysFloat(75),
-        AdjustmentToBalances(76),
-        AvgAdjustmentToBalancesMtd(77),
-        AvgAdjustmentToBalancesYtd(78),
-        FourDayFloat(79),
-        FiveDayFloat(80),
-        SixDayFloat(81),
-        AvgOneDayFloatMtd(82),
-        AvgOneDayFloatYtd(83),
-        AvgTwoDayFloatMtd(84),
-        AvgTwoDayFloatYtd(85),
-        TransferCalculation(86);
+        OpeningLedger(10, "Opening Ledger"),
+        AvgOpeningLedgerMtd(11, "Average Opening Ledger MTD"),
+        AvgOpeningLedgerYtd(12, "Average Opening Ledger YTD"),
+        ClosingLedger(15, "Closing Ledger"),
+        AvgClosingLedgerMtd(20, "Average Closing Ledger MTD"),
+        AvgClosingLedgerPrevMonth(21, "Average Closing Ledger Previous Month"),
+        AggregateBalanceAdjustments(22, "Aggregate Balance Adjustments"),
+        AvgClosingLedgerYtdPrevMonth(24, "Average Closing Ledger YTD Previous Month"),
+        AvgClosingLedgerYtd(25, "Average Closing Ledger YTD"),
+        CurrentLedger(30, "Current Ledger"),
+        AchNetPosition(37, "ACH Net Position"),
+        OpeningAvailPlusTotalSameDayAchDtcDeposit(
+            39,
+            "Opening Available plus Total Same Day ACH DTC Deposit"
+        ),
+        OpeningAvail(40, "Opening Available"),
+        AvgOpeningAvailMtd(41, "Average Opening Available MTD"),
+        AvgOpeningAvailYtd(42, "Average Opening Available YTD"),
+        AvgAvailPrevMonth(43, "Average Available Previous Month"),
+        DisbursingOpeningAvailBalance(44, "Disbursing Opening Available Balance"),
+        ClosingAvail(45, "Closing Available"),
+        AvgClosingAvailMtd(50, "Average Closing Available MTD"),
+        AvgClosingAvailLastMonth(51, "Average Closing Available Last Month"),
+        AvgClosingAvailYtdLastMonth(54, "Average Closing Available YTD Last Month"),
+        AvgClosingAvailYtd(55, "Average Closing Available YTD"),
+        LoanBalance(56, "Loan Balance"),
+        TotalInvestmentPosition(57, "Total Investment Position"),
+        CurrentAvailCrsSupressed(59, "Current Available CRS Suppressed"),
+        CurrentAvail(60, "Current Available"),
+        AvgCurrentAvailMtd(61, "Average Current Available MTD"),
+        AvgCurrentAvailYtd(62, "Average Current Available YTD"),
+        TotalFloat(63, "Total Float"),
+        TargetBalance(65, "Target Balance"),
+        AdjustedBalance(66, "Adjusted Balance"),
+        AdjustedBalanceMtd(67, "Adjusted Balance MTD"),
+        AdjustedBalanceYtd(68, "Adjusted Balance YTD"),
+        ZeroDayFloat(70, "Zero Day Float"),
+        OneDayFloat(72, "One Day Float"),
+        FloatAdjustment(73, "Float Adjustment"),
+        TwoOrMoreDaysFloat(74, "Two or More Days Float"),
+        ThreeOrMoreDaysFloat(75, "Three or More Days Float"),
+        AdjustmentToBalances(76, "Adjustment to Balances"),
+        AvgAdjustmentToBalancesMtd(77, "Average Adjustment to Balances MTD"),
+        AvgAdjustmentToBalancesYtd(78, "Average Adjustment to Balances YTD"),
+        FourDayFloat(79, "Four Day Float"),
+        FiveDayFloat(80, "Five Day Float"),
+        SixDayFloat(81, "Six Day Float"),
+        AvgOneDayFloatMtd(82, "Average One Day Float MTD"),
+        AvgOneDayFloatYtd(83, "Average One Day Float YTD"),
+        AvgTwoDayFloatMtd(84, "Average Two Day Float MTD"),
+        AvgTwoDayFloatYtd(85, "Average Two Day Float YTD"),
+        TransferCalculation(86, "Transfer Calculation");
 
         Custom {
             from: c @ 900...919 => Ok(AccountStatus::Custom(c));
@@ -219,111 +306,114 @@ enum_mapping! {
 }
 
 enum_mapping! {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     #[cfg_attr(feature="serde-serialize", derive(Serialize, Deserialize))]
     pub LoanStatus(u16) {
-        PrincipalLoanBalance(701),
-        AvailableCommitmentAmount(703),
-        PaymentAmountDue(705),
-        PrincipalAmountPastDue(707),
-        InterestAmountPastDue(709),
+        PrincipalLoanBalance(701, "Principal Loan Balance"),
+        AvailableCommitmentAmount(703, "Available Commitment Amount"),
+        PaymentAmountDue(705, "Payment Amount Due"),
+        PrincipalAmountPastDue(707, "Principal Amount Past Due"),
+        InterestAmountPastDue(709, "Interest Amount Past Due"),
     }
 }
 
 enum_mapping! {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     #[cfg_attr(feature="serde-serialize", derive(Serialize, Deserialize))]
     pub CreditSummary(u16) {
-        TotalCredits(100),
-        TotalCreditAmountMtd(101),
-        CreditsNotDetailed(105),
-        DespositsSubjectToFloat(106),
-        TotalAdjustmentCreditsYtd(107),
-        CurrentDayTotalLockboxDeposits(109),
+        TotalCredits(100, "Total Credits"),
+        TotalCreditAmountMtd(101, "Total Credit Amount MTD"),
+        CreditsNotDetailed(105, "Credits Not Detailed"),
+        DespositsSubjectToFloat(106, "Deposits Subject to Float"),
+        TotalAdjustmentCreditsYtd(107, "Total Adjustment Credits YTD"),
+        CurrentDayTotalLockboxDeposits(109, "Current Day Total Lockbox Deposits"),
         // Lockbox
-        TotalLockboxDeposits(110),
-        EdiTransactionCredit(120),
+        TotalLockboxDeposits(110, "Total Lockbox Deposits"),
+        EdiTransactionCredit(120, "EDI Transaction Credit"),
         // Concentration
-        TotalConcentrationCredits(130),
-        TotalDtcCredits(131),
-        TotalAchCredits(140),
-        TotalBankCardDeposits(146),
+        TotalConcentrationCredits(130, "Total Concentration Credits"),
+        TotalDtcCredits(131, "Total DTC Credits"),
+        TotalAchCredits(140, "Total ACH Credits"),
+        TotalBankCardDeposits(146, "Total Bank Card Deposits"),
         // Preauthorized and ACH
-        TotalPreauthorizedPaymentCredits(150),
-        TotalAchDisbursingFundingCredits(160),
-        CorporateTradePaymentSettlement(162),
-        CorporateTradePaymentCredits(163),
-        AchSettlementCredits(167),
+        TotalPreauthorizedPaymentCredits(150, "Total Preauthorized Payment Credits"),
+        TotalAchDisbursingFundingCredits(160, "Total ACH Disbursing Funding Credits"),
+        CorporateTradePaymentSettlement(162, "Corporate Trade Payment Settlement"),
+        CorporateTradePaymentCredits(163, "Corporate Trade Payment Credits"),
+        AchSettlementCredits(167, "ACH Settlement Credits"),
         // Other Deposits
-        TotalOtherCheckDeposits(170),
-        ListPostCredits(178),
-        TotalLoanProceeds(180),
-        TotalBankPreparedDeposits(182),
-        TotalMiscDeposits(185),
-        TotalCashLetterCredits(186),
-        TotalCashLetterAdjustments(188),
+        TotalOtherCheckDeposits(170, "Total Other Check Deposits"),
+        ListPostCredits(178, "List Post Credits"),
+        TotalLoanProceeds(180, "Total Loan Proceeds"),
+        TotalBankPreparedDeposits(182, "Total Bank Prepared Deposits"),
+        TotalMiscDeposits(185, "Total Miscellaneous Deposits"),
+        TotalCashLetterCredits(186, "Total Cash Letter Credits"),
+        TotalCashLetterAdjustments(188, "Total Cash Letter Adjustments"),
         // Money Transfer
-        TotalIncomingMoneyTransfers(190),
-        TotalAutomaticTransferCredits(200),
-        TotalBookTransferCredits(205),
-        TotalInternationalMoneyTransferCredits(207),
-        TotalInternationalCredits(210),
-        TotalLettersOfCredit(215),
+        TotalIncomingMoneyTransfers(190, "Total Incoming Money Transfers"),
+        TotalAutomaticTransferCredits(200, "Total Automatic Transfer Credits"),
+        TotalBookTransferCredits(205, "Total Book Transfer Credits"),
+        TotalInternationalMoneyTransferCredits(207, "Total International Money Transfer Credits"),
+        TotalInternationalCredits(210, "Total International Credits"),
+        TotalLettersOfCredit(215, "Total Letters of Credit"),
         // Security
-        TotalSecurityCredits(230),
-        TotalCollectionCredits(231),
-        TotalBankersAcceptanceCredits(239),
-        MonthlyDividends(245),
-        TotalChecksPostedAndReturned(250),
-        TotalDebitReversals(251),
-        TotalAchReturnItems(256),
-        TotalRejectedCredits(260),
+        TotalSecurityCredits(230, "Total Security Credits"),
+        TotalCollectionCredits(231, "Total Collection Credits"),
+        TotalBankersAcceptanceCredits(239, "Total Bankers' Acceptance Credits"),
+        MonthlyDividends(245, "Monthly Dividends"),
+        TotalChecksPostedAndReturned(250, "Total Checks Posted and Returned"),
+        TotalDebitReversals(251, "Total Debit Reversals"),
+        TotalAchReturnItems(256, "Total ACH Return Items"),
+        TotalRejectedCredits(260, "Total Rejected Credits"),
         // ZBA and Disbursing
-        TotalZbaCredits(270),
-        NetZeroBalanceAmount(271),
-        TotalControlledDisbursingCredits(280),
-        TotalDtcDisbursingCredits(285),
+        TotalZbaCredits(270, "Total ZBA Credits"),
+        NetZeroBalanceAmount(271, "Net Zero Balance Amount"),
+        TotalControlledDisbursingCredits(280, "Total Controlled Disbursing Credits"),
+        TotalDtcDisbursingCredits(285, "Total DTC Disbursing Credits"),
         // Other (Expansion)
-        TotalAtmCredits(294),
-        CorrespondentBankDeposit(302),
-        TotalWireTransfersInFf(303),
-        TotalWireTransfersInChf(304),
-        TotalFedFundsSold(305),
-        TotalTrustCredits(307),
-        TotalValueDatedFunds(309),
-        TotalCommercialDeposits(310),
-        TotalInternationalCreditsFf(315),
-        TotalInternationalCreditsChf(316),
-        TotalForeignCheckPurchased(318),
-        LateDeposit(319),
-        TotalSecuritiesSoldFf(320),
-        TotalSecuritiesSoldChf(321),
-        TotalSecuritiesMaturedFf(324),
-        TotalSecuritiesMaturedChf(325),
-        TotalSecuritiesInterest(326),
-        TotalSecuritiesMatured(327),
-        TotalSecuritiesInterestFf(328),
-        TotalSecuritiesInterestChf(329),
-        TotalEscrowCredits(330),
-        TotalMiscSecuritiesCreditsFf(332),
-        TotalMiscSecuritiesCreditsChf(336),
-        TotalSecuritiesSold(338),
-        TotalBrokerDeposits(340),
-        TotalBrokerDepositsFf(341),
-        TotalBrokerDepositsChf(343),
-        InvestmentSold(350),
-        TotalCashCenterCredits(352),
-        InvestmentInterest(355),
-        TotalCreditAdjustment(356),
-        TotalCreditsLessWireTransferAndReturnedChecks(360),
-        GrandTotalCreditsLessGrandTotalDebits(361),
+        TotalAtmCredits(294, "Total ATM Credits"),
+        CorrespondentBankDeposit(302, "Correspondent Bank Deposit"),
+        TotalWireTransfersInFf(303, "Total Wire Transfers in FF"),
+        TotalWireTransfersInChf(304, "Total Wire Transfers in CHF"),
+        TotalFedFundsSold(305, "Total Fed Funds Sold"),
+        TotalTrustCredits(307, "Total Trust Credits"),
+        TotalValueDatedFunds(309, "Total Value Dated Funds"),
+        TotalCommercialDeposits(310, "Total Commercial Deposits"),
+        TotalInternationalCreditsFf(315, "Total International Credits FF"),
+        TotalInternationalCreditsChf(316, "Total International Credits CHF"),
+        TotalForeignCheckPurchased(318, "Total Foreign Check Purchased"),
+        LateDeposit(319, "Late Deposit"),
+        TotalSecuritiesSoldFf(320, "Total Securities Sold FF"),
+        TotalSecuritiesSoldChf(321, "Total Securities Sold CHF"),
+        TotalSecuritiesMaturedFf(324, "Total Securities Matured FF"),
+        TotalSecuritiesMaturedChf(325, "Total Securities Matured CHF"),
+        TotalSecuritiesInterest(326, "Total Securities Interest"),
+        TotalSecuritiesMatured(327, "Total Securities Matured"),
+        TotalSecuritiesInterestFf(328, "Total Securities Interest FF"),
+        TotalSecuritiesInterestChf(329, "Total Securities Interest CHF"),
+        TotalEscrowCredits(330, "Total Escrow Credits"),
+        TotalMiscSecuritiesCreditsFf(332, "Total Miscellaneous Securities Credits FF"),
+        TotalMiscSecuritiesCreditsChf(336, "Total Miscellaneous Securities Credits CHF"),
+        TotalSecuritiesSold(338, "Total Securities Sold"),
+        TotalBrokerDeposits(340, "Total Broker Deposits"),
+        TotalBrokerDepositsFf(341, "Total Broker Deposits FF"),
+        TotalBrokerDepositsChf(343, "Total Broker Deposits CHF"),
+        InvestmentSold(350, "Investment Sold"),
+        TotalCashCenterCredits(352, "Total Cash Center Credits"),
+        InvestmentInterest(355, "Investment Interest"),
+        TotalCreditAdjustment(356, "Total Credit Adjustment"),
+        TotalCreditsLessWireTransferAndReturnedChecks(
+            360,
+            "Total Credits less Wire Transfer and Returned Checks"
+        ),
+        GrandTotalCreditsLessGrandTotalDebits(361, "Grand Total Credits less Grand Total Debits"),
         // Correspondent Bank and Federal Reserve
-        TotalBackValueCredits(370),
-        TotalUniversalCredits(385),
-        TotalFreightPaymentCredits(389),
+        TotalBackValueCredits(370, "Total Back Value Credits"),
+        TotalUniversalCredits(385, "Total Universal Credits"),
+        TotalFreightPaymentCredits(389, "Total Freight Payment Credits"),
         // Miscellaneous
-        TotalMiscCredits(390),
-        TotalLoanPayment(720);
+        TotalMiscCredits(390, "Total Miscellaneous Credits"),
+        TotalLoanPayment(720, "Total Loan Payment");
 
         Custom {
             from: c @ 920...959 => Ok(CreditSummary::Custom(c));
@@ -333,93 +423,99 @@ enum_mapping! {
 }
 
 enum_mapping! {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     #[cfg_attr(feature="serde-serialize", derive(Serialize, Deserialize))]
     pub DebitSummary(u16) {
-        TotalDebits(400),
-        TotalDebitAmountMtd(401),
-        TodaysTotalDebits(403),
-        TotalDebitLessWireTransfersAndChargeBacks(405),
-        DebitsNotDetailed(406),
-        TotalYtdAdjustment(410),
-        TotalDebitsExcludingReturnedItems(412),
+        TotalDebits(400, "Total Debits"),
+        TotalDebitAmountMtd(401, "Total Debit Amount MTD"),
+        TodaysTotalDebits(403, "Today's Total Debits"),
+        TotalDebitLessWireTransfersAndChargeBacks(
+            405,
+            "Total Debit less Wire Transfers and Charge Backs"
+        ),
+        DebitsNotDetailed(406, "Debits Not Detailed"),
+        TotalYtdAdjustment(410, "Total YTD Adjustment"),
+        TotalDebitsExcludingReturnedItems(412, "Total Debits Excluding Returned Items"),
         // Lockbox
-        TotalLockboxDebits(416),
-        EdiTransactionDebits(420),
+        TotalLockboxDebits(416, "Total Lockbox Debits"),
+        EdiTransactionDebits(420, "EDI Transaction Debits"),
         // Payable-Through Draft
-        TotalPayableThroughDrafts(430),
+        TotalPayableThroughDrafts(430, "Total Payable through Drafts"),
         // ACH
-        TotalAchDisbursementFundingDebits(446),
-        TotalAchDebits(450),
-        CorporateTradePaymentDebits(463),
-        CorporateTradePaymentSettlement(465),
-        AchSettlementDebits(467),
+        TotalAchDisbursementFundingDebits(446, "Total ACH Disbursement Funding Debits"),
+        TotalAchDebits(450, "Total ACH Debits"),
+        CorporateTradePaymentDebits(463, "Corporate Trade Payment Debits"),
+        CorporateTradePaymentSettlement(465, "Corporate Trade Payment Settlement"),
+        AchSettlementDebits(467, "ACH Settlement Debits"),
         // Checks Paid
-        TotalCheckPaid(470),
-        TotalCheckPaidCumulativeMtd(471),
-        ListPostDebits(478),
-        TotalLoanPayments(480),
-        TotalBankOriginatedDebits(482),
-        TotalCashLetterDebits(486),
+        TotalCheckPaid(470, "Total Check Paid"),
+        TotalCheckPaidCumulativeMtd(471, "Total Check Paid Cumulative MTD"),
+        ListPostDebits(478, "List Post Debits"),
+        TotalLoanPayments(480, "Total Loan Payments"),
+        TotalBankOriginatedDebits(482, "Total Bank Originated Debits"),
+        TotalCashLetterDebits(486, "Total Cash Letter Debits"),
         // Money Transfer
-        TotalOutgoingMoneyTransfers(490),
-        TotalAutomaticTransferDebits(500),
-        TotalBookTransferDebits(505),
-        TotalInternationalMoneyTransferDebits(507),
-        TotalInternationalDebits(510),
-        TotalLettersOfCredit(515),
+        TotalOutgoingMoneyTransfers(490, "Total Outgoing Money Transfers"),
+        TotalAutomaticTransferDebits(500, "Total Automatic Transfer Debits"),
+        TotalBookTransferDebits(505, "Total Book Transfer Debits"),
+        TotalInternationalMoneyTransferDebits(507, "Total International Money Transfer Debits"),
+        TotalInternationalDebits(510, "Total International Debits"),
+        TotalLettersOfCredit(515, "Total Letters of Credit"),
         // Security
-        TotalSecurityDebits(530),
-        TotalAmountOfSecuritiesPurchased(532),
-        TotalMiscSecuritiesDbFf(534),
-        TotalMiscSecuritiesDebitChf(536),
-        TotalCollectionDebit(537),
-        TotalBankersAcceptancesDebit(539),
+        TotalSecurityDebits(530, "Total Security Debits"),
+        TotalAmountOfSecuritiesPurchased(532, "Total Amount of Securities Purchased"),
+        TotalMiscSecuritiesDbFf(534, "Total Miscellaneous Securities Debit FF"),
+        TotalMiscSecuritiesDebitChf(536, "Total Miscellaneous Securities Debit CHF"),
+        TotalCollectionDebit(537, "Total Collection Debit"),
+        TotalBankersAcceptancesDebit(539, "Total Bankers' Acceptances Debit"),
         // Deposited Items Returned
-        TotalDepositedItemsReturned(550),
-        TotalCreditReversals(551),
-        TotalAchReturnItems(556),
-        TotalRejectedDebits(560),
+        TotalDepositedItemsReturned(550, "Total Deposited Items Returned"),
+        TotalCreditReversals(551, "Total Credit Reversals"),
+        TotalAchReturnItems(556, "Total ACH Return Items"),
+        TotalRejectedDebits(560, "Total Rejected Debits"),
         // ZBA and Disbursing
-        TotalZbaDebits(570),
-        TotalControlledDisbursingDebits(580),
-        TotalDisbursingChecksPaidEarlyAmount(583),
-        TotalDisbursingChecksPaidLaterAmount(584),
-        DisbursingFundingRequirement(585),
-        FrbPresentmentEstimateFedEstimate(586),
-        LateDebitsAfterNotification(587),
-        TotalDisbursingChecksPaidLastAmount(588),
+        TotalZbaDebits(570, "Total ZBA Debits"),
+        TotalControlledDisbursingDebits(580, "Total Controlled Disbursing Debits"),
+        TotalDisbursingChecksPaidEarlyAmount(583, "Total Disbursing Checks Paid Early Amount"),
+        TotalDisbursingChecksPaidLaterAmount(584, "Total Disbursing Checks Paid Later Amount"),
+        DisbursingFundingRequirement(585, "Disbursing Funding Requirement"),
+        FrbPresentmentEstimateFedEstimate(586, "FRB Presentment Estimate Fed Estimate"),
+        LateDebitsAfterNotification(587, "Late Debits after Notification"),
+        TotalDisbursingChecksPaidLastAmount(588, "Total Disbursing Checks Paid Last Amount"),
         // Other (Expansion)
-        TotalDtcDebits(590),
-        TotalAtmDebits(594),
-        TotalAprDebits(596),
-        EstimatedTotalDisbursement(601),
-        AdjustedTotalDisbursement(602),
-        TotalFundsRequired(610),
-        TotalWireTransfersOutChf(611),
-        TotalWireTransfersOutFf(612),
-        TotalInternationalDebitChf(613),
-        TotalInternationalDebitFf(614),
-        TotalFederalReserveBankCommercialBankDebit(615),
-        TotalSecuritiesPurchasedChf(617),
-        TotalSecuritiesPurchasedFf(618),
-        TotalBrokerDebitsChf(621),
-        TotalBrokerDebitsFf(623),
-        TotalBrokerDebits(625),
-        TotalFedFundsPurchased(626),
-        TotalCashCenterDebits(628),
-        TotalDebitAdjustments(630),
-        TotalTrustDebits(632),
-        TotalEscrowDebits(640),
-        TransferCalculationDebit(646),
-        InvestmentsPurchased(650),
-        TotalInvestmentInterestDebits(655),
+        TotalDtcDebits(590, "Total DTC Debits"),
+        TotalAtmDebits(594, "Total ATM Debits"),
+        TotalAprDebits(596, "Total APR Debits"),
+        EstimatedTotalDisbursement(601, "Estimated Total Disbursement"),
+        AdjustedTotalDisbursement(602, "Adjusted Total Disbursement"),
+        TotalFundsRequired(610, "Total Funds Required"),
+        TotalWireTransfersOutChf(611, "Total Wire Transfers Out CHF"),
+        TotalWireTransfersOutFf(612, "Total Wire Transfers Out FF"),
+        TotalInternationalDebitChf(613, "Total International Debit CHF"),
+        TotalInternationalDebitFf(614, "Total International Debit FF"),
+        TotalFederalReserveBankCommercialBankDebit(
+            615,
+            "Total Federal Reserve Bank Commercial Bank Debit"
+        ),
+        TotalSecuritiesPurchasedChf(617, "Total Securities Purchased CHF"),
+        TotalSecuritiesPurchasedFf(618, "Total Securities Purchased FF"),
+        TotalBrokerDebitsChf(621, "Total Broker Debits CHF"),
+        TotalBrokerDebitsFf(623, "Total Broker Debits FF"),
+        TotalBrokerDebits(625, "Total Broker Debits"),
+        TotalFedFundsPurchased(626, "Total Fed Funds Purchased"),
+        TotalCashCenterDebits(628, "Total Cash Center Debits"),
+        TotalDebitAdjustments(630, "Total Debit Adjustments"),
+        TotalTrustDebits(632, "Total Trust Debits"),
+        TotalEscrowDebits(640, "Total Escrow Debits"),
+        TransferCalculationDebit(646, "Transfer Calculation Debit"),
+        InvestmentsPurchased(650, "Investments Purchased"),
+        TotalInvestmentInterestDebits(655, "Total Investment Interest Debits"),
         // Correspondent Bank and Federal Reserve
-        InterceptDebits(665),
-        TotalBackValueDebits(670),
-        TotalUniversalDebits(685),
-        FrbFreightPaymentDebits(689),
-        TotalMiscellaneousDebits(690);
+        InterceptDebits(665, "Intercept Debits"),
+        TotalBackValueDebits(670, "Total Back Value Debits"),
+        TotalUniversalDebits(685, "Total Universal Debits"),
+        FrbFreightPaymentDebits(689, "FRB Freight Payment Debits"),
+        TotalMiscellaneousDebits(690, "Total Miscellaneous Debits");
 
         Custom {
             from: c @ 960...999 => Ok(DebitSummary::Custom(c));
@@ -429,155 +525,158 @@ enum_mapping! {
 }
 
 enum_mapping! {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     #[cfg_attr(feature="serde-serialize", derive(Serialize, Deserialize))]
     pub LoanSummary(u16) {
-        TotalLoanPayment(720),
-        LoanDisbursement(760),
+        TotalLoanPayment(720, "Total Loan Payment"),
+        LoanDisbursement(760, "Loan Disbursement"),
     }
 }
 
 enum_mapping! {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     #[cfg_attr(feature="serde-serialize", derive(Serialize, Deserialize))]
     pub CreditDetail(u16) {
-        CreditAnyType(108),
+        CreditAnyType(108, "Credit Any Type"),
         // Lockbox
-        LockboxDeposit(115),
-        ItemInLockboxDeposit(116),
-        LockboxAdjustmentCredit(118),
-        EdiTransactionCredit(121),
-        EdibanxCreditReceived(122),
-        EdibanxCreditReturn(123),
+        LockboxDeposit(115, "Lockbox Deposit"),
+        ItemInLockboxDeposit(116, "Item in Lockbox Deposit"),
+        LockboxAdjustmentCredit(118, "Lockbox Adjustment Credit"),
+        EdiTransactionCredit(121, "EDI Transaction Credit"),
+        EdibanxCreditReceived(122, "EDIBANX Credit Received"),
+        EdibanxCreditReturn(123, "EDIBANX Credit Return"),
         // Concentration
-        DtcConcentrationCredit(135),
-        ItemInDtcDeposit(136),
-        AchCreditReceived(142),
-        ItemInAchDeposit(143),
-        AchConcentrationCredit(145),
-        IndividualBankCardDeposit(147),
+        DtcConcentrationCredit(135, "DTC Concentration Credit"),
+        ItemInDtcDeposit(136, "Item in DTC Deposit"),
+        AchCreditReceived(142, "ACH Credit Received"),
+        ItemInAchDeposit(143, "Item in ACH Deposit"),
+        AchConcentrationCredit(145, "ACH Concentration Credit"),
+        IndividualBankCardDeposit(147, "Individual Bank Card Deposit"),
         // Preauthorized and ACH
-        PreauthorizedDraftCredit(155),
-        ItemInPacDeposit(156),
-        CorporateTradePaymentCredit(164),
-        PreauthorizedAchCredit(165),
-        AchSettlement(166),
-        AchReturnItemOrAdjustmentSettlement(168),
-        MiscAchCredit(169),
+        PreauthorizedDraftCredit(155, "Preauthorized Draft Credit"),
+        ItemInPacDeposit(156, "Item in PAC Deposit"),
+        CorporateTradePaymentCredit(164, "Corporate Trade Payment Credit"),
+        PreauthorizedAchCredit(165, "Preauthorized ACH Credit"),
+        AchSettlement(166, "ACH Settlement"),
+        AchReturnItemOrAdjustmentSettlement(168, "ACH Return Item or Adjustment Settlement"),
+        MiscAchCredit(169, "Miscellaneous ACH Credit"),
         // Other Deposits
-        IndividualLoanDeposit(171),
-        DepositCorrection(172),
-        BankPreparedDeposit(173),
-        OtherDeposit(174),
-        CheckDepositPackage(175),
-        RePresentedCheckDeposit(176),
-        DraftDeposit(184),
-        CashLetterCredit(187),
-        CashLetterAdjustment(189),
+        IndividualLoanDeposit(171, "Individual Loan Deposit"),
+        DepositCorrection(172, "Deposit Correction"),
+        BankPreparedDeposit(173, "Bank Prepared Deposit"),
+        OtherDeposit(174, "Other Deposit"),
+        CheckDepositPackage(175, "Check Deposit Package"),
+        RePresentedCheckDeposit(176, "Re-presented Check Deposit"),
+        DraftDeposit(184, "Draft Deposit"),
+        CashLetterCredit(187, "Cash Letter Credit"),
+        CashLetterAdjustment(189, "Cash Letter Adjustment"),
         // Money Transfer
-        IndividualIncomingInternalMoneyTransfer(191),
-        IncomingMoneyTransfer(195),
-        MoneyTransferAdjustment(196),
-        Compensation(198),
-        IndividualAutomaticTransferCredit(201),
-        BondOperationsCredit(202),
-        BookTransferCredit(206),
-        IndividualInternationalMoneyTransferCredit(208),
-        ForeignLetterOfCredit(212),
-        LetterOfCredit(213),
-        ForeignExchangeOfCredit(214),
-        ForeignRemittanceCredit(216),
-        ForeignCollectionCredit(218),
-        ForeignCheckPurchase(221),
-        ForeignChecksDeposited(222),
-        Commission(224),
-        InternationalMoneyMarketTrading(226),
-        StandingOrder(227),
-        MiscInternationalCredit(229),
+        IndividualIncomingInternalMoneyTransfer(191, "Individual Incoming Internal Money Transfer"),
+        IncomingMoneyTransfer(195, "Incoming Money Transfer"),
+        MoneyTransferAdjustment(196, "Money Transfer Adjustment"),
+        Compensation(198, "Compensation"),
+        IndividualAutomaticTransferCredit(201, "Individual Automatic Transfer Credit"),
+        BondOperationsCredit(202, "Bond Operations Credit"),
+        BookTransferCredit(206, "Book Transfer Credit"),
+        IndividualInternationalMoneyTransferCredit(
+            208,
+            "Individual International Money Transfer Credit"
+        ),
+        ForeignLetterOfCredit(212, "Foreign Letter of Credit"),
+        LetterOfCredit(213, "Letter of Credit"),
+        ForeignExchangeOfCredit(214, "Foreign Exchange of Credit"),
+        ForeignRemittanceCredit(216, "Foreign Remittance Credit"),
+        ForeignCollectionCredit(218, "Foreign Collection Credit"),
+        ForeignCheckPurchase(221, "Foreign Check Purchase"),
+        ForeignChecksDeposited(222, "Foreign Checks Deposited"),
+        Commission(224, "Commission"),
+        InternationalMoneyMarketTrading(226, "International Money Market Trading"),
+        StandingOrder(227, "Standing Order"),
+        MiscInternationalCredit(229, "Miscellaneous International Credit"),
         // Security
-        SaleOfDebtSecurity(232),
-        SecuritiesSold(233),
-        SaleOfEquitySecurity(234),
-        MaturedReverseRepurchaseOrder(235),
-        MaturityOfDebtSecurity(236),
-        IndividualCollectionCredit(237),
-        CollectionOfDividends(238),
-        CouponCollectionsBanks(240),
-        BankersAcceptances(241),
-        CollectionOfInterestIncome(242),
-        MaturedFedFundsPurchased(243),
-        InterestOrMaturedPrincipalPayment(244),
-        CommercialPaper(246),
-        CapitalChange(247),
-        SavingsBondsSalesAdjustment(248),
-        MiscSecurityCredit(249),
-        DebitReversal(252),
-        PostingErrorCorrectionCredit(254),
-        CheckPostedAndReturned(255),
-        IndividualAchReturnItem(257),
-        AchReversalCredit(258),
-        IndividualRejectedCredit(261),
-        Overdraft(263),
-        ReturnItem(266),
-        ReturnItemAdjustment(268),
+        SaleOfDebtSecurity(232, "Sale of Debt Security"),
+        SecuritiesSold(233, "Securities Sold"),
+        SaleOfEquitySecurity(234, "Sale of Equity Security"),
+        MaturedReverseRepurchaseOrder(235, "Matured Reverse Repurchase Order"),
+        MaturityOfDebtSecurity(236, "Maturity of Debt Security"),
+        IndividualCollectionCredit(237, "Individual Collection Credit"),
+        CollectionOfDividends(238, "Collection of Dividends"),
+        CouponCollectionsBanks(240, "Coupon Collections Banks"),
+        BankersAcceptances(241, "Bankers' Acceptances"),
+        CollectionOfInterestIncome(242, "Collection of Interest Income"),
+        MaturedFedFundsPurchased(243, "Matured Fed Funds Purchased"),
+        InterestOrMaturedPrincipalPayment(244, "Interest or Matured Principal Payment"),
+        CommercialPaper(246, "Commercial Paper"),
+        CapitalChange(247, "Capital Change"),
+        SavingsBondsSalesAdjustment(248, "Savings Bonds Sales Adjustment"),
+        MiscSecurityCredit(249, "Miscellaneous Security Credit"),
+        DebitReversal(252, "Debit Reversal"),
+        PostingErrorCorrectionCredit(254, "Posting Error Correction Credit"),
+        CheckPostedAndReturned(255, "Check Posted and Returned"),
+        IndividualAchReturnItem(257, "Individual ACH Return Item"),
+        AchReversalCredit(258, "ACH Reversal Credit"),
+        IndividualRejectedCredit(261, "Individual Rejected Credit"),
+        Overdraft(263, "Overdraft"),
+        ReturnItem(266, "Return Item"),
+        ReturnItemAdjustment(268, "Return Item Adjustment"),
         // ZBA and Disbursing
-        CumulativeZbaOrDisbursementCredits(274),
-        ZbaCredit(275),
-        ZbaFloatAdjustment(276),
-        ZbaCreditTransfer(277),
-        ZbaCreditAdjustment(278),
-        IndividualControlledDisbursingCredit(281),
-        IndividualDtcDisbursingCredit(286),
+        CumulativeZbaOrDisbursementCredits(274, "Cumulative ZBA or Disbursement Credits"),
+        ZbaCredit(275, "ZBA Credit"),
+        ZbaFloatAdjustment(276, "ZBA Float Adjustment"),
+        ZbaCreditTransfer(277, "ZBA Credit Transfer"),
+        ZbaCreditAdjustment(278, "ZBA Credit Adjustment"),
+        IndividualControlledDisbursingCredit(281, "Individual Controlled Disbursing Credit"),
+        IndividualDtcDisbursingCredit(286, "Individual DTC Disbursing Credit"),
         // Other (Expansion)
-        AtmCredit(295),
-        CommercialDeposit(301),
-        FedFundsSold(306),
-        TrustCredit(308),
-        IndividualEscrowCredit(331),
-        BrokerDeposit(342),
-        IndividualBackValueCredit(344),
-        ItemInBrokersDeposit(345),
-        SweepInterestIncome(346),
-        SweepPrincipalSell(347),
-        FuturesCredit(348),
-        PrincipalPaymentsCredit(349),
-        IndividualInvestmentSold(351),
-        CashCenterCredit(353),
-        InterestCredit(354),
-        CreditAdjustment(357),
-        YtdAdjustmentCredit(358),
-        InterestAdjustmentCredit(359),
+        AtmCredit(295, "ATM Credit"),
+        CommercialDeposit(301, "Commercial Deposit"),
+        FedFundsSold(306, "Fed Funds Sold"),
+        TrustCredit(308, "Trust Credit"),
+        IndividualEscrowCredit(331, "Individual Escrow Credit"),
+        BrokerDeposit(342, "Broker Deposit"),
+        IndividualBackValueCredit(344, "Individual Back Value Credit"),
+        ItemInBrokersDeposit(345, "Item in Brokers Deposit"),
+        SweepInterestIncome(346, "Sweep Interest Income"),
+        SweepPrincipalSell(347, "Sweep Principal Sell"),
+        FuturesCredit(348, "Futures Credit"),
+        PrincipalPaymentsCredit(349, "Principal Payments Credit"),
+        IndividualInvestmentSold(351, "Individual Investment Sold"),
+        CashCenterCredit(353, "Cash Center Credit"),
+        InterestCredit(354, "Interest Credit"),
+        CreditAdjustment(357, "Credit Adjustment"),
+        YtdAdjustmentCredit(358, "YTD Adjustment Credit"),
+        InterestAdjustmentCredit(359, "Interest Adjustment Credit"),
         // Correspondent Bank and Federal Reserve
-        CorrespondentCollection(362),
-        CorrespondentCollectionAdjustment(363),
-        LoanParticipation(364),
-        CurrencyAndCoinDeposited(366),
-        FoodStampLetter(367),
-        FoodStampAdjustment(368),
-        ClearingSettlementCredit(369),
-        BackValueAdjustment(372),
-        CustomerPayroll(373),
-        FrbStatementRecap(374),
-        SavingsBondLetterOrAdjustment(376),
-        TreasuryTaxAndLoanCredit(377),
-        TransferOfTreasuryCredit(378),
-        FrbGovernmentChecksCashLetterCredit(379),
-        FrbGovernmentCheckAdjustment(381),
-        FrbPostalMoneyOrderCredit(382),
-        FrbPostalMoneyOrderAdjustment(383),
-        FrbCashLetterAutoChargeCredit(384),
-        FrbCashLetterAutoChargeAdjustment(386),
-        FrbFineSortCashLetterCredit(387),
-        FrbFineSortAdjustment(388),
+        CorrespondentCollection(362, "Correspondent Collection"),
+        CorrespondentCollectionAdjustment(363, "Correspondent Collection Adjustment"),
+        LoanParticipation(364, "Loan Participation"),
+        CurrencyAndCoinDeposited(366, "Currency and Coin Deposited"),
+        FoodStampLetter(367, "Food Stamp Letter"),
+        FoodStampAdjustment(368, "Food Stamp Adjustment"),
+        ClearingSettlementCredit(369, "Clearing Settlement Credit"),
+        BackValueAdjustment(372, "Back Value Adjustment"),
+        CustomerPayroll(373, "Customer Payroll"),
+        FrbStatementRecap(374, "FRB Statement Recap"),
+        SavingsBondLetterOrAdjustment(376, "Savings Bond Letter or Adjustment"),
+        TreasuryTaxAndLoanCredit(377, "Treasury Tax and Loan Credit"),
+        TransferOfTreasuryCredit(378, "Transfer of Treasury Credit"),
+        FrbGovernmentChecksCashLetterCredit(379, "FRB Government Checks Cash Letter Credit"),
+        FrbGovernmentCheckAdjustment(381, "FRB Government Check Adjustment"),
+        FrbPostalMoneyOrderCredit(382, "FRB Postal Money Order Credit"),
+        FrbPostalMoneyOrderAdjustment(383, "FRB Postal Money Order Adjustment"),
+        FrbCashLetterAutoChargeCredit(384, "FRB Cash Letter Auto Charge Credit"),
+        FrbCashLetterAutoChargeAdjustment(386, "FRB Cash Letter Auto Charge Adjustment"),
+        FrbFineSortCashLetterCredit(387, "FRB Fine Sort Cash Letter Credit"),
+        FrbFineSortAdjustment(388, "FRB Fine Sort Adjustment"),
         // Miscellaneous
-        UniversalCredit(391),
-        FreightPaymentCredit(392),
-        ItemizedCreditOverTenThousandDollars(393),
-        CumulativeCredits(394),
-        CheckReversal(395),
-        FloatAdjustment(397),
-        MiscFeeRefund(398),
-        MiscCredit(399);
+        UniversalCredit(391, "Universal Credit"),
+        FreightPaymentCredit(392, "Freight Payment Credit"),
+        ItemizedCreditOverTenThousandDollars(393, "Itemized Credit over Ten Thousand Dollars"),
+        CumulativeCredits(394, "Cumulative Credits"),
+        CheckReversal(395, "Check Reversal"),
+        FloatAdjustment(397, "Float Adjustment"),
+        MiscFeeRefund(398, "Miscellaneous Fee Refund"),
+        MiscCredit(399, "Miscellaneous Credit");
 
         // 920-959
         Custom {
@@ -588,143 +687,146 @@ enum_mapping! {
 }
 
 enum_mapping! {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     #[cfg_attr(feature="serde-serialize", derive(Serialize, Deserialize))]
     pub DebitDetail(u16) {
-        FloatAdjustment(408),
-        DebitAnyType(409),
+        FloatAdjustment(408, "Float Adjustment"),
+        DebitAnyType(409, "Debit Any Type"),
         // Lockbox
-        LockboxDebit(415),
-        EdiTransactionDebit(421),
-        EdibanxSettlementDebit(422),
-        EdibanxReturnItemDebit(423),
+        LockboxDebit(415, "Lockbox Debit"),
+        EdiTransactionDebit(421, "EDI Transaction Debit"),
+        EdibanxSettlementDebit(422, "EDIBANX Settlement Debit"),
+        EdibanxReturnItemDebit(423, "EDIBANX Return Item Debit"),
         // Payable-Through Draft
-        PayableThroughDraft(435),
+        PayableThroughDraft(435, "Payable through Draft"),
         // ACH
-        AchConcentrationDebit(445),
-        AchDisbursementFundingDebit(447),
-        AchDebitRecieved(451),
-        ItemInAchDisbursementOrDebit(452),
-        PreauthorizedAchDebit(455),
-        AccountHolderInitiatedAchDebit(462),
-        CorporateTradePaymentDebit(464),
-        AchSettlement(466),
-        AchReturnItemOrAdjustmentSettlement(468),
-        MiscAchDebit(469),
+        AchConcentrationDebit(445, "ACH Concentration Debit"),
+        AchDisbursementFundingDebit(447, "ACH Disbursement Funding Debit"),
+        AchDebitRecieved(451, "ACH Debit Received"),
+        ItemInAchDisbursementOrDebit(452, "Item in ACH Disbursement or Debit"),
+        PreauthorizedAchDebit(455, "Preauthorized ACH Debit"),
+        AccountHolderInitiatedAchDebit(462, "Account Holder Initiated ACH Debit"),
+        CorporateTradePaymentDebit(464, "Corporate Trade Payment Debit"),
+        AchSettlement(466, "ACH Settlement"),
+        AchReturnItemOrAdjustmentSettlement(468, "ACH Return Item or Adjustment Settlement"),
+        MiscAchDebit(469, "Miscellaneous ACH Debit"),
         // Checks Paid
-        CumulativeChecksPaid(472),
-        CertifiedCheckDebit(474),
-        CheckPaid(475),
-        FederalReserveBankLetterDebit(476),
-        BankOriginatedDebit(477),
-        ListPostDebit(479),
-        IndividualLoanPayment(481),
-        Draft(484),
-        DtcDebit(485),
-        CashLetterDebit(487),
-        CashLetterAdjustment(489),
+        CumulativeChecksPaid(472, "Cumulative Checks Paid"),
+        CertifiedCheckDebit(474, "Certified Check Debit"),
+        CheckPaid(475, "Check Paid"),
+        FederalReserveBankLetterDebit(476, "Federal Reserve Bank Letter Debit"),
+        BankOriginatedDebit(477, "Bank Originated Debit"),
+        ListPostDebit(479, "List Post Debit"),
+        IndividualLoanPayment(481, "Individual Loan Payment"),
+        Draft(484, "Draft"),
+        DtcDebit(485, "DTC Debit"),
+        CashLetterDebit(487, "Cash Letter Debit"),
+        CashLetterAdjustment(489, "Cash Letter Adjustment"),
         // Money Transfer
-        IndividualOutgoingInternalMoneyTransfer(491),
-        CustomerTerminalInitiatedMoneyTransfer(493),
-        OutgoingMoneyTransfer(495),
-        MoneyTransferAdjustment(496),
-        Compensation(498),
-        IndividualAutomaticTransferDebit(501),
-        BondOperationsDebit(502),
-        BookTransferDebit(506),
-        IndividualInternationalMoneyTransferDebits(507),
-        LetterOfCreditDebit(512),
-        LetterOfCredit(513),
-        ForeignExchangeDebit(514),
-        ForeignRemittanceDebit(516),
-        ForeignCollectionDebit(518),
-        ForeignChecksPaid(522),
-        Commision(524),
-        InternationalMoneyMarketTrading(526),
-        StandingOrder(527),
-        MiscInternationalDebit(529),
+        IndividualOutgoingInternalMoneyTransfer(491, "Individual Outgoing Internal Money Transfer"),
+        CustomerTerminalInitiatedMoneyTransfer(493, "Customer Terminal Initiated Money Transfer"),
+        OutgoingMoneyTransfer(495, "Outgoing Money Transfer"),
+        MoneyTransferAdjustment(496, "Money Transfer Adjustment"),
+        Compensation(498, "Compensation"),
+        IndividualAutomaticTransferDebit(501, "Individual Automatic Transfer Debit"),
+        BondOperationsDebit(502, "Bond Operations Debit"),
+        BookTransferDebit(506, "Book Transfer Debit"),
+        IndividualInternationalMoneyTransferDebits(
+            507,
+            "Individual International Money Transfer Debits"
+        ),
+        LetterOfCreditDebit(512, "Letter of Credit Debit"),
+        LetterOfCredit(513, "Letter of Credit"),
+        ForeignExchangeDebit(514, "Foreign Exchange Debit"),
+        ForeignRemittanceDebit(516, "Foreign Remittance Debit"),
+        ForeignCollectionDebit(518, "Foreign Collection Debit"),
+        ForeignChecksPaid(522, "Foreign Checks Paid"),
+        Commision(524, "Commission"),
+        InternationalMoneyMarketTrading(526, "International Money Market Trading"),
+        StandingOrder(527, "Standing Order"),
+        MiscInternationalDebit(529, "Miscellaneous International Debit"),
         // Security
-        SecuritiesPurchased(531),
-        SecurityCollectionDebit(533),
-        PurchaseOfEquitySecurities(535),
-        MaturedRepurchaseOrder(538),
-        CouponCollectionDebit(540),
-        BankersAcceptances(541),
-        PurchaseOfDebtSecurities(542),
-        DomesticCollection(543),
-        InterestOrMaturedPrincipalPayment(544),
-        CommercialPaper(546),
-        CapitalChange(547),
-        SavingsBondsSalesAdjustment(548),
-        MiscSecurityDebit(549),
+        SecuritiesPurchased(531, "Securities Purchased"),
+        SecurityCollectionDebit(533, "Security Collection Debit"),
+        PurchaseOfEquitySecurities(535, "Purchase of Equity Securities"),
+        MaturedRepurchaseOrder(538, "Matured Repurchase Order"),
+        CouponCollectionDebit(540, "Coupon Collection Debit"),
+        BankersAcceptances(541, "Bankers' Acceptances"),
+        PurchaseOfDebtSecurities(542, "Purchase of Debt Securities"),
+        DomesticCollection(543, "Domestic Collection"),
+        InterestOrMaturedPrincipalPayment(544, "Interest or Matured Principal Payment"),
+        CommercialPaper(546, "Commercial Paper"),
+        CapitalChange(547, "Capital Change"),
+        SavingsBondsSalesAdjustment(548, "Savings Bonds Sales Adjustment"),
+        MiscSecurityDebit(549, "Miscellaneous Security Debit"),
         // Deposited Items Returned
-        CreditReversal(552),
-        PostingErrorCorrectionDebit(554),
-        DepositedItemReturned(555),
-        IndividualAchReturnItem(557),
-        AchReversalDebit(558),
-        IndividualRejectedDebit(561),
-        Overdraft(563),
-        OverdraftFee(564),
-        ReturnItem(566),
-        ReturnItemFee(567),
-        ReturnItemAdjustment(568),
+        CreditReversal(552, "Credit Reversal"),
+        PostingErrorCorrectionDebit(554, "Posting Error Correction Debit"),
+        DepositedItemReturned(555, "Deposited Item Returned"),
+        IndividualAchReturnItem(557, "Individual ACH Return Item"),
+        AchReversalDebit(558, "ACH Reversal Debit"),
+        IndividualRejectedDebit(561, "Individual Rejected Debit"),
+        Overdraft(563, "Overdraft"),
+        OverdraftFee(564, "Overdraft Fee"),
+        ReturnItem(566, "Return Item"),
+        ReturnItemFee(567, "Return Item Fee"),
+        ReturnItemAdjustment(568, "Return Item Adjustment"),
         // ZBA and Disbursing
-        CumulativeZbaDebits(574),
-        ZbaDebit(575),
-        ZbaDebitTransfer(577),
-        ZbaDebitAdjustment(578),
-        IndividualControlledDisbursingDebit(581),
+        CumulativeZbaDebits(574, "Cumulative ZBA Debits"),
+        ZbaDebit(575, "ZBA Debit"),
+        ZbaDebitTransfer(577, "ZBA Debit Transfer"),
+        ZbaDebitAdjustment(578, "ZBA Debit Adjustment"),
+        IndividualControlledDisbursingDebit(581, "Individual Controlled Disbursing Debit"),
         // Other (Expansion)
-        AtmDebit(595),
-        ArpDebit(597),
-        FederalReserveBankCommercialBankDebit(616),
-        BrokerDebit(622),
-        FedFundsPurchased(627),
-        CashCenterDebit(629),
-        DebitAdjustment(631),
-        TrustDebit(633),
-        YtdAdjustmentDebit(634),
-        IndividualEscrowDebit(641),
-        IndividualBackValueDebit(644),
-        IndividualInvestmentPurchased(651),
-        InterestDebit(654),
-        SweepPrincipalBuy(656),
-        FuturesDebit(657),
-        PrincipalPaymentsDebit(658),
-        InterestAdjustmentDebit(659),
+        AtmDebit(595, "ATM Debit"),
+        ArpDebit(597, "ARP Debit"),
+        FederalReserveBankCommercialBankDebit(616, "Federal Reserve Bank Commercial Bank Debit"),
+        BrokerDebit(622, "Broker Debit"),
+        FedFundsPurchased(627, "Fed Funds Purchased"),
+        CashCenterDebit(629, "Cash Center Debit"),
+        DebitAdjustment(631, "Debit Adjustment"),
+        TrustDebit(633, "Trust Debit"),
+        YtdAdjustmentDebit(634, "YTD Adjustment Debit"),
+        IndividualEscrowDebit(641, "Individual Escrow Debit"),
+        IndividualBackValueDebit(644, "Individual Back Value Debit"),
+        IndividualInvestmentPurchased(651, "Individual Investment Purchased"),
+        InterestDebit(654, "Interest Debit"),
+        SweepPrincipalBuy(656, "Sweep Principal Buy"),
+        FuturesDebit(657, "Futures Debit"),
+        PrincipalPaymentsDebit(658, "Principal Payments Debit"),
+        InterestAdjustmentDebit(659, "Interest Adjustment Debit"),
         // Correspondent Bank and Federal Reserve
-        AccountAnalysisFee(661),
-        CorrespondentCollectionDebit(662),
-        CorrespondentCollectionAdjustment(663),
-        LoanParticipation(664),
-        CurrencyAndCoinShipped(666),
-        FoodStampLetter(667),
-        FoodStampAdjustment(668),
-        ClearingSettlementDebit(669),
-        BackValueAdjustment(672),
-        CustomerPayroll(673),
-        FrbStatementRecap(674),
-        SavingsBondLetterOrAdjustment(676),
-        TreasuryTaxAndLoanDebit(677),
-        TransferOfTreasuryDebit(678),
-        FrbGovernmentChecksCashLetterDebit(679),
-        FrbGovernmentCheckAdjustment(681),
-        FrbPostalMoneyOrderDebit(682),
-        FrbPostalMoneyOrderAdjustment(683),
-        FrbCashLetterAutoChargeDebit(684),
-        FrbCashLetterAutoChargeAdjustment(686),
-        FrbFineSortCashLetterDebit(687),
-        FrbFineSortAdjustment(688),
-        UniversalDebit(691),
-        FreightPaymentDebit(692),
-        ItemizedDebitOverTenThousandDollars(693),
-        DepositReversal(694),
-        DepositCorrectionDebit(695),
-        RegularCollectionDebit(696),
-        CumulativeDebits(697),
-        MiscFees(698),
-        MiscDebit(699);
+        AccountAnalysisFee(661, "Account Analysis Fee"),
+        CorrespondentCollectionDebit(662, "Correspondent Collection Debit"),
+        CorrespondentCollectionAdjustment(663, "Correspondent Collection Adjustment"),
+        LoanParticipation(664, "Loan Participation"),
+        CurrencyAndCoinShipped(666, "Currency and Coin Shipped"),
+        FoodStampLetter(667, "Food Stamp Letter"),
+        FoodStampAdjustment(668, "Food Stamp Adjustment"),
+        ClearingSettlementDebit(669, "Clearing Settlement Debit"),
+        BackValueAdjustment(672, "Back Value Adjustment"),
+        CustomerPayroll(673, "Customer Payroll"),
+        FrbStatementRecap(674, "FRB Statement Recap"),
+        SavingsBondLetterOrAdjustment(676, "Savings Bond Letter or Adjustment"),
+        TreasuryTaxAndLoanDebit(677, "Treasury Tax and Loan Debit"),
+        TransferOfTreasuryDebit(678, "Transfer of Treasury Debit"),
+        FrbGovernmentChecksCashLetterDebit(679, "FRB Government Checks Cash Letter Debit"),
+        FrbGovernmentCheckAdjustment(681, "FRB Government Check Adjustment"),
+        FrbPostalMoneyOrderDebit(682, "FRB Postal Money Order Debit"),
+        FrbPostalMoneyOrderAdjustment(683, "FRB Postal Money Order Adjustment"),
+        FrbCashLetterAutoChargeDebit(684, "FRB Cash Letter Auto Charge Debit"),
+        FrbCashLetterAutoChargeAdjustment(686, "FRB Cash Letter Auto Charge Adjustment"),
+        FrbFineSortCashLetterDebit(687, "FRB Fine Sort Cash Letter Debit"),
+        FrbFineSortAdjustment(688, "FRB Fine Sort Adjustment"),
+        UniversalDebit(691, "Universal Debit"),
+        FreightPaymentDebit(692, "Freight Payment Debit"),
+        ItemizedDebitOverTenThousandDollars(693, "Itemized Debit over Ten Thousand Dollars"),
+        DepositReversal(694, "Deposit Reversal"),
+        DepositCorrectionDebit(695, "Deposit Correction Debit"),
+        RegularCollectionDebit(696, "Regular Collection Debit"),
+        CumulativeDebits(697, "Cumulative Debits"),
+        MiscFees(698, "Miscellaneous Fees"),
+        MiscDebit(699, "Miscellaneous Debit");
 
         // 960-999
         Custom {
@@ -735,16 +837,16 @@ enum_mapping! {
 }
 
 enum_mapping! {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     #[cfg_attr(feature="serde-serialize", derive(Serialize, Deserialize))]
     pub LoanDetail(u16) {
-        AmountAppliedToInterest(721),
-        AmountAppliedToPrincipal(722),
-        AmountAppliedToEscrow(723),
-        AmountAppliedToLateCharges(724),
-        AmountAppliedToBuydown(725),
-        AmountAppliedToMiscFees(726),
-        AmountAppliedToDeferredInterestDetail(727),
-        AmountAppliedToServiceCharge(728),
+        AmountAppliedToInterest(721, "Amount Applied to Interest"),
+        AmountAppliedToPrincipal(722, "Amount Applied to Principal"),
+        AmountAppliedToEscrow(723, "Amount Applied to Escrow"),
+        AmountAppliedToLateCharges(724, "Amount Applied to Late Charges"),
+        AmountAppliedToBuydown(725, "Amount Applied to Buydown"),
+        AmountAppliedToMiscFees(726, "Amount Applied to Miscellaneous Fees"),
+        AmountAppliedToDeferredInterestDetail(727, "Amount Applied to Deferred Interest Detail"),
+        AmountAppliedToServiceCharge(728, "Amount Applied to Service Charge"),
     }
 }
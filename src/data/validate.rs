@@ -0,0 +1,445 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use super::{Account, AccountInfo, AccountNumber, AccountStatus, AsOfDateModifier, File,
+            FundsType, GroupStatus, ReferenceNum, SummaryCode, TransactionDetail};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Severity::Info => write!(f, "Info"),
+            Severity::Warning => write!(f, "Warning"),
+            Severity::Error => write!(f, "Error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum Location {
+    File,
+    Group { group: usize },
+    Account { group: usize, account: usize },
+    TransactionDetail {
+        group: usize,
+        account: usize,
+        transaction: usize,
+    },
+}
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Location::File => write!(f, "file"),
+            Location::Group { group } => write!(f, "group {}", group),
+            Location::Account { group, account } => {
+                write!(f, "group {}, account {}", group, account)
+            }
+            Location::TransactionDetail {
+                group,
+                account,
+                transaction,
+            } => {
+                write!(
+                    f,
+                    "group {}, account {}, transaction {}",
+                    group,
+                    account,
+                    transaction
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum IssueKind {
+    EmptySender,
+    EmptyReceiver,
+    MissingOriginator,
+    DefaultCurrency,
+    EmptyAccountNumber,
+    DuplicateAccount(AccountNumber),
+    // Opening ledger plus signed transaction amounts doesn't match closing ledger.
+    Unreconciled { expected: i64, actual: i64 },
+    // A summary's item count doesn't match the number of detail records it covers.
+    ItemCount {
+        code: SummaryCode,
+        expected: u32,
+        actual: usize,
+    },
+    // A correction or deletion that doesn't say which previously sent data it replaces.
+    RevisionWithoutModifier(GroupStatus),
+    // Corrections and deletions replace final data, not interim data.
+    RevisionOfInterimData {
+        status: GroupStatus,
+        modifier: AsOfDateModifier,
+    },
+    // Files normally have one status for all their groups, so a group with a different status
+    // than the first group is flagged, as that's often a bug in whatever made the file.
+    MixedGroupStatus {
+        first: GroupStatus,
+        status: GroupStatus,
+    },
+    // Distributed availability amounts that don't add up to the transaction's amount.
+    FundsDistribution {
+        bank_ref_num: Option<ReferenceNum>,
+        amount: i64,
+        distributed: i64,
+    },
+    // Trailer counts that don't match what was read. `actual` for records counts the header and
+    // trailer, as the spec does.
+    GroupCount { expected: usize, actual: usize },
+    AccountCount { expected: usize, actual: usize },
+    RecordCount { expected: usize, actual: usize },
+}
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IssueKind::EmptySender => write!(f, "Sender identification is empty"),
+            IssueKind::EmptyReceiver => write!(f, "Receiver identification is empty"),
+            IssueKind::MissingOriginator => write!(f, "Originator identification is missing"),
+            IssueKind::DefaultCurrency => write!(f, "No currency given, defaulting to USD"),
+            IssueKind::EmptyAccountNumber => write!(f, "Customer account number is empty"),
+            IssueKind::DuplicateAccount(ref account) => {
+                write!(f, "Account {} appears more than once", account)
+            }
+            IssueKind::Unreconciled { expected, actual } => {
+                write!(
+                    f,
+                    "Closing ledger is {}, but opening ledger and transactions give {}",
+                    actual,
+                    expected
+                )
+            }
+            IssueKind::ItemCount {
+                code,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "{} claims {} items, but there are {} transaction details",
+                    code,
+                    expected,
+                    actual
+                )
+            }
+            IssueKind::RevisionWithoutModifier(status) => {
+                write!(f, "{} group has no as-of date modifier", status)
+            }
+            IssueKind::RevisionOfInterimData { status, modifier } => {
+                write!(f, "{} group replaces {}", status, modifier)
+            }
+            IssueKind::MixedGroupStatus { first, status } => {
+                write!(f, "{} group in a file that starts with a {} group", status, first)
+            }
+            IssueKind::FundsDistribution {
+                ref bank_ref_num,
+                amount,
+                distributed,
+            } => {
+                write!(f, "Transaction ")?;
+                if let Some(ref bank_ref_num) = *bank_ref_num {
+                    write!(f, "{} ", bank_ref_num)?;
+                }
+                write!(
+                    f,
+                    "of {} has {} distributed, {:+} off",
+                    amount,
+                    distributed,
+                    distributed.saturating_sub(amount)
+                )
+            }
+            IssueKind::GroupCount { expected, actual } => {
+                write!(f, "Trailer counts {} groups, but there are {}", expected, actual)
+            }
+            IssueKind::AccountCount { expected, actual } => {
+                write!(f, "Trailer counts {} accounts, but there are {}", expected, actual)
+            }
+            IssueKind::RecordCount { expected, actual } => {
+                write!(f, "Trailer counts {} records, but there are {}", expected, actual)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct Issue {
+    pub severity: Severity,
+    pub location: Location,
+    pub kind: IssueKind,
+}
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {}: {}", self.severity, self.location, self.kind)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct ValidationReport {
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    fn push(&mut self, severity: Severity, location: Location, kind: IssueKind) {
+        self.issues.push(Issue {
+            severity,
+            location,
+            kind,
+        });
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(|i| i.severity == Severity::Error)
+    }
+
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.issues.iter().map(|i| i.severity).max()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.issues.is_empty() {
+            return write!(f, "No issues");
+        }
+        for issue in &self.issues {
+            write!(f, "{}\n", issue)?;
+        }
+        Ok(())
+    }
+}
+
+fn item_count_issues(account: &Account) -> Vec<IssueKind> {
+    // Accounts without any details are taken to only report summaries.
+    if account.transaction_details.is_empty() {
+        return Vec::new();
+    }
+    account
+        .infos
+        .iter()
+        .filter_map(|i| match *i {
+            AccountInfo::Summary {
+                code,
+                item_count: Some(expected),
+                ..
+            } => {
+                let counted = account
+                    .transaction_details
+                    .iter()
+                    .map(|td| code.counts_detail(td.code))
+                    .collect::<Option<Vec<_>>>()?;
+                let actual = counted.into_iter().filter(|&c| c).count();
+                if actual == expected as usize {
+                    None
+                } else {
+                    Some(IssueKind::ItemCount {
+                        code,
+                        expected,
+                        actual,
+                    })
+                }
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+// Parts of an S distribution that were left empty count as zero.
+fn funds_distribution_issue(detail: &TransactionDetail) -> Option<IssueKind> {
+    let amount = detail.amount?;
+    let distributed = match detail.funds {
+        Some(FundsType::DistributedAvailS {
+            immediate,
+            one_day,
+            more_than_one_day,
+        }) => [immediate, one_day, more_than_one_day]
+            .iter()
+            .fold(0i64, |sum, a| sum.saturating_add(a.unwrap_or(0))),
+        Some(FundsType::DistributedAvailD(ref dists)) => dists
+            .iter()
+            .fold(0i64, |sum, d| sum.saturating_add(d.amount)),
+        _ => return None,
+    };
+    if distributed == amount {
+        return None;
+    }
+    Some(IssueKind::FundsDistribution {
+        bank_ref_num: detail.bank_ref_num.clone(),
+        amount,
+        distributed,
+    })
+}
+
+// Group and account trailers can count records either way `RecordCountConvention` allows, but the
+// file trailer has to count them as the spec does.
+fn record_count_issue(
+    declared: Option<usize>,
+    actual: Option<usize>,
+    exclusive: bool,
+) -> Option<IssueKind> {
+    match (declared, actual) {
+        (Some(expected), Some(actual))
+            if expected != actual && !(exclusive && expected + 2 == actual) =>
+        {
+            Some(IssueKind::RecordCount { expected, actual })
+        }
+        _ => None,
+    }
+}
+
+impl File {
+    pub fn validate(&self) -> ValidationReport {
+        use self::IssueKind as IK;
+        use self::Severity as S;
+
+        let mut report = ValidationReport::default();
+        if self.sender.0.trim().is_empty() {
+            report.push(S::Error, Location::File, IK::EmptySender);
+        }
+        if self.receiver.0.trim().is_empty() {
+            report.push(S::Error, Location::File, IK::EmptyReceiver);
+        }
+        match self.declared_group_count {
+            Some(expected) if expected != self.groups.len() => {
+                let actual = self.groups.len();
+                report.push(S::Error, Location::File, IK::GroupCount { expected, actual });
+            }
+            _ => {}
+        }
+        if let Some(kind) = record_count_issue(self.declared_record_count, self.record_count, false)
+        {
+            report.push(S::Error, Location::File, kind);
+        }
+
+        let first_status = self.groups.first().map(|g| g.status);
+        for (g, group) in self.groups.iter().enumerate() {
+            let location = Location::Group { group: g };
+            if let Some(first) = first_status {
+                if group.status != first {
+                    report.push(
+                        S::Warning,
+                        location,
+                        IK::MixedGroupStatus {
+                            first,
+                            status: group.status,
+                        },
+                    );
+                }
+            }
+            if group.originator.as_ref().map_or(true, |o| o.0.trim().is_empty()) {
+                report.push(S::Warning, location, IK::MissingOriginator);
+            }
+            if group.currency.is_none() {
+                report.push(S::Warning, location, IK::DefaultCurrency);
+            }
+            match group.declared_account_count {
+                Some(expected) if expected != group.accounts.len() => {
+                    let actual = group.accounts.len();
+                    report.push(S::Error, location, IK::AccountCount { expected, actual });
+                }
+                _ => {}
+            }
+            if let Some(kind) =
+                record_count_issue(group.declared_record_count, group.record_count, true)
+            {
+                report.push(S::Error, location, kind);
+            }
+            if group.status == GroupStatus::Correction || group.status == GroupStatus::Deletion {
+                match group.as_of_date_mod {
+                    None => {
+                        report.push(S::Warning, location, IK::RevisionWithoutModifier(group.status))
+                    }
+                    Some(modifier @ AsOfDateModifier::InterimPrevious) |
+                    Some(modifier @ AsOfDateModifier::InterimSame) => {
+                        report.push(
+                            S::Warning,
+                            location,
+                            IK::RevisionOfInterimData {
+                                status: group.status,
+                                modifier,
+                            },
+                        )
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            let mut seen = HashSet::new();
+            for (a, account) in group.accounts.iter().enumerate() {
+                let location = Location::Account {
+                    group: g,
+                    account: a,
+                };
+                let account_num = account.customer_account.normalized();
+                if account_num.is_empty() {
+                    report.push(S::Error, location, IK::EmptyAccountNumber);
+                } else if !seen.insert(account_num) {
+                    report.push(
+                        S::Warning,
+                        location,
+                        IK::DuplicateAccount(account.customer_account.clone()),
+                    );
+                }
+
+                if let Some(kind) =
+                    record_count_issue(account.declared_record_count, account.record_count, true)
+                {
+                    report.push(S::Error, location, kind);
+                }
+                for kind in item_count_issues(account) {
+                    report.push(S::Warning, location, kind);
+                }
+
+                for (t, detail) in account.transaction_details.iter().enumerate() {
+                    if let Some(kind) = funds_distribution_issue(detail) {
+                        let location = Location::TransactionDetail {
+                            group: g,
+                            account: a,
+                            transaction: t,
+                        };
+                        report.push(S::Warning, location, kind);
+                    }
+                }
+
+                let opening = account.status_amount(AccountStatus::OpeningLedger);
+                let closing = account.status_amount(AccountStatus::ClosingLedger);
+                if let (Some(opening), Some(closing)) = (opening, closing) {
+                    let net = account
+                        .transaction_details
+                        .iter()
+                        .map(|td| td.signed_amount())
+                        .fold(Some(0i64), |acc, a| match (acc, a) {
+                            (Some(acc), Some(a)) => acc.checked_add(a),
+                            _ => None,
+                        });
+                    // Loan and non-monetary details can't be reconciled, and neither can amounts
+                    // that add up past `i64`.
+                    if let Some(expected) = net.and_then(|net| opening.checked_add(net)) {
+                        if expected != closing {
+                            report.push(
+                                S::Warning,
+                                location,
+                                IK::Unreconciled {
+                                    expected,
+                                    actual: closing,
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        report
+    }
+}
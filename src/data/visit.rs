@@ -0,0 +1,44 @@
+use super::{Account, File, Group, TransactionDetail};
+
+// Every hook defaults to doing nothing. Parents are visited before their children.
+pub trait FileVisitor {
+    fn visit_file(&mut self, _file: &File) {}
+    fn visit_group(&mut self, _group: &Group) {}
+    fn visit_account(&mut self, _account: &Account) {}
+    fn visit_transaction(&mut self, _transaction: &TransactionDetail) {}
+}
+
+pub trait FileVisitorMut {
+    fn visit_file(&mut self, _file: &mut File) {}
+    fn visit_group(&mut self, _group: &mut Group) {}
+    fn visit_account(&mut self, _account: &mut Account) {}
+    fn visit_transaction(&mut self, _transaction: &mut TransactionDetail) {}
+}
+
+impl File {
+    pub fn visit<V: FileVisitor>(&self, v: &mut V) {
+        v.visit_file(self);
+        for group in &self.groups {
+            v.visit_group(group);
+            for account in &group.accounts {
+                v.visit_account(account);
+                for transaction in &account.transaction_details {
+                    v.visit_transaction(transaction);
+                }
+            }
+        }
+    }
+
+    pub fn visit_mut<V: FileVisitorMut>(&mut self, v: &mut V) {
+        v.visit_file(self);
+        for group in &mut self.groups {
+            v.visit_group(group);
+            for account in &mut group.accounts {
+                v.visit_account(account);
+                for transaction in &mut account.transaction_details {
+                    v.visit_transaction(transaction);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt;
+
+use ast::{self, ParsedRecord};
+use ast::convert::{ConvertError, Converter};
+use ast::parse::Parsed;
+use parse::ParseOptions;
+
+use super::{is_deprecated_code, File, FileProcessError};
+
+// Things worth flagging in a file that don't stop it from being processed. Record numbers count
+// physical records from 1, so they point at the line the record starts on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum ProcessWarning {
+    // A code the spec doesn't list, read through a `CodeTable` override. See
+    // `is_deprecated_code`.
+    DeprecatedCode { code: u16, record_number: usize },
+    // Read as Z, with `ParseOptions::lenient_funds_types`. Without it the file isn't processed.
+    UnknownFundsType {
+        indicator: String,
+        record_number: usize,
+    },
+}
+
+impl fmt::Display for ProcessWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ProcessWarning::DeprecatedCode {
+                code,
+                record_number,
+            } => write!(f, "Record {} uses deprecated type code {:03}", record_number, code),
+            ProcessWarning::UnknownFundsType {
+                ref indicator,
+                record_number,
+            } => write!(
+                f,
+                "Record {} has unknown funds type {:?}, read as Z",
+                record_number,
+                indicator
+            ),
+        }
+    }
+}
+
+fn record_warnings(record: &ParsedRecord, record_number: usize) -> Vec<ProcessWarning> {
+    let (codes, funds) = match *record {
+        ParsedRecord::AccountIdent(ref ai) => (
+            ai.infos.iter().filter_map(|i| i.type_code).collect(),
+            ai.infos.iter().filter_map(|i| i.funds_type.as_ref()).collect(),
+        ),
+        ParsedRecord::TransactionDetail(ref td) => {
+            (vec![td.type_code], td.funds_type.iter().collect())
+        }
+        _ => (Vec::new(), Vec::new()),
+    };
+    let deprecated = codes
+        .into_iter()
+        .filter(|&code| is_deprecated_code(code))
+        .map(|code| {
+            ProcessWarning::DeprecatedCode {
+                code,
+                record_number,
+            }
+        });
+    let unknown = funds.into_iter().filter_map(|funds| match *funds {
+        ast::ParsedFundsType::Other(ref indicator) => Some(ProcessWarning::UnknownFundsType {
+            indicator: indicator.clone(),
+            record_number,
+        }),
+        _ => None,
+    });
+    deprecated.chain(unknown).collect()
+}
+
+// Parses every record, collecting the warnings about each.
+fn parse_with_warnings<'a>(
+    raw_records: &[(ast::RawRecord<'a>, usize)],
+) -> Result<(Vec<(ParsedRecord<'a>, usize)>, Vec<ProcessWarning>), FileProcessError<'a>> {
+    let mut warnings = Vec::new();
+    let mut record_number = 1;
+    let mut parsed_records = Vec::with_capacity(raw_records.len());
+    for &(ref r, physical) in raw_records {
+        let record = ast::Record::parse(r).map_err(FileProcessError::FieldParse)?;
+        warnings.extend(record_warnings(&record, record_number));
+        record_number += physical;
+        parsed_records.push((record, physical));
+    }
+    Ok((parsed_records, warnings))
+}
+
+fn unfinished<'a>(e: Option<ConvertError>) -> FileProcessError<'a> {
+    match e {
+        Some(e) => e.into(),
+        None => FileProcessError::UnfinishedConversion,
+    }
+}
+
+impl File {
+    pub fn process_with_warnings<'a>(
+        file: &'a [u8],
+    ) -> Result<(File, Vec<ProcessWarning>), FileProcessError<'a>> {
+        let raw_records = super::raw_records(file)?;
+        let (parsed_records, warnings) = parse_with_warnings(&raw_records)?;
+        let mut parsed_records = parsed_records.into_iter().map(Ok);
+        let file = Converter::fold_results_with_count(&mut parsed_records, unfinished)?;
+        Ok((file, warnings))
+    }
+
+    pub fn process_with_options_and_warnings(
+        file: &[u8],
+        options: &ParseOptions,
+    ) -> Result<(File, Vec<ProcessWarning>), FileProcessError<'static>> {
+        let input = options.prepare(file);
+        let raw_records = super::dialect_raw_records(&input, options.dialect)
+            .map_err(FileProcessError::into_owned)?;
+        let (parsed_records, warnings) =
+            parse_with_warnings(&raw_records).map_err(FileProcessError::into_owned)?;
+        let mut parsed_records = parsed_records.into_iter().map(Ok);
+        let file = options
+            .converter()
+            .fold_results_with(&mut parsed_records, unfinished)
+            .map_err(FileProcessError::into_owned)?;
+        Ok((File::finish_prepared(file, &input, options)?, warnings))
+    }
+}
@@ -8,18 +8,22 @@ extern crate itertools;
 #[macro_use]
 extern crate nom;
 extern crate penny;
+#[cfg(feature = "encoding")]
+extern crate encoding_rs;
 #[cfg(feature = "serde")]
 extern crate serde;
 #[cfg(feature = "serde")]
 #[macro_use]
 extern crate serde_derive;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
 #[cfg(test)]
 extern crate test;
 extern crate void;
 
 macro_rules! enum_mapping {
     ($(#[$attr:meta])* pub $name:ident($ty:ty) {
-        $($key:ident($val:expr)),+;
+        $($key:ident($val:expr, $desc:expr)),+;
         $custom:ident {
             from: $from_pat:pat => $from_expr:expr;
             to: $to_pat:pat => $to_expr:expr;
@@ -48,8 +52,16 @@ macro_rules! enum_mapping {
                 }
             }
         }
+        impl $name {
+            pub fn description(&self) -> &'static str {
+                match *self {
+                    $($name::$key => $desc,)+
+                    $name::$custom(_) => "Custom",
+                }
+            }
+        }
     };
-    ($(#[$attr:meta])* pub $name:ident($ty:ty) { $($key:ident($val:expr),)+ }) => {
+    ($(#[$attr:meta])* pub $name:ident($ty:ty) { $($key:ident($val:expr, $desc:expr),)+ }) => {
         $(#[$attr])*
         pub enum $name {
             $($key,)+
@@ -70,12 +82,25 @@ macro_rules! enum_mapping {
                 }
             }
         }
+        impl $name {
+            pub fn description(&self) -> &'static str {
+                match *self {
+                    $($name::$key => $desc,)+
+                }
+            }
+        }
     };
 }
 
 pub mod ast;
 pub mod data;
+#[cfg(feature = "encoding")]
+pub mod encoding;
 pub mod parse;
+pub mod prelude;
+pub mod write;
+
+pub use write::{to_string, to_vec, to_writer};
 
 #[cfg(test)]
 mod tests {
@@ -151,4 +176,3009 @@ mod tests {
         ast_parse_spec_example,
         convert_spec_example
     );
+
+    // The spec example's groups repeated, for throughput on something closer to a real file.
+    fn large_file() -> String {
+        let mut file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let groups = file.groups.clone();
+        for _ in 1..200 {
+            file.groups.extend(groups.iter().cloned());
+        }
+        let mut large = String::new();
+        write::file(&mut large, &file).unwrap();
+        large
+    }
+
+    #[bench]
+    fn process_large_file(b: &mut Bencher) {
+        let large = large_file();
+        b.iter(|| data::File::process(large.as_bytes()).unwrap())
+    }
+
+    // The AST borrows every field from the input, so this is the cost without any allocation per
+    // field.
+    #[bench]
+    fn ast_parse_large_file(b: &mut Bencher) {
+        let large = large_file();
+        let raw = parse::file(large.as_bytes()).to_result().unwrap();
+        use ast::parse::Parsed;
+        b.iter(|| raw.iter().map(|r| ast::Record::parse(r)).count())
+    }
+
+    #[bench]
+    fn records_large_file(b: &mut Bencher) {
+        let large = large_file();
+        b.iter(|| parse::records(large.as_bytes()).count())
+    }
+
+    static REFERENCE_NUMS: &'static str = "\
+01,SENDER,RECEIVER,170601,1200,1,,,2/
+02,RECEIVER,SENDER,1,170601,,USD,2/
+03,12345,,010,1000,,/
+16,175,500,,0000123,  00042,/
+49,1500,3/
+98,1500,1,5/
+99,1500,1,7/
+";
+
+    #[test]
+    fn reference_nums_verbatim() {
+        let file = data::File::process(REFERENCE_NUMS.as_bytes()).unwrap();
+        let detail = &file.groups[0].accounts[0].transaction_details[0];
+        let bank_ref_num = detail.bank_ref_num.as_ref().unwrap();
+        let customer_ref_num = detail.customer_ref_num.as_ref().unwrap();
+        assert_eq!(bank_ref_num.0, "0000123");
+        assert_eq!(customer_ref_num.0, "  00042");
+        assert_eq!(bank_ref_num.to_string(), "r#\"0000123\"");
+        assert_eq!(customer_ref_num.to_string(), "r#\"  00042\"");
+    }
+
+    #[test]
+    fn file_try_from_and_from_str() {
+        use std::convert::TryInto;
+
+        let file: data::File = SPEC_EXAMPLE.as_bytes().try_into().unwrap();
+        assert_eq!(file.groups.len(), 4);
+        let file: data::File = SPEC_EXAMPLE.parse().unwrap();
+        assert_eq!(file.groups.len(), 4);
+        let file = data::File::process_str(&SPEC_EXAMPLE.to_owned()).unwrap();
+        assert_eq!(file.groups.len(), 4);
+        assert!("garbage\n".parse::<data::File>().is_err());
+
+        // The errors don't borrow the input, so they can be passed on with `?`.
+        fn parse_owned(input: String) -> Result<data::File, Box<::std::error::Error>> {
+            let file: data::File = input.as_bytes().try_into()?;
+            Ok(file)
+        }
+        assert_eq!(parse_owned(SPEC_EXAMPLE.to_owned()).unwrap().groups.len(), 4);
+        assert!(parse_owned("garbage\n".to_owned()).is_err());
+        let err = "garbage\n".parse::<data::File>().unwrap_err();
+        let err: Box<::std::error::Error> = Box::new(err);
+        assert!(err.to_string().starts_with("Couldn't parse"));
+    }
+
+    #[test]
+    fn file_trailer_records_num() {
+        let truncated = SPEC_EXAMPLE.replace("99,345450000,4,31/", "99,345450000,4,30/");
+        assert!(data::File::process(truncated.as_bytes()).is_ok());
+        match data::File::process_validated(truncated.as_bytes()) {
+            Err(data::FileProcessError::RecordCountMismatch { expected: 30, actual: 31 }) => {}
+            r => panic!("unexpected result: {:?}", r),
+        }
+
+        let truncated = SPEC_EXAMPLE.replace("99,345450000,4,31/", "99,345450000,5,31/");
+        match data::File::process_validated(truncated.as_bytes()) {
+            Err(data::FileProcessError::RecordCountMismatch { expected: 5, actual: 4 }) => {}
+            r => panic!("unexpected result: {:?}", r),
+        }
+
+        let file = data::File::process_validated(SPEC_EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(file, data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn file_diff() {
+        let old = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        assert!(old.diff(&old).is_empty());
+
+        let mut new = old.clone();
+        new.groups[0].accounts.remove(1);
+        new.groups[1].accounts[0].customer_account = data::AccountNumber("04589761203".to_owned());
+        new.groups[1].accounts[0].transaction_details[0].amount = Some(20000001);
+        new.groups[1].accounts[0].transaction_details.remove(1);
+        let diff = old.diff(&new);
+        assert_eq!(diff.added_accounts, vec![]);
+        assert_eq!(diff.removed_accounts, vec![data::AccountNumber("9876543210".to_owned())]);
+        assert_eq!(diff.changed_accounts.len(), 1);
+        let account = &diff.changed_accounts[0];
+        assert_eq!(account.account.normalized(), "4589761203");
+        assert_eq!(account.infos, None);
+        assert_eq!(account.changed_transactions.len(), 1);
+        assert_eq!(account.changed_transactions[0].new.amount, Some(20000001));
+        assert_eq!(account.removed_transactions.len(), 1);
+        assert_eq!(account.added_transactions, vec![]);
+    }
+
+    static EMPTY_GROUP_AND_ACCOUNT: &'static str = "\
+01,SENDER,RECEIVER,170601,1200,2,,,2/
+02,RECEIVER,SENDER,1,170601,,,2/
+98,0,0,2/
+02,RECEIVER,SENDER,1,170601,,,2/
+03,12345,,010,1000,,,015,1000,,/
+49,2000,2/
+98,2000,1,4/
+99,2000,2,8/
+";
+
+    #[test]
+    fn empty_group_and_account() {
+        let file = data::File::process(EMPTY_GROUP_AND_ACCOUNT.as_bytes()).unwrap();
+        assert_eq!(file.groups.len(), 2);
+        assert!(file.groups[0].accounts.is_empty());
+        let account = &file.groups[1].accounts[0];
+        assert_eq!(account.infos.len(), 2);
+        assert!(account.transaction_details.is_empty());
+    }
+
+    #[test]
+    fn totals_by_currency() {
+        use penny::Currency;
+
+        let mut file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        file.groups[0].accounts[1].currency = Some(Currency::EUR);
+        file.groups[1].currency = Some(Currency::CAD);
+        let totals = file.totals_by_currency();
+        assert_eq!(totals.len(), 3);
+        assert_eq!(totals["USD"].amount(), 450000);
+        assert_eq!(totals["USD"].currency(), Currency::USD);
+        assert_eq!(totals["EUR"].amount(), 500000);
+        assert_eq!(totals["CAD"].amount(), 30000000);
+
+        // The 475 debit cancels out the 175 credit.
+        let file = data::File::process(DISTRIBUTED_AVAIL.as_bytes()).unwrap();
+        assert_eq!(file.totals_by_currency()["USD"].amount(), 0);
+    }
+
+    #[test]
+    fn physical_records() {
+        let records = parse::records(SPEC_EXAMPLE.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 31);
+        assert_eq!(records[0].code, "01");
+        assert_eq!(
+            records[0].fields,
+            vec!["122099999", "123456789", "040621", "0200", "1", "65", "", "2"]
+        );
+        assert_eq!(records[3].code, "88");
+        assert_eq!(records[3].fields, vec!["072", "1020000", "", "", "074", "500000", "", ""]);
+        assert_eq!(records[9].fields.last(), Some(&"LOCK BOX NO.68751"));
+        assert_eq!(records[30].code, "99");
+
+        // Commas in text don't split it.
+        let commas = SLASHED_TEXT
+            .replace("WIRE IN/OUT NET SETTLEMENT", "WIRE, IN/OUT\n88,NET, SETTLEMENT")
+            .replace("16,195,250,,,,", "16,195,250,S,1,2,3,REF,,");
+        let records = parse::records(commas.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(records[3].fields, vec!["195", "500", "", "", "", "WIRE, IN/OUT"]);
+        assert_eq!(records[4].code, "88");
+        assert_eq!(records[4].fields, vec!["NET, SETTLEMENT"]);
+        assert_eq!(
+            records[5].fields,
+            vec!["195", "250", "S", "1", "2", "3", "REF", "", "/FX/ NET SETTLEMENT"]
+        );
+    }
+
+    static SLASHED_TEXT: &'static str = "\
+01,SENDER,RECEIVER,170601,1200,1,,,2/
+02,RECEIVER,SENDER,1,170601,,,2/
+03,12345,,010,1000,,/
+16,195,500,,,,WIRE IN/OUT NET SETTLEMENT
+16,195,250,,,,/FX/ NET SETTLEMENT/
+16,195,100,,,,/
+49,1850,5/
+98,1850,1,7/
+99,1850,1,9/
+";
+
+    #[test]
+    fn slashes_in_text() {
+        let file = data::File::process(SLASHED_TEXT.as_bytes()).unwrap();
+        let details = &file.groups[0].accounts[0].transaction_details;
+        assert_eq!(details[0].text, Some(vec!["WIRE IN/OUT NET SETTLEMENT".to_owned()]));
+        assert_eq!(details[1].text, Some(vec!["/FX/ NET SETTLEMENT/".to_owned()]));
+        assert_eq!(details[2].text, None);
+    }
+
+    #[test]
+    fn iterate_file() {
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let mut accounts = 0;
+        let mut details = 0;
+        for group in &file {
+            for account in group {
+                accounts += 1;
+                for _detail in account {
+                    details += 1;
+                }
+            }
+        }
+        assert_eq!((accounts, details), (5, 4));
+    }
+
+    static OPTIONAL_GROUP_FIELDS: &'static str = "\
+01,SENDER,RECEIVER,170601,1200,1,,,2/
+02,,SENDER,1,170601,,,/
+98,0,0,2/
+02,RECEIVER,SENDER,1,170601,0800,CAD,3/
+98,0,0,2/
+02,,,1,170601,9999,,4/
+98,0,0,2/
+99,0,3,8/
+";
+
+    #[test]
+    fn write_optional_group_fields() {
+        let file = data::File::process(OPTIONAL_GROUP_FIELDS.as_bytes()).unwrap();
+        let mut written = String::new();
+        write::file(&mut written, &file).unwrap();
+        assert_eq!(written, OPTIONAL_GROUP_FIELDS);
+    }
+
+    // Trailers are recomputed when writing, so they can differ from the ones a file was read with.
+    fn undeclared(mut file: data::File) -> data::File {
+        file.declared_control_total = None;
+        file.declared_group_count = None;
+        file.declared_record_count = None;
+        file.record_count = None;
+        for group in &mut file.groups {
+            group.declared_control_total = None;
+            group.declared_account_count = None;
+            group.declared_record_count = None;
+            group.record_count = None;
+            for account in &mut group.accounts {
+                account.declared_control_total = None;
+                account.declared_record_count = None;
+                account.record_count = None;
+            }
+        }
+        file
+    }
+
+    #[test]
+    fn write_round_trip() {
+        for input in &[SPEC_EXAMPLE, REFERENCE_NUMS, SLASHED_TEXT, EMPTY_GROUP_AND_ACCOUNT] {
+            let file = data::File::process(input.as_bytes()).unwrap();
+            let mut written = String::new();
+            write::file(&mut written, &file).unwrap();
+            assert_eq!(
+                undeclared(data::File::process(written.as_bytes()).unwrap()),
+                undeclared(file)
+            );
+        }
+    }
+
+    #[test]
+    fn validate() {
+        use data::{AccountInfo, IssueKind, Location, Severity};
+
+        let mut file = data::File::process(EMPTY_GROUP_AND_ACCOUNT.as_bytes()).unwrap();
+        let report = file.validate();
+        assert!(!report.has_errors());
+        assert_eq!(report.max_severity(), Some(Severity::Warning));
+        assert!(report.issues.iter().all(|i| i.kind == IssueKind::DefaultCurrency));
+
+        file.groups[1].accounts[0].customer_account.0 = "".to_string();
+        if let AccountInfo::Status { ref mut amount, .. } = file.groups[1].accounts[0].infos[1] {
+            *amount = Some(1500);
+        }
+        let report = file.validate();
+        assert!(report.has_errors());
+        let location = Location::Account {
+            group: 1,
+            account: 0,
+        };
+        let account_issues = report.issues
+            .iter()
+            .filter(|i| i.location == location)
+            .map(|i| i.kind.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            account_issues,
+            vec![
+                IssueKind::EmptyAccountNumber,
+                IssueKind::Unreconciled {
+                    expected: 1000,
+                    actual: 1500,
+                },
+            ]
+        );
+
+        let overflowing = EMPTY_GROUP_AND_ACCOUNT
+            .replace(
+                "03,12345,,010,1000,,,015,1000,,/",
+                "03,12345,,010,-9000000000000000000,,,015,0,,/\n16,495,1000000000000000000,,,,",
+            )
+            .replace("49,2000,2/", "49,-8000000000000000000,3/")
+            .replace("98,2000,1,4/", "98,-8000000000000000000,1,5/")
+            .replace("99,2000,2,8/", "99,-8000000000000000000,2,9/");
+        let file = data::File::process(overflowing.as_bytes()).unwrap();
+        assert!(file.validate().issues.iter().all(|i| match i.kind {
+            IssueKind::Unreconciled { .. } => false,
+            _ => true,
+        }));
+
+        // Trailer counts are only checked while processing when asked to.
+        let miscounted = SPEC_EXAMPLE.replace("99,345450000,4,31/", "99,345450000,4,999/");
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        assert!(file.validate().issues.iter().all(|i| i.severity != Severity::Error));
+        let mut file = data::File::process(miscounted.as_bytes()).unwrap();
+        file.groups.pop();
+        file.groups[0].accounts.pop();
+        let counts = file.validate()
+            .issues
+            .into_iter()
+            .filter(|i| i.severity == Severity::Error)
+            .map(|i| (i.location, i.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            counts,
+            vec![
+                (
+                    Location::File,
+                    IssueKind::GroupCount {
+                        expected: 4,
+                        actual: 3,
+                    },
+                ),
+                (
+                    Location::File,
+                    IssueKind::RecordCount {
+                        expected: 999,
+                        actual: 31,
+                    },
+                ),
+                (
+                    Location::Group { group: 0 },
+                    IssueKind::AccountCount {
+                        expected: 2,
+                        actual: 1,
+                    },
+                ),
+            ]
+        );
+
+        // Accounts and groups can leave their header and trailer out of the count.
+        let exclusive = SPEC_EXAMPLE.replace("49,9150000,4/", "49,9150000,2/");
+        let file = data::File::process(exclusive.as_bytes()).unwrap();
+        assert!(file.validate().issues.iter().all(|i| i.severity != Severity::Error));
+        let wrong = SPEC_EXAMPLE.replace("49,9150000,4/", "49,9150000,3/");
+        let file = data::File::process(wrong.as_bytes()).unwrap();
+        assert!(file.validate().issues.iter().any(|i| {
+            i.kind == IssueKind::RecordCount {
+                expected: 3,
+                actual: 4,
+            }
+        }));
+    }
+
+    static ITEM_COUNT_ONLY: &'static str = "\
+01,SENDER,RECEIVER,170601,1200,3,,,2/
+02,RECEIVER,SENDER,1,170601,,,2/
+03,12345,,100,,50,/
+49,0,2/
+98,0,1,4/
+99,0,1,6/
+";
+
+    #[test]
+    fn item_count_only_summary() {
+        use data::AccountInfo;
+
+        let file = data::File::process(ITEM_COUNT_ONLY.as_bytes()).unwrap();
+        match file.groups[0].accounts[0].infos[0] {
+            AccountInfo::Summary {
+                code,
+                amount,
+                item_count,
+                ref funds,
+            } => {
+                assert_eq!(u16::from(code), 100);
+                assert_eq!(amount, None);
+                assert_eq!(item_count, Some(50));
+                assert_eq!(*funds, None);
+            }
+            ref info => panic!("Expected summary, found {:?}", info),
+        }
+    }
+
+    #[test]
+    fn file_process_error_display() {
+        let bad_total = EMPTY_GROUP_AND_ACCOUNT.replace("99,2000,2,8/", "99,2001,2,8/");
+        let err = data::File::process_str(&bad_total).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid file: control total is 2001 but the amounts add up to 2000"
+        );
+        assert_eq!(bad_total.parse::<data::File>().unwrap_err().to_string(), err.to_string());
+        assert!(::std::error::Error::source(&err).is_some());
+
+        let bad_amount = EMPTY_GROUP_AND_ACCOUNT.replace("010,1000,", "010,10X0,");
+        let err = data::File::process_str(&bad_amount).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Couldn't parse field AccountIdent: Infos: account info 0: Amount isn't a number: \
+             invalid digit found in string"
+        );
+        assert!(::std::error::Error::source(&err).is_none());
+    }
+
+    #[test]
+    fn file_format_version() {
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(file.format_version, Some(2));
+
+        let no_version = EMPTY_GROUP_AND_ACCOUNT.replacen(",2/", ",/", 1);
+        let file = data::File::process_str(&no_version).unwrap();
+        assert_eq!(file.format_version, None);
+        let mut written = String::new();
+        write::file(&mut written, &file).unwrap();
+        assert!(written.starts_with("01,SENDER,RECEIVER,170601,1200,2,,,/\n"));
+    }
+
+    #[test]
+    fn prelude() {
+        use prelude::*;
+
+        let file: Result<File, FileProcessError> = File::process(SPEC_EXAMPLE.as_bytes());
+        let group: &Group = &file.unwrap().groups[0];
+        assert_eq!(group.currency_def(), Currency::USD);
+    }
+
+    #[test]
+    fn bom_and_leading_blank_lines() {
+        let expected = data::File::process(EMPTY_GROUP_AND_ACCOUNT.as_bytes()).unwrap();
+
+        let mut with_bom = b"\xEF\xBB\xBF".to_vec();
+        with_bom.extend_from_slice(EMPTY_GROUP_AND_ACCOUNT.as_bytes());
+        assert_eq!(data::File::process(&with_bom).unwrap(), expected);
+
+        let with_blank_lines = format!("\r\n\n{}", EMPTY_GROUP_AND_ACCOUNT);
+        assert_eq!(data::File::process_str(&with_blank_lines).unwrap(), expected);
+        assert_eq!(parse::records(with_blank_lines.as_bytes()).count(), 8);
+    }
+
+    static SIGNED_AMOUNTS: &'static str = "\
+01,SENDER,RECEIVER,170601,1200,4,,,2/
+02,RECEIVER,SENDER,1,170601,,,2/
+03,12345,,010,-12345,,,015,+100,,/
+49,-12245,2/
+98,-12245,1,4/
+99,-12245,1,6/
+";
+
+    #[test]
+    fn signed_amounts() {
+        use ast::convert::{AccountConvError, AccountInfoConvError, ConvertError};
+        use data::{AccountInfo, FileProcessError};
+
+        let file = data::File::process(SIGNED_AMOUNTS.as_bytes()).unwrap();
+        let amounts = file.groups[0].accounts[0]
+            .infos
+            .iter()
+            .map(|info| match *info {
+                AccountInfo::Status { amount, .. } => amount,
+                ref info => panic!("Expected status, found {:?}", info),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(amounts, vec![Some(-12345), Some(100)]);
+
+        let negative_summary = SIGNED_AMOUNTS.replace("015,+100,,", "100,-100,,");
+        match data::File::process_str(&negative_summary) {
+            Err(FileProcessError::Conversion(ConvertError::Account {
+                err: AccountConvError::AccountInfo(1, AccountInfoConvError::SummaryNegativeAmount),
+                ..
+            })) => {}
+            r => panic!("Expected negative summary amount error, found {:?}", r),
+        }
+    }
+
+    #[test]
+    fn visit_file() {
+        use data::{Account, FileVisitor, FileVisitorMut, TransactionDetail};
+
+        struct Counter {
+            accounts: usize,
+            transactions: usize,
+        }
+        impl FileVisitor for Counter {
+            fn visit_account(&mut self, _account: &Account) {
+                self.accounts += 1;
+            }
+            fn visit_transaction(&mut self, _transaction: &TransactionDetail) {
+                self.transactions += 1;
+            }
+        }
+
+        struct ClearText;
+        impl FileVisitorMut for ClearText {
+            fn visit_transaction(&mut self, transaction: &mut TransactionDetail) {
+                transaction.text = None;
+            }
+        }
+
+        let mut file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let mut counter = Counter {
+            accounts: 0,
+            transactions: 0,
+        };
+        file.visit(&mut counter);
+        assert_eq!(counter.accounts, 5);
+        assert_eq!(counter.transactions, 4);
+
+        file.visit_mut(&mut ClearText);
+        assert!(
+            file.groups
+                .iter()
+                .flat_map(|g| &g.accounts)
+                .flat_map(|a| &a.transaction_details)
+                .all(|td| td.text.is_none())
+        );
+    }
+
+    #[test]
+    fn redact() {
+        use data::RedactOptions;
+
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let redacted = file.redact(RedactOptions::default());
+        assert_eq!(redacted.groups[0].accounts[0].customer_account.0, "******6789");
+        let amounts = |f: &data::File| {
+            f.totals_by_currency()
+                .into_iter()
+                .map(|(code, m)| (code, m.amount()))
+                .collect::<std::collections::HashMap<_, _>>()
+        };
+        assert_eq!(amounts(&redacted), amounts(&file));
+        assert_eq!(redacted, file.redact(RedactOptions::default()));
+        let details = redacted
+            .groups
+            .iter()
+            .flat_map(|g| &g.accounts)
+            .flat_map(|a| &a.transaction_details)
+            .collect::<Vec<_>>();
+        assert_eq!(details.len(), 4);
+        assert!(details.iter().all(|td| {
+            td.text.is_none() && td.bank_ref_num.is_none() && td.customer_ref_num.is_none()
+        }));
+
+        let text_only = file.redact(RedactOptions {
+            account_numbers: false,
+            reference_nums: false,
+            ..RedactOptions::default()
+        });
+        assert_eq!(text_only.groups[0].accounts[0], {
+            let mut account = file.groups[0].accounts[0].clone();
+            for td in &mut account.transaction_details {
+                td.text = None;
+            }
+            account
+        });
+    }
+
+    #[test]
+    fn effective_funds_type() {
+        use data::FundsType;
+
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let details = &file.groups[1].accounts[0].transaction_details;
+        assert_eq!(details[1].funds, Some(FundsType::OneDayAvail));
+        assert_eq!(FundsType::effective(&details[1].funds), FundsType::OneDayAvail);
+        assert_eq!(FundsType::effective(&None), FundsType::Unknown);
+        assert_eq!(FundsType::effective(&Some(FundsType::Unknown)), FundsType::Unknown);
+    }
+
+    static ITEM_COUNT_MISMATCH: &'static str = "\
+01,SENDER,RECEIVER,170601,1200,5,,,2/
+02,RECEIVER,SENDER,1,170601,,USD,2/
+03,12345,,100,300,3,,400,0,0,/
+16,175,100,,,,/
+16,175,200,,,,/
+49,600,4/
+98,600,1,6/
+99,600,1,8/
+";
+
+    #[test]
+    fn validate_item_counts() {
+        use std::convert::TryFrom;
+        use data::{IssueKind, SummaryCode};
+
+        let file = data::File::process(ITEM_COUNT_MISMATCH.as_bytes()).unwrap();
+        let kinds = file.validate()
+            .issues
+            .into_iter()
+            .map(|i| i.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                IssueKind::ItemCount {
+                    code: SummaryCode::try_from(100).unwrap(),
+                    expected: 3,
+                    actual: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn normalized_file() {
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let normalized = file.normalized();
+        let codes = normalized.groups[1].accounts[0]
+            .transaction_details
+            .iter()
+            .map(|td| u16::from(td.code))
+            .collect::<Vec<_>>();
+        assert_eq!(codes, vec![195, 218]);
+
+        let mut shuffled = file.clone();
+        for group in &mut shuffled.groups {
+            group.accounts.reverse();
+            for account in &mut group.accounts {
+                account.transaction_details.reverse();
+            }
+        }
+        assert_ne!(shuffled, file);
+        assert_eq!(shuffled.normalized(), normalized);
+
+        let (mut written, mut shuffled_written) = (String::new(), String::new());
+        write::file(&mut written, &normalized).unwrap();
+        write::file(&mut shuffled_written, &shuffled.normalized()).unwrap();
+        assert_eq!(written, shuffled_written);
+    }
+
+    #[test]
+    fn blocked_records() {
+        let blocked = EMPTY_GROUP_AND_ACCOUNT
+            .lines()
+            .map(|line| format!("{:80}", line))
+            .collect::<String>();
+        assert_eq!(blocked.len(), 8 * 80);
+        assert!(data::File::process_str(&blocked).is_err());
+
+        let options = parse::ParseOptions::blocked(80);
+        assert_eq!(
+            data::File::process_with_options(blocked.as_bytes(), &options).unwrap(),
+            data::File::process(EMPTY_GROUP_AND_ACCOUNT.as_bytes()).unwrap()
+        );
+    }
+
+    #[test]
+    fn display_alternate() {
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let mut written = String::new();
+        write::file(&mut written, &file).unwrap();
+        assert_eq!(format!("{:#}", file), written);
+        assert!(format!("{}", file).starts_with("File: "));
+    }
+
+    #[test]
+    fn effective_currency() {
+        use penny::Currency;
+
+        let mut file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            file.groups[0].accounts[0].effective_currency(&file.groups[0]),
+            Currency::USD
+        );
+        file.groups[0].currency = Some(Currency::CAD);
+        file.groups[0].accounts[1].currency = Some(Currency::EUR);
+        let group = &file.groups[0];
+        assert_eq!(group.accounts[0].effective_currency(group), Currency::CAD);
+        assert_eq!(group.accounts[1].effective_currency(group), Currency::EUR);
+    }
+
+    #[test]
+    fn padded_numbers() {
+        use data::{AccountInfo, FundsType};
+
+        let padded = SIGNED_AMOUNTS
+            .replace("010,-12345,,", "010, 12345 ,,")
+            .replace("015,+100,,", "100,12345 , 2,S, 100,,")
+            .replace("-12245", "24690");
+        let file = data::File::process_str(&padded).unwrap();
+        let infos = &file.groups[0].accounts[0].infos;
+        match infos[0] {
+            AccountInfo::Status { amount, .. } => assert_eq!(amount, Some(12345)),
+            ref info => panic!("Expected status, found {:?}", info),
+        }
+        match infos[1] {
+            AccountInfo::Summary {
+                amount,
+                item_count,
+                ref funds,
+                ..
+            } => {
+                assert_eq!(amount, Some(12345));
+                assert_eq!(item_count, Some(2));
+                assert_eq!(
+                    *funds,
+                    Some(FundsType::DistributedAvailS {
+                        immediate: Some(100),
+                        one_day: None,
+                        more_than_one_day: None,
+                    })
+                );
+            }
+            ref info => panic!("Expected summary, found {:?}", info),
+        }
+
+        let malformed = padded.replace(" 12345 ", "12 345");
+        match data::File::process_str(&malformed) {
+            Err(data::FileProcessError::FieldParse(_)) => {}
+            r => panic!("Expected field parse error, found {:?}", r),
+        }
+    }
+
+    #[test]
+    fn transaction_contexts() {
+        use chrono::NaiveDate;
+
+        let mut file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let dates = file.transactions()
+            .map(|t| (t.as_of_date(), u16::from(t.transaction.code)))
+            .collect::<Vec<_>>();
+        let as_of = NaiveDate::from_ymd(2004, 6, 20);
+        assert_eq!(
+            dates,
+            vec![(as_of, 115), (as_of, 115), (as_of, 218), (as_of, 195)]
+        );
+        assert_eq!(file.latest_as_of(), Some(as_of));
+
+        let later = NaiveDate::from_ymd(2004, 6, 21);
+        file.groups[1].as_of = later.into();
+        assert_eq!(file.latest_as_of(), Some(later));
+        file.groups.clear();
+        assert_eq!(file.latest_as_of(), None);
+        assert_eq!(file.transactions().count(), 0);
+    }
+
+    #[test]
+    fn build_and_write_as_of_modifiers() {
+        use chrono::NaiveDate;
+        use data::{AsOfDateModifier, FileBuilder, FileIdent, GroupBuilder, GroupStatus, Party};
+
+        let date = NaiveDate::from_ymd(2017, 6, 1);
+        let group = |receiver: &str| {
+            GroupBuilder::new(GroupStatus::Update, date.into())
+                .ultimate_receiver(Party(receiver.to_string()))
+                .originator(Party("SENDER".to_string()))
+        };
+        let file = FileBuilder::new(
+            Party("SENDER".to_string()),
+            Party("RECEIVER".to_string()),
+            date.and_hms(12, 0, 0).into(),
+            FileIdent(1),
+        ).as_of_modifier(AsOfDateModifier::FinalPrevious)
+            .group(group("A").build())
+            .group(group("B").as_of_modifier(AsOfDateModifier::InterimSame).build())
+            .build();
+
+        let mut written = String::new();
+        write::file(&mut written, &file).unwrap();
+        assert_eq!(
+            written,
+            "\
+01,SENDER,RECEIVER,170601,1200,1,,,2/
+02,A,SENDER,1,170601,,,2/
+98,0,0,2/
+02,B,SENDER,1,170601,,,3/
+98,0,0,2/
+99,0,2,6/
+"
+        );
+        assert_eq!(undeclared(data::File::process_str(&written).unwrap()), file);
+    }
+
+    #[test]
+    fn flat_transactions() {
+        use chrono::NaiveDate;
+        use penny::Currency;
+        use data::FlatTransaction;
+
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let rows = file.to_rows();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(
+            rows[2],
+            FlatTransaction {
+                file_ident: 1,
+                group_originator: Some("122099999".to_string()),
+                account_number: "4589761203".to_string(),
+                as_of_date: NaiveDate::from_ymd(2004, 6, 20),
+                code: 218,
+                amount: Some(20000000),
+                currency: Currency::USD,
+                bank_ref: Some("SP4738".to_string()),
+                customer_ref: Some("YRC065321".to_string()),
+                text: Some("PROCEEDS OF LETTER OF CREDIT FROM THE ARAMCO OIL CO".to_string()),
+            }
+        );
+    }
+
+    static DISTRIBUTED_AVAIL: &'static str = "\
+01,SENDER,RECEIVER,170601,1200,6,,,2/
+02,RECEIVER,SENDER,1,170601,,USD,2/
+03,12345,,100,300,,D,2,0,100,1,200,400,300,,/
+16,175,300,D,2,0,100,1,200,,,/
+16,475,300,,,,/
+49,1200,4/
+98,1200,1,6/
+99,1200,1,8/
+";
+
+    #[test]
+    fn distributed_avail_count() {
+        use ast::convert::{ConvertError, FundsTypeConvError, TransactionDetailConvError};
+        use data::{AccountInfo, DistributedAvailDistribution, FileProcessError, FundsType};
+
+        let file = data::File::process(DISTRIBUTED_AVAIL.as_bytes()).unwrap();
+        let account = &file.groups[0].accounts[0];
+        let dists = FundsType::DistributedAvailD(vec![
+            DistributedAvailDistribution { days: 0, amount: 100 },
+            DistributedAvailDistribution { days: 1, amount: 200 },
+        ]);
+        assert_eq!(account.infos.len(), 2);
+        match account.infos[0] {
+            AccountInfo::Summary { ref funds, .. } => assert_eq!(funds.as_ref(), Some(&dists)),
+            ref info => panic!("Expected summary, found {:?}", info),
+        }
+        assert_eq!(account.transaction_details[0].funds.as_ref(), Some(&dists));
+
+        let miscounted = DISTRIBUTED_AVAIL.replace("16,175,300,D,2,", "16,175,300,D,3,");
+        match data::File::process_str(&miscounted) {
+            Err(FileProcessError::Conversion(ConvertError::TransactionDetail {
+                err: TransactionDetailConvError::Funds(
+                    FundsTypeConvError::DistributedAvailDNum {
+                        expected: 3,
+                        actual: 2,
+                    },
+                ),
+                ..
+            })) => {}
+            r => panic!("Expected distribution count error, found {:?}", r),
+        }
+    }
+
+    #[test]
+    fn format_money() {
+        use penny::{Currency, Money};
+        use data::format_money;
+
+        assert_eq!(format_money(&Money::new(123456, Currency::USD)), "$1,234.56");
+        assert_eq!(format_money(&Money::new(1234, Currency::JPY)), "¥1,234");
+        assert_eq!(format_money(&Money::new(123456, Currency::EUR)), "€1.234,56");
+        assert_eq!(format_money(&Money::new(-5, Currency::USD)), "-$0.05");
+        assert_eq!(format_money(&Money::new(123456789, Currency::CAD)), "CAD 1,234,567.89");
+        assert_eq!(
+            format_money(&Money::new(i64::min_value(), Currency::USD)),
+            "-$92,233,720,368,547,758.08"
+        );
+    }
+
+    #[test]
+    fn group_fragments() {
+        use ast::convert::{ConvertError, FragmentConvError};
+        use data::FileProcessError;
+
+        let file = data::File::process(EMPTY_GROUP_AND_ACCOUNT.as_bytes()).unwrap();
+        let fragment = EMPTY_GROUP_AND_ACCOUNT
+            .lines()
+            .filter(|l| !l.starts_with("01") && !l.starts_with("99"))
+            .map(|l| format!("{}\n", l))
+            .collect::<String>();
+        assert_eq!(data::Group::process_fragments(fragment.as_bytes()).unwrap(), file.groups);
+
+        let single = fragment.lines().skip(2).map(|l| format!("{}\n", l)).collect::<String>();
+        assert_eq!(data::Group::process_fragment(single.as_bytes()).unwrap(), file.groups[1]);
+        match data::Group::process_fragment(fragment.as_bytes()) {
+            Err(FileProcessError::Conversion(ConvertError::Fragment(
+                FragmentConvError::GroupsNum { expected: 1, actual: 2 },
+            ))) => {}
+            other => panic!("{:?}", other),
+        }
+        assert!(data::Group::process_fragment(b"02,RECEIVER,SENDER,1,170601,,,2/\n").is_err());
+        assert!(data::File::process(fragment.as_bytes()).is_err());
+
+        let trailed = format!("{}99,2000,2,8/\n", fragment);
+        match data::Group::process_fragments(trailed.as_bytes()) {
+            Err(FileProcessError::Conversion(ConvertError::Fragment(
+                FragmentConvError::FileTrailer,
+            ))) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn deprecated_code_warnings() {
+        use data::{CodeDef, CodeTable, Direction, ProcessWarning};
+        use parse::ParseOptions;
+
+        let (_, warnings) = data::File::process_with_warnings(SPEC_EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(warnings, vec![]);
+
+        // Customized codes are the bank's to define.
+        let customized = DISTRIBUTED_AVAIL
+            .replace("400,300,,/", "960,300,,/")
+            .replace("16,475,", "16,930,");
+        let (file, warnings) = data::File::process_with_warnings(customized.as_bytes()).unwrap();
+        assert_eq!(file, data::File::process_str(&customized).unwrap());
+        assert_eq!(warnings, vec![]);
+
+        let unlisted = SLASHED_TEXT.replace("16,195,100,,,,/", "16,899,100,,,,/");
+        assert!(data::File::process_with_warnings(unlisted.as_bytes()).is_err());
+        let options = ParseOptions {
+            code_table: CodeTable::builtin().with_override(899, CodeDef {
+                description: "Wire Fee".to_owned(),
+                direction: Some(Direction::Debit),
+                category: None,
+            }),
+            ..ParseOptions::default()
+        };
+        let (_, warnings) =
+            data::File::process_with_options_and_warnings(unlisted.as_bytes(), &options).unwrap();
+        assert_eq!(
+            warnings,
+            vec![
+                ProcessWarning::DeprecatedCode {
+                    code: 899,
+                    record_number: 6,
+                },
+            ]
+        );
+        assert!(data::is_deprecated_code(800));
+        assert!(!data::is_deprecated_code(195));
+        assert!(!data::is_deprecated_code(999));
+    }
+
+    #[test]
+    fn transaction_text() {
+        let mut file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let detail = &mut file.groups[1].accounts[0].transaction_details[0];
+        detail.text = Some(vec![
+            " PROCEEDS ".to_string(),
+            "   ".to_string(),
+            "OF CREDIT".to_string(),
+        ]);
+        assert_eq!(
+            detail.text_joined("|"),
+            Some(" PROCEEDS |   |OF CREDIT".to_string())
+        );
+        assert_eq!(detail.text_trimmed(), Some(vec!["PROCEEDS", "OF CREDIT"]));
+        detail.text = None;
+        assert_eq!(detail.text_joined(" "), None);
+        assert_eq!(detail.text_trimmed(), None);
+    }
+
+    #[test]
+    fn group_header_continuations() {
+        use data::{AsOfDateModifier, FileProcessError};
+
+        // A continuation within the group header's fields belongs to the group.
+        let split = EMPTY_GROUP_AND_ACCOUNT
+            .replacen(
+                "02,RECEIVER,SENDER,1,170601,,,2/",
+                "02,RECEIVER,SENDER,1,170601/\n88,,,2/",
+                1,
+            )
+            .replace("99,2000,2,8/", "99,2000,2,9/");
+        let file = data::File::process_str(&split).unwrap();
+        assert_eq!(file.groups[0].as_of_date_mod, Some(AsOfDateModifier::FinalPrevious));
+        assert!(file.groups[0].accounts.is_empty());
+
+        // After a complete group header, there's nothing for a continuation to continue.
+        let orphan = EMPTY_GROUP_AND_ACCOUNT.replacen(
+            "02,RECEIVER,SENDER,1,170601,,,2/",
+            "02,RECEIVER,SENDER,1,170601,,,2/\n88,100,500,,/",
+            1,
+        );
+        match data::File::process_str(&orphan) {
+            Err(FileProcessError::OrphanContinuation { record_number: 3 }) => {}
+            r => panic!("Expected orphan continuation error, found {:?}", r),
+        }
+
+        let unparsed = EMPTY_GROUP_AND_ACCOUNT.replacen("98,", "98;", 1);
+        let offset = unparsed.find("98;").unwrap();
+        match data::File::process_str(&unparsed) {
+            Err(FileProcessError::UnparsedRecord {
+                record_number: 3,
+                offset: o,
+            }) => assert_eq!(o, offset),
+            r => panic!("Expected unparsed record error, found {:?}", r),
+        }
+        assert_eq!(
+            data::File::process_str(&unparsed).unwrap_err().to_string(),
+            format!("Couldn't parse record 3 at byte {}", offset)
+        );
+    }
+
+    #[test]
+    fn mixed_record_ends() {
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let mixed = SPEC_EXAMPLE
+            .lines()
+            .enumerate()
+            .map(|(i, line)| if i % 2 == 0 {
+                format!("{}\n", line.trim_end_matches('/'))
+            } else {
+                format!("{}\n", line)
+            })
+            .collect::<String>();
+        assert_ne!(mixed, SPEC_EXAMPLE);
+        assert_eq!(data::File::process_str(&mixed).unwrap(), file);
+
+        let no_slashes = SPEC_EXAMPLE.replace("/\n", "\n");
+        assert_eq!(data::File::process_str(&no_slashes).unwrap(), file);
+    }
+
+    #[test]
+    fn merge_files() {
+        use data::{MergeError, Party};
+
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let mut first = file.clone();
+        let mut second = file.clone();
+        first.groups.truncate(2);
+        first.groups[1].accounts.clear();
+        second.groups.remove(0);
+        let merged = data::File::merge(vec![first.clone(), second.clone()]).unwrap();
+        assert_eq!(merged.declared_control_total, None);
+        assert_eq!(merged.groups[1].declared_account_count, None);
+        assert_eq!(merged.groups[2], file.groups[2]);
+        assert_eq!(undeclared(merged.clone()), undeclared(file.clone()));
+        let mut written = String::new();
+        write::file(&mut written, &merged).unwrap();
+        assert_eq!(
+            undeclared(data::File::process_str(&written).unwrap()),
+            undeclared(file.clone())
+        );
+
+        assert_eq!(data::File::merge(vec![]), Err(MergeError::Empty));
+        second.receiver = Party("OTHER".to_string());
+        assert_eq!(
+            data::File::merge(vec![first.clone(), second]),
+            Err(MergeError::Receiver { file: 1 })
+        );
+        let mut other_currency = file.clone();
+        other_currency.groups.truncate(1);
+        other_currency.groups[0].currency = Some(::penny::Currency::CAD);
+        let merged = data::File::merge(vec![first.clone(), other_currency]).unwrap();
+        assert_eq!(merged.groups.len(), first.groups.len() + 1);
+    }
+
+    #[test]
+    fn status_with_funds() {
+        use data::{AccountInfo, FundsType};
+
+        let with_funds = SIGNED_AMOUNTS.replace("010,-12345,,", "010,-12345,,S,100,200,300");
+        let file = data::File::process_str(&with_funds).unwrap();
+        match file.groups[0].accounts[0].infos[0] {
+            AccountInfo::Status {
+                amount,
+                ref funds,
+                ..
+            } => {
+                assert_eq!(amount, Some(-12345));
+                assert_eq!(
+                    *funds,
+                    Some(FundsType::DistributedAvailS {
+                        immediate: Some(100),
+                        one_day: Some(200),
+                        more_than_one_day: Some(300),
+                    })
+                );
+            }
+            ref info => panic!("Expected status, found {:?}", info),
+        }
+    }
+
+    #[test]
+    fn pretty_printer() {
+        use data::PrettyPrinter;
+
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let out = PrettyPrinter::new().render(&file);
+        assert!(out.starts_with("File: "));
+        assert!(out.ends_with("}\n"));
+        assert!(out.contains("\n    Group "));
+        assert!(!out.contains(": none,"));
+
+        let printer = PrettyPrinter {
+            indent: 2,
+            show_empty: true,
+            max_text_len: Some(5),
+            ..PrettyPrinter::default()
+        };
+        let out = printer.render(&file);
+        assert!(out.contains("\n  Group "));
+        assert!(out.contains("\n    Account "));
+        assert!(out.contains("Customer: none,\n"));
+        let text_lines = out.lines()
+            .filter(|l| l.trim().starts_with("Text: "))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            text_lines,
+            vec![
+                "          Text: none,",
+                "          Text: \"LOCK ...\",",
+                "          Text: \"PROCE...\",",
+                "          Text: none,",
+            ]
+        );
+    }
+
+    #[test]
+    fn availability_schedule() {
+        use chrono::NaiveDate;
+        use data::{AvailabilityDays, FundsType};
+
+        let file = data::File::process(DISTRIBUTED_AVAIL.as_bytes()).unwrap();
+        let details = &file.groups[0].accounts[0].transaction_details;
+        let thursday = NaiveDate::from_ymd(2017, 6, 1);
+        let friday = NaiveDate::from_ymd(2017, 6, 2);
+        assert_eq!(
+            details[0].availability_schedule(thursday),
+            vec![(thursday, 100), (friday, 200)]
+        );
+        assert_eq!(
+            details[0].availability_schedule_in(friday, AvailabilityDays::Business),
+            vec![(friday, 100), (NaiveDate::from_ymd(2017, 6, 5), 200)]
+        );
+        assert_eq!(details[1].availability_schedule(thursday), vec![]);
+
+        let mut detail = details[1].clone();
+        detail.funds = Some(FundsType::TwoOrMoreDaysAvail);
+        assert_eq!(
+            detail.availability_schedule_in(friday, AvailabilityDays::Business),
+            vec![(NaiveDate::from_ymd(2017, 6, 6), 300)]
+        );
+        detail.funds = Some(FundsType::ValueDated(NaiveDate::from_ymd(2017, 6, 9).into()));
+        assert_eq!(
+            detail.availability_schedule(thursday),
+            vec![(NaiveDate::from_ymd(2017, 6, 9), 300)]
+        );
+        detail.amount = None;
+        assert_eq!(detail.availability_schedule(thursday), vec![]);
+    }
+
+    #[cfg(feature = "serde-serialize")]
+    #[test]
+    fn compact_round_trip() {
+        use std::convert::TryFrom;
+        use data::{CompactError, CompactFile, CompactFunds, CompactInfo};
+
+        for input in &[SPEC_EXAMPLE, DISTRIBUTED_AVAIL, SIGNED_AMOUNTS, SLASHED_TEXT] {
+            let file = data::File::process(input.as_bytes()).unwrap();
+            let json = serde_json::to_string(&CompactFile::from(&file)).unwrap();
+            let compact: CompactFile = serde_json::from_str(&json).unwrap();
+            assert_eq!(data::File::try_from(compact).unwrap(), file);
+        }
+
+        let file = data::File::process(DISTRIBUTED_AVAIL.as_bytes()).unwrap();
+        let mut compact = CompactFile::from(&file);
+        assert_eq!(compact.creation, (20170601, Some(1200)));
+        assert_eq!(compact.groups[0].as_of, (20170601, None));
+        let account = &compact.groups[0].accounts[0];
+        assert_eq!(
+            account.infos[0],
+            CompactInfo::Summary(
+                100,
+                Some(300),
+                None,
+                Some(CompactFunds::D(vec![(0, 100), (1, 200)]))
+            )
+        );
+        assert_eq!(account.transaction_details[1].0, 475);
+
+        compact.groups[0].accounts[0].transaction_details[1].0 = 1000;
+        assert_eq!(data::File::try_from(compact.clone()), Err(CompactError::TypeCode(1000)));
+        compact.creation = (20170231, Some(1200));
+        assert_eq!(data::File::try_from(compact), Err(CompactError::Date(20170231)));
+    }
+
+    #[test]
+    fn orphan_continuation() {
+        use data::FileProcessError;
+
+        let first_body = EMPTY_GROUP_AND_ACCOUNT.replacen("\n", "\n88,100,500,,/\n", 1);
+        match data::File::process_str(&first_body) {
+            Err(FileProcessError::OrphanContinuation { record_number: 2 }) => {}
+            r => panic!("Expected orphan continuation error, found {:?}", r),
+        }
+
+        let first = format!("88,100,500,,/\n{}", EMPTY_GROUP_AND_ACCOUNT);
+        let err = data::File::process_str(&first).unwrap_err();
+        assert_eq!(err.to_string(), "Record 1 continues nothing");
+    }
+
+    #[test]
+    fn major_unit_amounts() {
+        use data::{AccountInfo, DistributedAvailDistribution, FundsType};
+        use parse::{AmountScale, ParseOptions};
+
+        let options = ParseOptions {
+            amount_scale: AmountScale::MajorUnits,
+            ..ParseOptions::default()
+        };
+        let minor = data::File::process(DISTRIBUTED_AVAIL.as_bytes()).unwrap();
+        let major = data::File::process_with_options(DISTRIBUTED_AVAIL.as_bytes(), &options)
+            .unwrap();
+        let account = &major.groups[0].accounts[0];
+        match account.infos[0] {
+            AccountInfo::Summary { amount, .. } => assert_eq!(amount, Some(30000)),
+            ref info => panic!("Expected summary, found {:?}", info),
+        }
+        assert_eq!(account.transaction_details[0].amount, Some(30000));
+        assert_eq!(
+            account.transaction_details[0].funds,
+            Some(FundsType::DistributedAvailD(vec![
+                DistributedAvailDistribution { days: 0, amount: 10000 },
+                DistributedAvailDistribution { days: 1, amount: 20000 },
+            ]))
+        );
+        assert_eq!(
+            data::File::process_with_options(DISTRIBUTED_AVAIL.as_bytes(), &ParseOptions::default())
+                .unwrap(),
+            minor
+        );
+
+        assert_eq!(account.declared_control_total, Some(120000));
+        assert_eq!(major.groups[0].declared_control_total, Some(120000));
+        assert_eq!(major.declared_control_total, Some(120000));
+
+        let yen = DISTRIBUTED_AVAIL.replacen(",USD,", ",JPY,", 1);
+        let major = data::File::process_with_options(yen.as_bytes(), &options).unwrap();
+        assert_eq!(major.groups[0].accounts[0].transaction_details[1].amount, Some(300));
+        assert_eq!(major.declared_control_total, Some(1200));
+
+        // No one factor fits the group and file totals of dollars and yen together.
+        let mixed = DISTRIBUTED_AVAIL
+            .replace("98,1200,1,6/", "03,67890,JPY,010,300,,/\n49,300,2/\n98,1500,2,8/")
+            .replace("99,1200,1,8/", "99,1500,1,10/");
+        let major = data::File::process_with_options(mixed.as_bytes(), &options).unwrap();
+        let totals = |g: &data::Group| {
+            g.accounts.iter().map(|a| a.declared_control_total).collect::<Vec<_>>()
+        };
+        assert_eq!(totals(&major.groups[0]), vec![Some(120000), Some(300)]);
+        assert_eq!(major.groups[0].declared_control_total, Some(1500));
+        assert_eq!(major.declared_control_total, Some(1500));
+    }
+
+    #[test]
+    fn account_balances() {
+        use penny::Currency;
+
+        let file = data::File::process(EMPTY_GROUP_AND_ACCOUNT.as_bytes()).unwrap();
+        let account = &file.groups[1].accounts[0];
+        let amount = |m: Option<penny::Money>| m.map(|m| (m.amount(), m.currency()));
+        assert_eq!(
+            amount(account.opening_ledger(Currency::USD)),
+            Some((1000, Currency::USD))
+        );
+        assert_eq!(
+            amount(account.closing_ledger(Currency::EUR)),
+            Some((1000, Currency::EUR))
+        );
+        assert_eq!(amount(account.opening_available(Currency::USD)), None);
+        assert_eq!(amount(account.closing_available(Currency::USD)), None);
+    }
+
+    #[cfg(feature = "serde-serialize")]
+    #[test]
+    fn penny_serde() {
+        use penny::Currency;
+        use serde::de::IntoDeserializer;
+        use serde::de::value::Error;
+        use data::penny_serde::currency;
+
+        let eur: Result<_, Error> = currency::deserialize("EUR".into_deserializer());
+        assert_eq!(eur, Ok(Currency::EUR));
+        let unknown: Result<_, Error> = currency::deserialize("XYZ".into_deserializer());
+        assert!(unknown.is_err());
+    }
+
+    #[test]
+    fn year_pivot() {
+        use chrono::NaiveDate;
+        use parse::ParseOptions;
+
+        let created = |date: &str, options: &ParseOptions| {
+            let file = EMPTY_GROUP_AND_ACCOUNT.replacen("170601", date, 1);
+            data::File::process_with_options(file.as_bytes(), options)
+                .map(|file| file.creation.date())
+                .map_err(|e| e.to_string())
+        };
+        let default = ParseOptions::default();
+        assert_eq!(created("691231", &default), Ok(NaiveDate::from_ymd(2069, 12, 31)));
+        assert_eq!(created("700101", &default), Ok(NaiveDate::from_ymd(1970, 1, 1)));
+
+        let options = ParseOptions {
+            year_pivot: 50,
+            ..ParseOptions::default()
+        };
+        assert_eq!(created("691231", &options), Ok(NaiveDate::from_ymd(1969, 12, 31)));
+        assert_eq!(created("491231", &options), Ok(NaiveDate::from_ymd(2049, 12, 31)));
+
+        let all_1900s = ParseOptions {
+            year_pivot: 0,
+            ..ParseOptions::default()
+        };
+        let file = data::File::process_with_options(SPEC_EXAMPLE.as_bytes(), &all_1900s).unwrap();
+        assert_eq!(file.groups[0].as_of.clone().date(), NaiveDate::from_ymd(1904, 6, 20));
+        assert_eq!(
+            created("000229", &all_1900s),
+            Err("Date 2000-02-29 doesn't exist with year pivot 0".to_owned())
+        );
+    }
+
+    #[test]
+    fn parse_callbacks() {
+        use data::{Account, BaiHandler, File, Group, TransactionDetail};
+
+        #[derive(Default)]
+        struct Events(Vec<String>);
+        impl BaiHandler for Events {
+            fn on_file_header(&mut self, file: &File) {
+                self.0.push(format!("01 {}", file.groups.len()));
+            }
+            fn on_group_header(&mut self, group: &Group) {
+                self.0.push(format!("02 {}", group.accounts.len()));
+            }
+            fn on_account(&mut self, account: &Account) {
+                self.0.push(format!("03 {}", account.customer_account.0));
+            }
+            fn on_transaction(&mut self, transaction: &TransactionDetail) {
+                self.0.push(format!("16 {}", u16::from(transaction.code)));
+            }
+            fn on_account_trailer(&mut self, account: &Account) {
+                self.0.push(format!(
+                    "49 {} {:?}",
+                    account.customer_account.0,
+                    account.declared_control_total
+                ));
+            }
+            fn on_group_trailer(&mut self, group: &Group) {
+                self.0.push(format!("98 {:?}", group.declared_control_total));
+            }
+            fn on_file_trailer(&mut self, file: &File) {
+                self.0.push(format!("99 {}", file.groups.len()));
+            }
+        }
+
+        let mut events = Events::default();
+        data::parse_callbacks(SPEC_EXAMPLE.as_bytes(), &mut events).unwrap();
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let mut expected = vec!["01 0".to_string()];
+        for group in &file.groups {
+            expected.push("02 0".to_string());
+            for account in &group.accounts {
+                expected.push(format!("03 {}", account.customer_account.0));
+                for td in &account.transaction_details {
+                    expected.push(format!("16 {}", u16::from(td.code)));
+                }
+                expected.push(format!(
+                    "49 {} {:?}",
+                    account.customer_account.0,
+                    account.declared_control_total
+                ));
+            }
+            expected.push(format!("98 {:?}", group.declared_control_total));
+        }
+        expected.push("99 0".to_string());
+        assert_eq!(events.0, expected);
+        assert_eq!(events.0.len(), 24);
+
+        let bad_total = EMPTY_GROUP_AND_ACCOUNT.replace("99,2000,2,8/", "99,2001,2,8/");
+        let mut events = Events::default();
+        assert!(data::parse_callbacks(bad_total.as_bytes(), &mut events).is_err());
+        assert_eq!(events.0.len(), 7);
+
+        // Records are handled as they're read, before a bad one further on is reached.
+        let unparsed = EMPTY_GROUP_AND_ACCOUNT.replace("99,2000,2,8/", "XX/");
+        let mut events = Events::default();
+        match data::parse_callbacks(unparsed.as_bytes(), &mut events) {
+            Err(data::FileProcessError::UnparsedRecord { record_number: 8, .. }) => {}
+            r => panic!("Expected unparsed record, found {:?}", r),
+        }
+        assert_eq!(events.0.len(), 7);
+    }
+
+    #[test]
+    fn funds_before_amount_dialect() {
+        use parse::{Dialect, ParseOptions};
+
+        let vendor = DISTRIBUTED_AVAIL
+            .replace("16,175,300,D,2,0,100,1,200,,,/", "16,175,D,2,0,100,1,200,300,,,/")
+            .replace("16,475,300,,,,/", "16,475,,300,,,/");
+        let options = ParseOptions {
+            dialect: Dialect::FundsBeforeAmount,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            data::File::process_with_options(vendor.as_bytes(), &options).unwrap(),
+            data::File::process(DISTRIBUTED_AVAIL.as_bytes()).unwrap()
+        );
+        assert!(data::File::process(vendor.as_bytes()).is_err());
+
+
+        let vendor = SPEC_EXAMPLE
+            .replace("16,115,450000,S,100000,200000,150000,", "16,115,S,100000,200000,150000,450000,")
+            .replace("16,115,500000,S,,200000,300000,", "16,115,S,,200000,300000,500000,")
+            .replace("16,218,20000000,V,040622,,", "16,218,V,040622,,20000000,")
+            .replace("16,195,10000000,1,", "16,195,1,10000000,");
+        assert_eq!(
+            data::File::process_with_options(vendor.as_bytes(), &options).unwrap(),
+            data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap()
+        );
+
+        // The fields can run on to a continuation, and raw records are kept as the bank sent them.
+        let recount = |input: String| {
+            input
+                .replace("49,1200,4/", "49,1200,5/")
+                .replace("98,1200,1,6/", "98,1200,1,7/")
+                .replace("99,1200,1,8/", "99,1200,1,9/")
+        };
+        let standard =
+            recount(DISTRIBUTED_AVAIL.replace("16,475,300,,,,/", "16,475,300\n88,,,,TEXT"));
+        let vendor = recount(
+            DISTRIBUTED_AVAIL
+                .replace("16,175,300,D,2,0,100,1,200,", "16,175,D,2,0,100,1,200,300,")
+                .replace("16,475,300,,,,/", "16,475,,300\n88,,,TEXT"),
+        );
+        let options = ParseOptions {
+            keep_raw_records: true,
+            ..options
+        };
+        let file = data::File::process_with_options(vendor.as_bytes(), &options).unwrap();
+        let detail = &file.groups[0].accounts[0].transaction_details[1];
+        assert_eq!(detail.raw_record, Some("16,475,,300\n88,,,TEXT".to_string()));
+        let mut unraw = file.clone();
+        for detail in &mut unraw.groups[0].accounts[0].transaction_details {
+            detail.raw_record = None;
+        }
+        assert_eq!(unraw, data::File::process(standard.as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn log_line() {
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            file.log_line(),
+            "file=1 sender=122099999 receiver=123456789 groups=4 transactions=4 total=USD:30950000"
+        );
+
+        let mut file = data::File::process(EMPTY_GROUP_AND_ACCOUNT.as_bytes()).unwrap();
+        file.groups.clear();
+        assert_eq!(
+            file.log_line(),
+            "file=2 sender=SENDER receiver=RECEIVER groups=0 transactions=0 total="
+        );
+
+        file.sender.0 = "S".repeat(40);
+        file.receiver.0 = "é".repeat(40);
+        assert_eq!(
+            file.log_line(),
+            format!(
+                "file=2 sender={} receiver={} groups=0 transactions=0 total=",
+                "S".repeat(32),
+                "é".repeat(32)
+            )
+        );
+    }
+
+    #[test]
+    fn declared_trailer_values() {
+        let file = data::File::process(EMPTY_GROUP_AND_ACCOUNT.as_bytes()).unwrap();
+        assert_eq!(file.declared_control_total, Some(2000));
+        assert_eq!(file.declared_group_count, Some(2));
+        assert_eq!(file.declared_record_count, Some(8));
+        let group = &file.groups[1];
+        assert_eq!(group.declared_control_total, Some(2000));
+        assert_eq!(group.declared_account_count, Some(1));
+        assert_eq!(group.declared_record_count, Some(4));
+        assert_eq!(group.accounts[0].declared_control_total, Some(2000));
+        assert_eq!(group.accounts[0].declared_record_count, Some(2));
+        assert_eq!(file.groups[0].declared_account_count, Some(0));
+    }
+
+    #[test]
+    fn blank_numeric_fields() {
+        use data::{AccountInfo, FundsType};
+
+        let cases = [("", None), ("   ", None), ("0", Some(0)), (" 0 ", Some(0))];
+        for &(raw, expected) in &cases {
+            let input = SIGNED_AMOUNTS
+                .replace("010,-12345,,", &format!("010,{0},,S,{0},{0},{0}", raw))
+                .replace("015,+100,,", &format!("100,{0},{0},", raw))
+                .replace("-12245", "0");
+            let file = data::File::process_str(&input).unwrap();
+            let infos = &file.groups[0].accounts[0].infos;
+            match infos[0] {
+                AccountInfo::Status {
+                    amount,
+                    ref funds,
+                    ..
+                } => {
+                    assert_eq!(amount, expected, "status amount {:?}", raw);
+                    assert_eq!(
+                        *funds,
+                        Some(FundsType::DistributedAvailS {
+                            immediate: expected,
+                            one_day: expected,
+                            more_than_one_day: expected,
+                        }),
+                        "funds {:?}",
+                        raw
+                    );
+                }
+                ref info => panic!("Expected status, found {:?}", info),
+            }
+            match infos[1] {
+                AccountInfo::Summary {
+                    amount, item_count, ..
+                } => {
+                    assert_eq!(amount, expected.map(|a| a as u64), "summary amount {:?}", raw);
+                    assert_eq!(item_count, expected.map(|c| c as u32), "item count {:?}", raw);
+                }
+                ref info => panic!("Expected summary, found {:?}", info),
+            }
+        }
+    }
+
+    #[test]
+    fn raw_records() {
+        use data::RedactOptions;
+
+        let continued = SLASHED_TEXT
+            .replace("16,195,500,,,,WIRE", "16,195,500\r\n88,,,,WIRE")
+            .replace("49,1850,5/", "49,1850,6/")
+            .replace("98,1850,1,7/", "98,1850,1,8/")
+            .replace("99,1850,1,9/", "99,1850,1,10/");
+        let options = parse::ParseOptions {
+            keep_raw_records: true,
+            ..parse::ParseOptions::default()
+        };
+        let file = data::File::process_with_options(continued.as_bytes(), &options).unwrap();
+        let raw_records = |file: &data::File| {
+            file.transactions()
+                .map(|t| t.transaction.raw_record.clone())
+                .collect::<Vec<_>>()
+        };
+        let expected = vec![
+            Some("16,195,500\r\n88,,,,WIRE IN/OUT NET SETTLEMENT".to_string()),
+            Some("16,195,250,,,,/FX/ NET SETTLEMENT/".to_string()),
+            Some("16,195,100,,,,/".to_string()),
+        ];
+        assert_eq!(raw_records(&file), expected);
+
+        // Reordering keeps each raw record with its detail.
+        let mut reordered = file.clone();
+        reordered.groups[0].accounts[0].transaction_details.reverse();
+        let normalized = reordered.normalized();
+        for t in normalized.transactions() {
+            let raw = t.transaction.raw_record.as_ref().unwrap();
+            let amount = t.transaction.amount.unwrap().to_string();
+            assert!(raw.starts_with(&format!("16,195,{}", amount)), "{}", raw);
+        }
+
+        // Redacting text or reference numbers drops the raw records that have them.
+        let redacted = file.redact(RedactOptions::default());
+        assert_eq!(raw_records(&redacted), vec![None, None, None]);
+        let masked_only = file.redact(RedactOptions {
+            account_numbers: true,
+            text: false,
+            reference_nums: false,
+        });
+        assert_eq!(raw_records(&masked_only), expected);
+
+        let file = data::File::process_str(&continued).unwrap();
+        assert_eq!(raw_records(&file), vec![None, None, None]);
+    }
+
+    #[test]
+    fn custom_type_codes() {
+        use data::{AccountInfo, CreditDetail, CreditSummary, DetailCode, SummaryCode};
+
+        let custom = SLASHED_TEXT
+            .replace("03,12345,,010,1000,,/", "03,12345,,010,1000,,,920,300,1,/")
+            .replace("16,195,100,,,,/", "16,920,100,,,,/")
+            .replace("1850", "2150");
+        let file = data::File::process_str(&custom).unwrap();
+        let account = &file.groups[0].accounts[0];
+        match account.infos[1] {
+            AccountInfo::Summary { code, .. } => {
+                assert_eq!(code, SummaryCode::Credit(CreditSummary::Custom(920)));
+                assert_eq!(u16::from(code), 920);
+            }
+            ref info => panic!("Expected summary, found {:?}", info),
+        }
+        let code = account.transaction_details[2].code;
+        assert_eq!(code, DetailCode::Credit(CreditDetail::Custom(920)));
+        assert_eq!(u16::from(code), 920);
+
+        let mut written = String::new();
+        write::file(&mut written, &file).unwrap();
+        assert!(written.contains(",920,300,1,"));
+        assert!(written.contains("16,920,100,"));
+    }
+
+    #[test]
+    fn non_ascii_policy() {
+        use write::{NonAsciiPolicy, WriteError, WriteOptions};
+
+        let mut file = data::File::process(SLASHED_TEXT.as_bytes()).unwrap();
+        file.sender = data::Party("Müller".to_string());
+        let written = |policy| {
+            let mut written = String::new();
+            let options = WriteOptions { non_ascii: Some(policy) };
+            write::file_with_options(&mut written, &file, &options).map(|()| written)
+        };
+
+        assert_eq!(
+            written(NonAsciiPolicy::Reject),
+            Err(WriteError::NonAscii {
+                field: "sender",
+                value: "Müller".to_string(),
+            })
+        );
+        assert!(written(NonAsciiPolicy::Transliterate).unwrap().starts_with("01,Muller,"));
+        assert!(written(NonAsciiPolicy::Strip).unwrap().starts_with("01,Mller,"));
+
+        let mut unchanged = String::new();
+        write::file_with_options(&mut unchanged, &file, &WriteOptions::default()).unwrap();
+        assert!(unchanged.starts_with("01,Müller,"));
+    }
+
+    #[test]
+    fn account_info_accessors() {
+        let with_summary = SIGNED_AMOUNTS.replace("015,+100,,", "100,100,2,");
+        let file = data::File::process_str(&with_summary).unwrap();
+        let infos = &file.groups[0].accounts[0].infos;
+        assert_eq!(
+            infos.iter().map(|i| i.numeric_code()).collect::<Vec<_>>(),
+            vec![10, 100]
+        );
+        assert_eq!(
+            infos.iter().map(|i| i.amount()).collect::<Vec<_>>(),
+            vec![Some(-12345), Some(100)]
+        );
+    }
+
+    #[test]
+    fn validate_revisions() {
+        use data::{AsOfDateModifier, GroupStatus, IssueKind, Location};
+
+        let revisions = EMPTY_GROUP_AND_ACCOUNT
+            .replacen("02,RECEIVER,SENDER,1,170601,,,2/", "02,RECEIVER,SENDER,2,170601,,,/", 1)
+            .replacen("02,RECEIVER,SENDER,1,170601,,,2/", "02,RECEIVER,SENDER,3,170601,,,3/", 1);
+        let file = data::File::process_str(&revisions).unwrap();
+        let issues = file.validate()
+            .issues
+            .into_iter()
+            .filter(|i| i.kind != IssueKind::DefaultCurrency)
+            .map(|i| (i.location, i.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            issues,
+            vec![
+                (
+                    Location::Group { group: 0 },
+                    IssueKind::RevisionWithoutModifier(GroupStatus::Deletion),
+                ),
+                (
+                    Location::Group { group: 1 },
+                    IssueKind::MixedGroupStatus {
+                        first: GroupStatus::Deletion,
+                        status: GroupStatus::Correction,
+                    },
+                ),
+                (
+                    Location::Group { group: 1 },
+                    IssueKind::RevisionOfInterimData {
+                        status: GroupStatus::Correction,
+                        modifier: AsOfDateModifier::InterimSame,
+                    },
+                ),
+            ]
+        );
+
+        let final_correction = EMPTY_GROUP_AND_ACCOUNT.replace(",1,170601,,,2/", ",3,170601,,,2/");
+        let file = data::File::process_str(&final_correction).unwrap();
+        assert!(file.validate().issues.iter().all(|i| i.kind == IssueKind::DefaultCurrency));
+    }
+
+    #[test]
+    fn rescale_amounts() {
+        use data::{AccountInfo, FundsType, RescaleError};
+
+        let input = SIGNED_AMOUNTS
+            .replace("010,-12345,,", "010,-12345,,S,100,,-5")
+            .replace("015,+100,,", "100,100,2,");
+        let original = data::File::process_str(&input).unwrap();
+        let mut file = original.clone();
+        file.rescale_amounts(100).unwrap();
+        let infos = &file.groups[0].accounts[0].infos;
+        assert_eq!(
+            infos.iter().map(|i| i.amount()).collect::<Vec<_>>(),
+            vec![Some(-1234500), Some(10000)]
+        );
+        match infos[0] {
+            AccountInfo::Status { ref funds, .. } => {
+                assert_eq!(
+                    *funds,
+                    Some(FundsType::DistributedAvailS {
+                        immediate: Some(10000),
+                        one_day: None,
+                        more_than_one_day: Some(-500),
+                    })
+                )
+            }
+            ref info => panic!("Expected status, found {:?}", info),
+        }
+        let scaled = |total: Option<i64>| total.map(|t| t * 100);
+        assert_eq!(file.declared_control_total, scaled(original.declared_control_total));
+        assert_eq!(
+            file.groups[0].declared_control_total,
+            scaled(original.groups[0].declared_control_total)
+        );
+        assert_eq!(
+            file.groups[0].accounts[0].declared_control_total,
+            scaled(original.groups[0].accounts[0].declared_control_total)
+        );
+
+        let mut overflowing = original.clone();
+        assert_eq!(
+            overflowing.rescale_amounts(i64::max_value()),
+            Err(RescaleError::Overflow)
+        );
+        assert_eq!(overflowing, original);
+        assert_eq!(
+            overflowing.rescale_amounts(-1),
+            Err(RescaleError::NegativeFactor(-1))
+        );
+    }
+
+    #[cfg(feature = "mt940")]
+    #[test]
+    fn mt940() {
+        let input = SLASHED_TEXT
+            .replace("03,12345,,010,1000,,/", "03,12345,,010,1000,,,015,1650,,/")
+            .replace("16,195,100,,,,/", "16,475,100,,BANKREF,CUSTREF,/")
+            .replace("1850", "3500");
+        let file = data::File::process_str(&input).unwrap();
+        let mut written = String::new();
+        file.to_mt940(&mut written).unwrap();
+        assert_eq!(
+            written,
+            "\
+:20:1
+:25:12345
+:28C:1/1
+:60F:C170601USD10,00
+:61:170601C5,00NTRFNONREF
+:86:WIRE IN/OUT NET SETTLEMENT
+:61:170601C2,50NTRFNONREF
+:86:/FX/ NET SETTLEMENT/
+:61:170601D1,00NCHKCUSTREF//BANKREF
+:62F:C170601USD16,50
+-
+"
+        );
+
+        // The closing ledger is worked out from the opening one.
+        let overflowing = EMPTY_GROUP_AND_ACCOUNT
+            .replace(
+                "03,12345,,010,1000,,,015,1000,,/",
+                "03,12345,,010,-9000000000000000000,,/\n16,495,1000000000000000000,,,,",
+            )
+            .replace("49,2000,2/", "49,-8000000000000000000,3/")
+            .replace("98,2000,1,4/", "98,-8000000000000000000,1,5/")
+            .replace("99,2000,2,8/", "99,-8000000000000000000,2,9/");
+        let file = data::File::process(overflowing.as_bytes()).unwrap();
+        assert_eq!(file.to_mt940(&mut String::new()), Err(write::WriteError::BalanceOverflow));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn process_instrumented() {
+        use data::FileProcessError;
+
+        let (file, metrics) = data::File::process_instrumented(SLASHED_TEXT.as_bytes());
+        assert_eq!(file.unwrap(), data::File::process(SLASHED_TEXT.as_bytes()).unwrap());
+        assert_eq!(metrics.bytes, SLASHED_TEXT.len());
+        assert_eq!((metrics.records, metrics.physical_records), (9, 9));
+        assert_eq!(
+            metrics.total(),
+            metrics.tokenize + metrics.field_parse + metrics.conversion
+        );
+
+        let unfinished = SLASHED_TEXT.replace("99,1850,1,9/\n", "");
+        let (file, metrics) = data::File::process_instrumented(unfinished.as_bytes());
+        match file {
+            Err(FileProcessError::UnfinishedConversion) => {}
+            r => panic!("Expected unfinished conversion, found {:?}", r),
+        }
+        assert_eq!(metrics.records, 8);
+    }
+
+    #[test]
+    fn creation_date_only() {
+        use chrono::NaiveDate;
+        use data::BaiDateTime;
+
+        let date_only = SLASHED_TEXT.replace("170601,1200,", "170601,,");
+        let file = data::File::process_str(&date_only).unwrap();
+        assert_eq!(file.creation, BaiDateTime::Date(NaiveDate::from_ymd(2017, 6, 1)));
+        assert_eq!(file.creation.time(), None);
+
+        let mut written = String::new();
+        write::file(&mut written, &file).unwrap();
+        assert!(written.starts_with("01,SENDER,RECEIVER,170601,,1,"));
+        assert_eq!(data::File::process_str(&written).unwrap().creation, file.creation);
+
+        let end_of_day = SLASHED_TEXT.replace("170601,1200,", "170601,9999,");
+        let file = data::File::process_str(&end_of_day).unwrap();
+        assert_eq!(file.creation, BaiDateTime::DateEndOfDay(NaiveDate::from_ymd(2017, 6, 1)));
+    }
+
+    #[test]
+    fn balance_table() {
+        use penny::Currency;
+
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let balances = file.groups[0].accounts[0]
+            .balances(Currency::USD)
+            .into_iter()
+            .map(|(code, desc, money)| (code, desc, money.map(|m| m.amount())))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            balances,
+            vec![
+                (10, "Opening Ledger", Some(4350000)),
+                (40, "Opening Available", Some(2830000)),
+                (72, "One Day Float", Some(1020000)),
+                (74, "Two or More Days Float", Some(500000)),
+            ]
+        );
+        assert_eq!(data::GroupStatus::TestOnly.to_string(), "Test Only");
+    }
+
+    #[test]
+    fn large_amounts() {
+        use ast::convert::ConvertError;
+        use data::FileProcessError;
+
+        let file_with = |status: &str, detail: &str, total: &str| {
+            format!(
+                "\
+01,SENDER,RECEIVER,170601,1200,1,,,2/
+02,RECEIVER,SENDER,1,170601,,,2/
+03,12345,,010,{},,/
+16,195,{},,,,/
+49,{2},3/
+98,{2},1,5/
+99,{2},1,7/
+",
+                status,
+                detail,
+                total
+            )
+        };
+
+        let eighteen = "999999999999999999";
+        let file =
+            data::File::process_str(&file_with(eighteen, eighteen, "1999999999999999998")).unwrap();
+        let account = &file.groups[0].accounts[0];
+        assert_eq!(account.infos[0].amount(), Some(999999999999999999));
+        assert_eq!(account.transaction_details[0].amount, Some(999999999999999999));
+
+        let max = i64::max_value().to_string();
+        let file = data::File::process_str(&file_with("0", &max, &max)).unwrap();
+        let detail = &file.groups[0].accounts[0].transaction_details[0];
+        assert_eq!(detail.amount, Some(i64::max_value()));
+
+        let twenty_five = "9999999999999999999999999";
+        let oversized_status = file_with(twenty_five, "0", "0");
+        match data::File::process_str(&oversized_status) {
+            Err(FileProcessError::FieldParse(_)) => {}
+            r => panic!("Expected field parse error, found {:?}", r),
+        }
+        let oversized_detail = file_with("0", twenty_five, "0");
+        match data::File::process_str(&oversized_detail) {
+            Err(FileProcessError::FieldParse(_)) => {}
+            r => panic!("Expected field parse error, found {:?}", r),
+        }
+
+        // Amounts that each fit but add up past i64::MAX can't match any trailer, even one that
+        // declares the wrapped total.
+        for total in &[max.as_str(), "-2"] {
+            match data::File::process_str(&file_with(&max, &max, total)) {
+                Err(FileProcessError::Conversion(ConvertError::ControlTotalOverflow {
+                    record: 4,
+                })) => {}
+                r => panic!("Expected control total overflow, found {:?}", r),
+            }
+        }
+        let overflowing_status = file_with(&format!("{},,,010,{}", max, max), "0", "0");
+        match data::File::process_str(&overflowing_status) {
+            Err(FileProcessError::Conversion(ConvertError::ControlTotalOverflow {
+                record: 3,
+            })) => {}
+            r => panic!("Expected control total overflow, found {:?}", r),
+        }
+    }
+
+    #[test]
+    fn borrowed_file() {
+        use std::borrow::Cow;
+
+        let file = data::File::process_borrowed(SPEC_EXAMPLE.as_bytes()).unwrap();
+        match file.sender.0 {
+            Cow::Borrowed(sender) => assert_eq!(sender, "122099999"),
+            Cow::Owned(_) => panic!("Sender wasn't borrowed"),
+        }
+        let account = &file.groups[0].accounts[0];
+        assert!(match account.customer_account.0 {
+            Cow::Borrowed(_) => true,
+            Cow::Owned(_) => false,
+        });
+        assert_eq!(file.into_owned(), data::File::process_str(SPEC_EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn file_finality() {
+        use data::{AsOfDateModifier, Finality};
+
+        let mut file = data::File::process_str(SPEC_EXAMPLE).unwrap();
+        assert_eq!(file.finality(), Finality::Final);
+        assert!(file.is_final());
+        assert!(!file.is_intraday());
+
+        for group in &mut file.groups {
+            group.as_of_date_mod = Some(AsOfDateModifier::InterimSame);
+        }
+        assert_eq!(file.finality(), Finality::Intraday);
+        assert!(file.is_intraday());
+
+        file.groups.truncate(2);
+        file.groups[1].as_of_date_mod = Some(AsOfDateModifier::FinalSame);
+        assert_eq!(
+            file.finality(),
+            Finality::Mixed(vec![
+                Some(AsOfDateModifier::InterimSame),
+                Some(AsOfDateModifier::FinalSame),
+            ])
+        );
+        assert!(!file.is_final());
+        assert!(!file.is_intraday());
+
+        file.groups.clear();
+        assert_eq!(file.finality(), Finality::Unknown);
+    }
+
+    #[test]
+    fn account_record_count_conventions() {
+        use ast::convert::{AccountConvError, ConvertError, GroupConvError, RecordCountConvention};
+        use data::FileProcessError;
+        use parse::ParseOptions;
+
+        let inclusive = ParseOptions {
+            record_count_convention: RecordCountConvention::Inclusive,
+            ..ParseOptions::default()
+        };
+        let exclusive = ParseOptions {
+            record_count_convention: RecordCountConvention::Exclusive,
+            ..ParseOptions::default()
+        };
+        // Counts the 03 and 49, and the 02 and 98, as the spec does.
+        assert!(data::File::process_with_options(SLASHED_TEXT.as_bytes(), &inclusive).is_ok());
+        assert!(data::File::process_with_options(SLASHED_TEXT.as_bytes(), &exclusive).is_err());
+
+        // Only counts the records between them.
+        let between = SLASHED_TEXT
+            .replace("49,1850,5/", "49,1850,3/")
+            .replace("98,1850,1,7/", "98,1850,1,5/");
+        assert!(data::File::process_with_options(between.as_bytes(), &exclusive).is_ok());
+        match data::File::process_with_options(between.as_bytes(), &inclusive) {
+            Err(FileProcessError::Conversion(ConvertError::Account {
+                err: AccountConvError::RecordsNum { expected: 3, actual: 5 },
+                ..
+            })) => {}
+            r => panic!("Expected record count error, found {:?}", r),
+        }
+        match data::File::process_validated(between.as_bytes()) {
+            Err(FileProcessError::Conversion(ConvertError::Account { .. })) => {}
+            r => panic!("Expected record count error, found {:?}", r),
+        }
+
+        let group_only = SLASHED_TEXT.replace("98,1850,1,7/", "98,1850,1,6/");
+        match data::File::process_with_options(group_only.as_bytes(), &inclusive) {
+            Err(FileProcessError::Conversion(ConvertError::Group {
+                err: GroupConvError::RecordsNum { expected: 6, actual: 7 },
+                ..
+            })) => {}
+            r => panic!("Expected record count error, found {:?}", r),
+        }
+
+        // Neither is checked unless asked for, as before.
+        assert!(data::File::process_str(&between).is_ok());
+        assert!(data::File::process_str(&group_only).is_ok());
+    }
+
+    #[test]
+    fn split_by_account() {
+        use parse::ParseOptions;
+
+        let file = data::File::process_str(SPEC_EXAMPLE).unwrap();
+        let split = file.split_by_account();
+        let accounts = file.groups.iter().map(|g| g.accounts.len()).sum::<usize>();
+        assert_eq!(split.len(), accounts);
+
+        for part in &split {
+            assert_eq!(part.groups.len(), 1);
+            assert_eq!(part.groups[0].accounts.len(), 1);
+            let mut written = String::new();
+            write::file(&mut written, part).unwrap();
+            let reparsed = data::File::process_str(&written).unwrap();
+            assert_eq!(undeclared(reparsed), undeclared(part.clone()));
+        }
+
+        let merged = data::File::merge(split).unwrap();
+        assert_eq!(undeclared(merged), undeclared(file));
+
+        let options = ParseOptions {
+            keep_raw_records: true,
+            ..ParseOptions::default()
+        };
+        let file = data::File::process_with_options(SPEC_EXAMPLE.as_bytes(), &options).unwrap();
+        let raw = |file: &data::File| {
+            file.groups
+                .iter()
+                .flat_map(|g| &g.accounts)
+                .flat_map(|a| &a.transaction_details)
+                .map(|td| td.raw_record.clone().unwrap())
+                .collect::<Vec<_>>()
+        };
+        let split = file.split_by_account();
+        assert_eq!(split.iter().flat_map(raw).collect::<Vec<_>>(), raw(&file));
+    }
+
+    #[test]
+    fn after_trailer() {
+        use data::FileProcessError;
+
+        let padded = vec![
+            format!("{}{}", SLASHED_TEXT, " ".repeat(500)),
+            format!("{}{}", SLASHED_TEXT.trim_end(), " ".repeat(500)),
+            format!("{}\n\n   \n\r\n", SLASHED_TEXT),
+            format!("{}{}\x1a", SLASHED_TEXT, "\0".repeat(20)),
+        ];
+        let expected = data::File::process_str(SLASHED_TEXT).unwrap();
+        for input in &padded {
+            assert_eq!(data::File::process_str(input).unwrap(), expected);
+        }
+
+        let another_file = format!("{}01,SENDER,RECEIVER,170601,1200,2,,,2/\n", SLASHED_TEXT);
+        match data::File::process_str(&another_file) {
+            Err(FileProcessError::AfterTrailer { record_number: 10 }) => {}
+            r => panic!("Expected record after trailer error, found {:?}", r),
+        }
+    }
+
+    #[test]
+    fn code_round_trips() {
+        use std::convert::TryFrom;
+        use data::{DetailCode, StatusCode, SummaryCode};
+
+        for code in 0..1000 {
+            if let Ok(status) = StatusCode::try_from(code) {
+                assert_eq!(u16::from(status), code);
+            }
+            if let Ok(summary) = SummaryCode::try_from(code) {
+                assert_eq!(u16::from(summary), code);
+            }
+            if let Ok(detail) = DetailCode::try_from(code) {
+                assert_eq!(u16::from(detail), code);
+            }
+        }
+
+        assert_eq!(StatusCode::try_from(10).map(u16::from), Ok(10));
+        assert_eq!(SummaryCode::try_from(100).map(u16::from), Ok(100));
+        assert_eq!(DetailCode::try_from(890).map(u16::from), Ok(890));
+        // Unknown codes are given back as the error.
+        assert_eq!(StatusCode::try_from(500), Err(500));
+        assert_eq!(SummaryCode::try_from(890), Err(890));
+        assert_eq!(DetailCode::try_from(0), Err(0));
+        assert_eq!(DetailCode::try_from(1000), Err(1000));
+    }
+
+    #[test]
+    fn detail_extensions() {
+        use parse::ParseOptions;
+
+        let input = "\
+01,SENDER,RECEIVER,170601,1200,1,,,2/
+02,RECEIVER,SENDER,1,170601,,,2/
+03,12345,,010,1000,,/
+16,195,500,,BANK,CUST,R,WIRE IN, NET SETTLEMENT
+88,SECOND LINE
+16,195,250,,,,M,/
+49,1750,5/
+98,1750,1,7/
+99,1750,1,9/
+";
+        let plain = data::File::process_str(input).unwrap();
+        let details = &plain.groups[0].accounts[0].transaction_details;
+        assert!(details[0].extensions.is_empty());
+        assert_eq!(
+            details[0].text,
+            Some(vec!["R,WIRE IN, NET SETTLEMENT".to_string(), "SECOND LINE".to_string()])
+        );
+
+        let options = ParseOptions {
+            detail_extensions: 1,
+            ..ParseOptions::default()
+        };
+        let file = data::File::process_with_options(input.as_bytes(), &options).unwrap();
+        let details = &file.groups[0].accounts[0].transaction_details;
+        assert_eq!(details[0].extensions, vec!["R".to_string()]);
+        assert_eq!(
+            details[0].text,
+            Some(vec!["WIRE IN, NET SETTLEMENT".to_string(), "SECOND LINE".to_string()])
+        );
+        assert_eq!(details[1].extensions, vec!["M".to_string()]);
+        assert_eq!(details[1].text, None);
+
+        let mut written = String::new();
+        write::file(&mut written, &file).unwrap();
+        let reparsed = data::File::process_with_options(written.as_bytes(), &options).unwrap();
+        assert_eq!(undeclared(reparsed), undeclared(file));
+    }
+
+    #[test]
+    fn from_source_limited() {
+        use std::io;
+
+        let len = SPEC_EXAMPLE.len() as u64;
+        let mut source = SPEC_EXAMPLE.as_bytes();
+        assert_eq!(
+            data::File::from_source_limited(&mut source, len).unwrap(),
+            data::File::process_str(SPEC_EXAMPLE).unwrap()
+        );
+        let mut source = SPEC_EXAMPLE.as_bytes();
+        assert!(data::File::from_source_limited(&mut source, len - 1).is_err());
+
+        // Never ends, so this only finishes because of the limit.
+        let mut endless = io::repeat(b' ');
+        assert_eq!(
+            data::File::from_source_limited(&mut endless, 1 << 20),
+            Err("Input is larger than 1048576 bytes".to_string())
+        );
+    }
+
+    #[test]
+    fn unbalanced_records() {
+        use ast::RecordField as RF;
+        use data::FileProcessError;
+
+        let file_header = "01,SENDER,RECEIVER,170601,1200,1,,,2/";
+        let group_header = "02,RECEIVER,SENDER,1,170601,,,2/";
+        let account_ident = "03,12345,,010,1000,,/";
+        let detail = "16,195,500,,,,/";
+        let account_trailer = "49,1500,3/";
+        let group_trailer = "98,1500,1,5/";
+        let file_trailer = "99,1500,1,7/";
+
+        let cases = vec![
+            (vec![group_header, file_trailer], RF::FileHeader, RF::GroupHeader, 1),
+            (vec![file_header, group_trailer, file_trailer], RF::FileTrailer, RF::GroupTrailer, 2),
+            (
+                vec![file_header, group_header, account_trailer, group_trailer, file_trailer],
+                RF::GroupTrailer,
+                RF::AccountTrailer,
+                3,
+            ),
+            (
+                vec![file_header, group_header, account_ident, detail, group_trailer],
+                RF::AccountTrailer,
+                RF::GroupTrailer,
+                5,
+            ),
+            (
+                vec![
+                    file_header,
+                    group_header,
+                    account_ident,
+                    detail,
+                    account_trailer,
+                    file_trailer,
+                ],
+                RF::GroupTrailer,
+                RF::FileTrailer,
+                6,
+            ),
+            (
+                vec![file_header, group_header, account_ident, group_header],
+                RF::AccountTrailer,
+                RF::GroupHeader,
+                4,
+            ),
+        ];
+        for (lines, expected, found, record_number) in cases {
+            let input = lines.join("\n");
+            match data::File::process_str(&input) {
+                Err(FileProcessError::UnbalancedRecords {
+                    expected: e,
+                    found: f,
+                    record_number: n,
+                }) => assert_eq!((e, f, n), (expected, found, record_number), "{}", input),
+                r => panic!("Expected unbalanced records in {:?}, found {:?}", input, r),
+            }
+        }
+
+        // Transaction details out of place aren't headers or trailers, so they're only of the
+        // wrong type.
+        let input = [file_header, group_header, detail].join("\n");
+        match data::File::process_str(&input) {
+            Err(FileProcessError::Conversion(ast::convert::ConvertError::RecordType { .. })) => {}
+            r => panic!("Expected record type error, found {:?}", r),
+        }
+    }
+
+    #[test]
+    fn dedup_infos() {
+        use data::DedupStrategy;
+
+        let input = "\
+01,SENDER,RECEIVER,170601,1200,1,,,2/
+02,RECEIVER,SENDER,1,170601,,,2/
+03,12345,,015,100,,,100,50,2,,015,300,,,100,25,1,,040,10,,/
+49,485,2/
+98,485,1,4/
+99,485,1,6/
+";
+        let file = data::File::process_str(input).unwrap();
+        let account = &file.groups[0].accounts[0];
+        let deduped = |strategy| {
+            let mut account = account.clone();
+            account.dedup_infos(strategy);
+            account
+                .infos
+                .iter()
+                .map(|i| (i.numeric_code(), i.amount(), i.item_count()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            deduped(DedupStrategy::Sum),
+            vec![(15, Some(400), None), (100, Some(75), Some(3)), (40, Some(10), None)]
+        );
+        assert_eq!(
+            deduped(DedupStrategy::FirstWins),
+            vec![(15, Some(100), None), (100, Some(50), Some(2)), (40, Some(10), None)]
+        );
+        assert_eq!(
+            deduped(DedupStrategy::LastWins),
+            vec![(15, Some(300), None), (100, Some(25), Some(1)), (40, Some(10), None)]
+        );
+
+        let mut account = account.clone();
+        account.infos.truncate(1);
+        let mut big = account.infos[0].clone();
+        if let data::AccountInfo::Status { ref mut amount, .. } = big {
+            *amount = Some(i64::max_value());
+        }
+        account.infos.push(big);
+        account.infos.push(account.infos[0].clone());
+        account.dedup_infos(DedupStrategy::Sum);
+        assert_eq!(
+            account.infos.iter().map(|i| i.amount()).collect::<Vec<_>>(),
+            vec![Some(200), Some(i64::max_value())]
+        );
+    }
+
+    #[test]
+    fn from_path() {
+        use std::env;
+        use std::fs;
+        use std::io;
+        use std::process;
+
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/spec-example.bai");
+        assert_eq!(
+            data::File::from_path(path).unwrap(),
+            data::File::process_str(SPEC_EXAMPLE).unwrap()
+        );
+        match data::File::from_path(concat!(env!("CARGO_MANIFEST_DIR"), "/missing.bai")) {
+            Err(data::FileProcessError::Io(ref err)) if err.kind() == io::ErrorKind::NotFound => {}
+            r => panic!("unexpected result: {:?}", r),
+        }
+
+        // The process ID keeps test runs going at the same time from sharing the file.
+        let gzipped = env::temp_dir().join(format!("baimax-{}-from-path.bai", process::id()));
+        fs::write(&gzipped, b"\x1f\x8b\x08\x00").unwrap();
+        let result = data::File::from_path(&gzipped);
+        fs::remove_file(&gzipped).unwrap();
+        match result {
+            Err(data::FileProcessError::Gzipped) => {}
+            r => panic!("unexpected result: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn validate_mixed_group_status() {
+        use data::{GroupStatus, IssueKind, Location, Severity};
+
+        let file = data::File::process_str(EMPTY_GROUP_AND_ACCOUNT).unwrap();
+        assert!(file.validate().issues.iter().all(|i| i.kind == IssueKind::DefaultCurrency));
+
+        // Only the second group becomes a deletion.
+        let mixed = EMPTY_GROUP_AND_ACCOUNT.replace(
+            "02,RECEIVER,SENDER,1,170601,,,2/\n03",
+            "02,RECEIVER,SENDER,2,170601,,,2/\n03",
+        );
+        let file = data::File::process_str(&mixed).unwrap();
+        let issues = file.validate()
+            .issues
+            .into_iter()
+            .filter(|i| i.kind != IssueKind::DefaultCurrency)
+            .collect::<Vec<_>>();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert_eq!(issues[0].location, Location::Group { group: 1 });
+        assert_eq!(
+            issues[0].kind,
+            IssueKind::MixedGroupStatus {
+                first: GroupStatus::Update,
+                status: GroupStatus::Deletion,
+            }
+        );
+    }
+
+    #[test]
+    fn available_as_of() {
+        use chrono::NaiveDate;
+        use penny::Currency;
+        use data::{AccountInfo, AccountStatus, AvailabilityDays, FundsType, StatusCode};
+
+        let file = data::File::process(DISTRIBUTED_AVAIL.as_bytes()).unwrap();
+        let mut account = file.groups[0].accounts[0].clone();
+        let wednesday = NaiveDate::from_ymd(2017, 5, 31);
+        let thursday = NaiveDate::from_ymd(2017, 6, 1);
+        let friday = NaiveDate::from_ymd(2017, 6, 2);
+        let available = |account: &data::Account, as_of, date, days| {
+            account.available_as_of_in(as_of, date, Currency::USD, days).amount()
+        };
+        let calendar = AvailabilityDays::Calendar;
+
+        // 100 of the 300 credit is available on the day, and the 300 debit is taken straight away.
+        assert_eq!(account.available_as_of(thursday, thursday, Currency::USD).amount(), -200);
+        assert_eq!(available(&account, thursday, wednesday, calendar), -200);
+        assert_eq!(available(&account, thursday, friday, calendar), 0);
+
+        account.infos.push(AccountInfo::Status {
+            code: StatusCode::Account(AccountStatus::OpeningLedger),
+            amount: Some(1000),
+            funds: Some(FundsType::DistributedAvailS {
+                immediate: Some(600),
+                one_day: None,
+                more_than_one_day: Some(300),
+            }),
+        });
+        // The 100 the distribution leaves out is taken as immediate.
+        assert_eq!(available(&account, thursday, thursday, calendar), 500);
+        assert_eq!(available(&account, thursday, friday, calendar), 700);
+        assert_eq!(available(&account, thursday, NaiveDate::from_ymd(2017, 6, 3), calendar), 1000);
+        assert_eq!(available(&account, friday, friday, calendar), 500);
+        let monday = NaiveDate::from_ymd(2017, 6, 5);
+        let business = AvailabilityDays::Business;
+        assert_eq!(available(&account, friday, monday, calendar), 1000);
+        assert_eq!(available(&account, friday, monday, business), 700);
+        assert_eq!(available(&account, friday, monday.succ(), business), 1000);
+    }
+
+    #[test]
+    fn continuation_text_lines() {
+        let input = "\
+01,SENDER,RECEIVER,170601,1200,1,,,2/
+02,RECEIVER,SENDER,1,170601,,,2/
+03,12345,,010,1000,,/
+16,195,500,,BANK,CUST,
+88,ORG=ACME CORP
+88,  OBI=INV 1001, INV 1002
+88,BBI=/ACC/PAYROLL
+16,195,250,,,,WIRE IN
+88,FROM ACME
+49,1750,8/
+98,1750,1,10/
+99,1750,1,12/
+";
+        let file = data::File::process_str(input).unwrap();
+        let details = &file.groups[0].accounts[0].transaction_details;
+        assert_eq!(
+            details[0].text,
+            Some(vec![
+                "ORG=ACME CORP".to_string(),
+                "  OBI=INV 1001, INV 1002".to_string(),
+                "BBI=/ACC/PAYROLL".to_string(),
+            ])
+        );
+        assert_eq!(
+            details[1].text,
+            Some(vec!["WIRE IN".to_string(), "FROM ACME".to_string()])
+        );
+
+        // A 16 that ends with a slash can still be continued.
+        let slashed = input.replacen("BANK,CUST,\n", "BANK,CUST,/\n", 1);
+        let file = data::File::process_str(&slashed).unwrap();
+        assert_eq!(
+            file.groups[0].accounts[0].transaction_details[0].text.as_ref().map(Vec::len),
+            Some(3)
+        );
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn encodings() {
+        use encoding::Encoding;
+        use parse::ParseOptions;
+
+        let ebcdic = include_bytes!("../tests/fixtures/spec-example-cp037.bai");
+        assert!(data::File::process(ebcdic).is_err());
+        let options = ParseOptions::default().encoding(Encoding::Ebcdic037);
+        assert_eq!(
+            data::File::process_with_options(ebcdic, &options).unwrap(),
+            data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap()
+        );
+
+        // Blocked records are padded with EBCDIC spaces, and the width counts each é as a byte.
+        let text = SLASHED_TEXT.replace("16,195,100,,,,/", "16,195,100,,,,CAF\u{e9}");
+        let to_ebcdic = |c: char| {
+            (0..256)
+                .map(|b| b as u8)
+                .find(|&b| Encoding::Ebcdic037.decode(&[b]) == c.to_string().as_bytes())
+                .unwrap()
+        };
+        let blocked = text.lines()
+            .flat_map(|line| {
+                let mut record = line.chars().map(&to_ebcdic).collect::<Vec<u8>>();
+                record.resize(80, 0x40);
+                record
+            })
+            .collect::<Vec<u8>>();
+        let options = ParseOptions::blocked(80).encoding(Encoding::Ebcdic037);
+        assert_eq!(
+            data::File::process_with_options(&blocked, &options).unwrap(),
+            data::File::process_str(&text).unwrap()
+        );
+
+        let windows = text.replace("\u{e9}", "\u{e9} \u{201c}OK\u{201d}");
+        let encoded = windows.chars()
+            .map(|c| match c {
+                '\u{e9}' => 0xE9,
+                '\u{201c}' => 0x93,
+                '\u{201d}' => 0x94,
+                c => c as u8,
+            })
+            .collect::<Vec<u8>>();
+        let options = ParseOptions::default().encoding(Encoding::Windows1252);
+        assert_eq!(
+            data::File::process_with_options(&encoded, &options).unwrap(),
+            data::File::process_str(&windows).unwrap()
+        );
+    }
+
+    #[test]
+    fn date_time_ordering() {
+        use chrono::NaiveDate;
+        use data::{BaiDateOrTime, BaiDateTime};
+
+        let day = NaiveDate::from_ymd(2017, 6, 1);
+        let mut times = vec![
+            BaiDateTime::DateEndOfDay(day),
+            BaiDateTime::DateTime(day.succ().and_hms(0, 0, 0)),
+            BaiDateTime::DateTime(day.and_hms(23, 59, 0)),
+            BaiDateTime::DateTime(day.and_hms(0, 0, 0)),
+            BaiDateTime::Date(day),
+            BaiDateTime::DateEndOfDay(day.pred()),
+        ];
+        times.sort();
+        assert_eq!(
+            times,
+            vec![
+                BaiDateTime::DateEndOfDay(day.pred()),
+                BaiDateTime::Date(day),
+                BaiDateTime::DateTime(day.and_hms(0, 0, 0)),
+                BaiDateTime::DateTime(day.and_hms(23, 59, 0)),
+                BaiDateTime::DateEndOfDay(day),
+                BaiDateTime::DateTime(day.succ().and_hms(0, 0, 0)),
+            ]
+        );
+        assert!(BaiDateOrTime::Date(day) < BaiDateOrTime::DateTime(day.and_hms(0, 0, 0)));
+        assert!(BaiDateOrTime::DateEndOfDay(day) < BaiDateOrTime::Date(day.succ()));
+
+        let mut file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        file.groups[0].as_of = BaiDateOrTime::DateEndOfDay(day.succ());
+        file.groups.sort_by(|a, b| a.as_of.cmp(&b.as_of));
+        assert_eq!(file.groups.last().unwrap().as_of, BaiDateOrTime::DateEndOfDay(day.succ()));
+    }
+
+    #[test]
+    fn code_histogram() {
+        use penny::Currency;
+
+        let input = SLASHED_TEXT.replace("16,195,250,,,,/FX/ NET SETTLEMENT/", "16,475,250,,,,/");
+        let file = data::File::process_str(&input).unwrap();
+        let histogram = file.code_histogram();
+        assert_eq!(histogram.len(), 2);
+        assert_eq!(histogram[&195]["USD"], (2, 600));
+        assert_eq!(histogram[&475]["USD"], (1, -250));
+
+        let mut file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let histogram = file.code_histogram();
+        let count = histogram.values().flat_map(|h| h.values()).map(|&(n, _)| n).sum::<usize>();
+        assert_eq!(count, file.transactions().count());
+
+        // Currencies are kept apart.
+        file.groups[1].currency = Some(Currency::CAD);
+        let histogram = file.code_histogram();
+        assert_eq!(histogram[&115]["USD"], (2, 950000));
+        assert_eq!(histogram[&218]["CAD"], (1, 20000000));
+        assert!(!histogram[&218].contains_key("USD"));
+    }
+
+    #[cfg(feature = "serde-serialize")]
+    #[test]
+    fn code_histogram_json() {
+        let file = data::File::process(SLASHED_TEXT.as_bytes()).unwrap();
+        let json = serde_json::to_string(&file.code_histogram()).unwrap();
+        assert_eq!(json, r#"{"195":{"USD":[3,850]}}"#);
+    }
+
+    #[test]
+    fn declared_record_length() {
+        let declared = EMPTY_GROUP_AND_ACCOUNT.replacen(",2,,,2/", ",2,80,1,2/", 1);
+        let blocked = declared
+            .lines()
+            .map(|line| format!("{:80}", line))
+            .collect::<String>();
+        assert_eq!(parse::declared_record_width(blocked.as_bytes()), Some(80));
+        assert_eq!(parse::declared_record_width(declared.as_bytes()), None);
+
+        let file = data::File::process_str(&declared).unwrap();
+        assert_eq!(file.physical_record_len, Some(80));
+        assert_eq!(file.block_size, Some(1));
+        let options = parse::ParseOptions::default();
+        assert!(data::File::process_with_options(blocked.as_bytes(), &options).is_err());
+        let options = parse::ParseOptions {
+            detect_record_width: true,
+            ..parse::ParseOptions::default()
+        };
+        assert_eq!(data::File::process_with_options(blocked.as_bytes(), &options).unwrap(), file);
+
+        let mut written = String::new();
+        write::file(&mut written, &file).unwrap();
+        assert!(written.starts_with("01,SENDER,RECEIVER,170601,1200,2,80,1,2/\n"));
+    }
+
+    #[test]
+    fn redacted_display() {
+        use data::{Redacted, RedactOptions};
+
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let redacted = Redacted(&file).to_string();
+        assert_eq!(redacted, file.redact(RedactOptions::default()).to_string());
+        assert_ne!(redacted, file.to_string());
+        assert!(!redacted.contains("Text: "));
+        assert!(!redacted.contains("Bank: "));
+        assert!(redacted.contains("Account a#\"******6789\""));
+    }
+
+    #[test]
+    fn optional_reference_nums() {
+        let check = |detail: &str, bank: Option<&str>, customer: Option<&str>, text: Option<&str>| {
+            let continuations = detail.matches("\n88,").count();
+            let input = SLASHED_TEXT
+                .replacen("16,195,100,,,,/", detail, 1)
+                .replace("49,1850,5/", &format!("49,1850,{}/", 5 + continuations))
+                .replace("98,1850,1,7/", &format!("98,1850,1,{}/", 7 + continuations))
+                .replace("99,1850,1,9/", &format!("99,1850,1,{}/", 9 + continuations));
+            let file = data::File::process_str(&input)
+                .unwrap_or_else(|e| panic!("{}: {}", detail, e));
+            let tx = &file.groups[0].accounts[0].transaction_details[2];
+            assert_eq!(tx.amount, Some(100), "{}", detail);
+            assert_eq!(tx.bank_ref_num.as_ref().map(|r| r.0.as_str()), bank, "{}", detail);
+            assert_eq!(tx.customer_ref_num.as_ref().map(|r| r.0.as_str()), customer, "{}", detail);
+            assert_eq!(tx.text, text.map(|t| vec![t.to_string()]), "{}", detail);
+        };
+
+        let refs = vec![
+            ("BANK,CUST", Some("BANK"), Some("CUST")),
+            ("BANK,", Some("BANK"), None),
+            (",CUST", None, Some("CUST")),
+            (",", None, None),
+        ];
+        // Funds types that take fields of their own come before the references.
+        let funds = vec!["", "0", "S,100,,", "V,170602,", "D,1,1,100"];
+        let texts = vec![("/", None), ("WIRE, IN", Some("WIRE, IN")), ("\n88,NEXT", Some("NEXT"))];
+        for &(refs, bank, customer) in &refs {
+            for funds in &funds {
+                for &(text, expected) in &texts {
+                    let detail = format!("16,195,100,{},{},{}", funds, refs, text);
+                    check(&detail, bank, customer, expected);
+                    // With a continuation between the references as well.
+                    let split = refs.replacen(",", "\n88,", 1);
+                    let text = text.trim_start_matches("\n88,");
+                    let detail = format!("16,195,100,{},{},{}", funds, split, text);
+                    check(&detail, bank, customer, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn code_table() {
+        use std::borrow::Cow;
+        use std::convert::TryFrom;
+        use data::{CodeDef, CodeTable, DetailCode, Direction};
+        use parse::ParseOptions;
+
+        let builtin = CodeTable::builtin();
+        assert_eq!(builtin.description(195).unwrap(), "Incoming Money Transfer");
+        assert_eq!(builtin.direction(475), Some(Direction::Debit));
+        assert_eq!(builtin.description(10).unwrap(), "Opening Ledger");
+        assert_eq!(builtin.direction(10), None);
+        assert_eq!(builtin.get(899), None);
+
+        let proprietary = SLASHED_TEXT.replace("16,195,100,,,,/", "16,899,100,,,,/");
+        assert!(data::File::process_str(&proprietary).is_err());
+
+        let fee = CodeDef {
+            description: "Wire Fee".to_owned(),
+            direction: Some(Direction::Debit),
+            category: Some("Fees".to_owned()),
+        };
+        let table = CodeTable::builtin()
+            .with_override(899, fee.clone())
+            .with_override(195, CodeDef {
+                category: Some("Wires".to_owned()),
+                ..builtin.get(195).unwrap().into_owned()
+            });
+        assert_eq!(table.get(899).map(Cow::into_owned), Some(fee));
+        assert_eq!(table.category(195).unwrap(), "Wires");
+        assert_eq!(table.description(195).unwrap(), "Incoming Money Transfer");
+
+        let options = ParseOptions {
+            code_table: table,
+            ..ParseOptions::default()
+        };
+        let file = data::File::process_with_options(proprietary.as_bytes(), &options).unwrap();
+        let details = &file.groups[0].accounts[0].transaction_details;
+        // Overrides that agree with the spec on direction keep the spec's code.
+        assert_eq!(details[0].code, DetailCode::try_from(195).unwrap());
+        assert_eq!(u16::from(details[2].code), 899);
+        assert_eq!(details[2].signed_amount(), Some(-100));
+
+        // 195 would read back as a credit wherever the table isn't at hand.
+        let flipped = CodeTable::builtin().with_override(195, CodeDef {
+            direction: Some(Direction::Debit),
+            ..builtin.get(195).unwrap().into_owned()
+        });
+        assert_eq!(flipped.detail_code(195), Err(195));
+        let options = ParseOptions {
+            code_table: flipped,
+            ..ParseOptions::default()
+        };
+        assert!(data::File::process_with_options(SLASHED_TEXT.as_bytes(), &options).is_err());
+    }
+
+    #[test]
+    fn validate_funds_distribution() {
+        use data::{IssueKind, Location, ReferenceNum};
+
+        let file = data::File::process(DISTRIBUTED_AVAIL.as_bytes()).unwrap();
+        assert!(file.validate().issues.iter().all(|i| match i.kind {
+            IssueKind::FundsDistribution { .. } => false,
+            _ => true,
+        }));
+
+        let input = DISTRIBUTED_AVAIL
+            .replace("16,175,300,D,2,0,100,1,200,,,/", "16,175,300,D,2,0,100,1,250,BANK,,/")
+            .replace("16,475,300,,,,/", "16,475,300,S,100,,150,,,/");
+        let file = data::File::process_str(&input).unwrap();
+        let issues = file.validate()
+            .issues
+            .into_iter()
+            .filter(|i| match i.kind {
+                IssueKind::FundsDistribution { .. } => true,
+                _ => false,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(
+            issues[0].location,
+            Location::TransactionDetail {
+                group: 0,
+                account: 0,
+                transaction: 0,
+            }
+        );
+        assert_eq!(
+            issues[0].kind,
+            IssueKind::FundsDistribution {
+                bank_ref_num: Some(ReferenceNum("BANK".to_string())),
+                amount: 300,
+                distributed: 350,
+            }
+        );
+        assert_eq!(
+            issues[0].kind.to_string(),
+            "Transaction r#\"BANK\" of 300 has 350 distributed, +50 off"
+        );
+        assert_eq!(issues[1].kind.to_string(), "Transaction of 300 has 250 distributed, -50 off");
+    }
+
+    #[cfg(feature = "serde-serialize")]
+    #[test]
+    fn flat_row_round_trip() {
+        use data::FlatTransaction;
+
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        for t in file.transactions() {
+            let row = serde_json::to_value(&t).unwrap();
+            let fields = row.as_object().unwrap();
+            assert!(fields.values().all(|v| !v.is_object() && !v.is_array()));
+            assert_eq!(fields["currency"], "USD");
+            assert_eq!(fields["as_of_date"], "2004-06-20");
+            let back: FlatTransaction = serde_json::from_value(row).unwrap();
+            assert_eq!(back, t.to_row());
+        }
+    }
+
+    #[test]
+    fn effective_originator() {
+        use data::Party;
+
+        let aggregated =
+            SPEC_EXAMPLE.replacen("02,031001234,122099999,", "02,031001234,021000021,", 1);
+        let file = data::File::process(aggregated.as_bytes()).unwrap();
+        assert_eq!(file.sender, Party("122099999".to_string()));
+        assert_eq!(file.groups[0].originator, Some(Party("021000021".to_string())));
+        assert_eq!(file.groups[0].effective_originator(&file), &Party("021000021".to_string()));
+
+        let unnamed = SPEC_EXAMPLE.replacen("02,031001234,122099999,", "02,031001234,,", 1);
+        let file = data::File::process(unnamed.as_bytes()).unwrap();
+        assert_eq!(file.groups[0].originator, None);
+        assert_eq!(file.groups[0].effective_originator(&file), &file.sender);
+    }
+
+    #[test]
+    fn lenient_funds_types() {
+        use data::{AccountInfo, FundsType, ProcessWarning};
+        use parse::ParseOptions;
+
+        // On a continuation, before a slash, and with CRLF line endings.
+        let odd = SPEC_EXAMPLE
+            .replacen("040,2830000,,/", "040,2830000,,Q/", 1)
+            .replacen("88,072,1020000,,,074", "88,072,1020000,,Q,074", 1)
+            .replacen("040,5000000,,,074", "040,5000000,,Q,074", 1)
+            .replacen("16,195,10000000,1,", "16,195,10000000,Q,", 1)
+            .replace("\n", "\r\n");
+        let strict = data::File::process_with_options(odd.as_bytes(), &ParseOptions::default());
+        assert!(strict.is_err());
+
+        let options = ParseOptions {
+            lenient_funds_types: true,
+            keep_raw_records: true,
+            ..ParseOptions::default()
+        };
+        let (file, warnings) =
+            data::File::process_with_options_and_warnings(odd.as_bytes(), &options).unwrap();
+        let unknown = |record_number| {
+            ProcessWarning::UnknownFundsType {
+                indicator: "Q".to_string(),
+                record_number,
+            }
+        };
+        assert_eq!(warnings, vec![unknown(3), unknown(3), unknown(14), unknown(18)]);
+        let funds = |info: &AccountInfo| match *info {
+            AccountInfo::Status { ref funds, .. } | AccountInfo::Summary { ref funds, .. } => {
+                funds.clone()
+            }
+        };
+        let first = &file.groups[0].accounts[0];
+        assert_eq!(funds(&first.infos[1]), Some(FundsType::Unknown));
+        assert_eq!(funds(&first.infos[2]), Some(FundsType::Unknown));
+        let account = &file.groups[1].accounts[0];
+        match account.infos[1] {
+            AccountInfo::Status { ref funds, .. } => assert_eq!(*funds, Some(FundsType::Unknown)),
+            ref other => panic!("expected a status, got {:?}", other),
+        }
+        let original = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(account.infos.len(), original.groups[1].accounts[0].infos.len());
+        assert_eq!(account.transaction_details[1].funds, Some(FundsType::Unknown));
+        assert_eq!(
+            account.transaction_details[1].raw_record.as_ref().map(String::as_str),
+            Some("16,195,10000000,Q,,,/")
+        );
+    }
+
+    #[test]
+    fn write_trio() {
+        use std::io;
+
+        struct Full;
+        impl io::Write for Full {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::WriteZero, "full"))
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let mut written = String::new();
+        write::file(&mut written, &file).unwrap();
+        assert_eq!(to_string(&file).unwrap(), written);
+        assert_eq!(to_vec(&file).unwrap(), written.as_bytes());
+        let mut buf = Vec::new();
+        to_writer(&mut buf, &file).unwrap();
+        assert_eq!(buf, written.as_bytes());
+        assert_eq!(to_writer(Full, &file).unwrap_err().kind(), io::ErrorKind::WriteZero);
+
+        let mut overflowing = file.clone();
+        {
+            let details = &mut overflowing.groups[0].accounts[0].transaction_details;
+            let mut detail = details[0].clone();
+            detail.amount = Some(i64::max_value());
+            details.push(detail.clone());
+            details.push(detail);
+        }
+        assert_eq!(to_string(&overflowing), Err(write::WriteError::ControlTotalOverflow));
+        let err = to_writer(Vec::new(), &overflowing).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn account_currency_before_infos() {
+        use penny::Currency;
+        use data::{AccountInfo, AccountStatus, StatusCode};
+
+        let eur = EMPTY_GROUP_AND_ACCOUNT.replacen("03,12345,,", "03,12345,EUR,", 1);
+        let file = data::File::process(eur.as_bytes()).unwrap();
+        let account = &file.groups[1].accounts[0];
+        assert_eq!(account.currency, Some(Currency::EUR));
+        let codes = account.infos.iter().map(|i| match *i {
+            AccountInfo::Status { code, amount, .. } => (code, amount),
+            ref other => panic!("expected a status, got {:?}", other),
+        });
+        assert_eq!(
+            codes.collect::<Vec<_>>(),
+            vec![
+                (StatusCode::Account(AccountStatus::OpeningLedger), Some(1000)),
+                (StatusCode::Account(AccountStatus::ClosingLedger), Some(1000)),
+            ]
+        );
+
+        // Without the currency field, the first type code would be read as one, so the record is
+        // rejected rather than shifted.
+        let missing = EMPTY_GROUP_AND_ACCOUNT.replacen("03,12345,,", "03,12345,", 1);
+        assert!(data::File::process(missing.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn transaction_detail_new() {
+        use chrono::NaiveDate;
+        use data::{BaiDateOrTime, CreditDetail, DetailCode, FundsType, ReferenceNum,
+                   TransactionDetail};
+
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let parsed = &file.groups[1].accounts[0].transaction_details[0];
+        let value_date = BaiDateOrTime::Date(NaiveDate::from_ymd(2004, 6, 22));
+        let code = DetailCode::Credit(CreditDetail::ForeignCollectionCredit);
+        let built = TransactionDetail::new(code, Some(20000000))
+            .with_funds(FundsType::ValueDated(value_date))
+            .with_bank_ref(ReferenceNum("SP4738".to_string()))
+            .with_customer_ref(ReferenceNum("YRC065321".to_string()))
+            .with_text("PROCEEDS OF LETTER OF CREDIT FROM THE ARAMCO OIL CO");
+        assert_eq!(&built, parsed);
+
+        let bare: TransactionDetail = TransactionDetail::new(code, None);
+        assert_eq!(bare.funds, None);
+        assert_eq!(bare.text, None);
+    }
+
+    #[test]
+    fn statement_view() {
+        use chrono::NaiveDate;
+        use penny::Currency;
+        use data::{AccountInfo, AccountStatus, DebitDetail, DetailCode, StatementLine, StatusCode,
+                   TransactionDetail};
+
+        let file = data::File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+        let group = &file.groups[1];
+        let view = group.accounts[0].statement_view(group, Currency::USD);
+        assert_eq!(view.as_of, NaiveDate::from_ymd(2004, 6, 20));
+        assert_eq!(view.opening_balance, 10000000);
+        // The value-dated credit comes after the one available the day it was reported.
+        assert_eq!(
+            view.lines,
+            vec![
+                StatementLine {
+                    date: NaiveDate::from_ymd(2004, 6, 20),
+                    code: 195,
+                    description: "Incoming Money Transfer".to_string(),
+                    amount: 10000000,
+                    balance: 20000000,
+                },
+                StatementLine {
+                    date: NaiveDate::from_ymd(2004, 6, 22),
+                    code: 218,
+                    description: "PROCEEDS OF LETTER OF CREDIT FROM THE ARAMCO OIL CO".to_string(),
+                    amount: 20000000,
+                    balance: 40000000,
+                },
+            ]
+        );
+        assert_eq!(view.closing_balance, 40000000);
+        assert_eq!(view.reported_closing, None);
+        assert!(view.reconciles());
+
+        // Debits count against the balance, and without an opening ledger it's worked out from
+        // the closing ledger.
+        let group = &file.groups[0];
+        let mut account = group.accounts[1].clone();
+        let check = DetailCode::Debit(DebitDetail::CheckPaid);
+        account.transaction_details.push(TransactionDetail::new(check, Some(200000)));
+        account.infos.retain(|info| info.numeric_code() != 10);
+        account.infos.push(AccountInfo::Status {
+            code: StatusCode::Account(AccountStatus::ClosingLedger),
+            amount: Some(-200000),
+            funds: None,
+        });
+        let view = account.statement_view(group, Currency::USD);
+        assert_eq!(view.opening_balance, -500000);
+        let balances = view.lines.iter().map(|l| (l.amount, l.balance)).collect::<Vec<_>>();
+        assert_eq!(balances, vec![(500000, 0), (-200000, -200000)]);
+        assert_eq!(view.closing_balance, -200000);
+        assert!(view.reconciles());
+    }
+
+    #[test]
+    fn unavailable_infos() {
+        use penny::Currency;
+        use data::{AccountInfo, SummaryCode};
+
+        let unreported = EMPTY_GROUP_AND_ACCOUNT
+            .replacen("03,12345,,010,1000,,,015,1000,,/", "03,12345,,010,1000,,,015,,,,999,,,/", 1)
+            .replacen("49,2000,2/", "49,1000,2/", 1)
+            .replacen("98,2000,1,4/", "98,1000,1,4/", 1)
+            .replacen("99,2000,2,8/", "99,1000,2,8/", 1);
+        let file = data::File::process(unreported.as_bytes()).unwrap();
+        let account = &file.groups[1].accounts[0];
+        let unavailable = account.infos.iter().map(AccountInfo::is_unavailable);
+        assert_eq!(unavailable.collect::<Vec<_>>(), vec![false, true, true]);
+        assert!(account.closing_ledger(Currency::USD).is_none());
+        match account.infos[2] {
+            AccountInfo::Summary { code: SummaryCode::Debit(code), .. } => {
+                assert_eq!(u16::from(code), 999)
+            }
+            ref other => panic!("expected a debit summary, got {:?}", other),
+        }
+    }
 }
@@ -1,6 +1,13 @@
+use std::borrow::Cow;
+use std::str;
+
 use nom::{self, ErrorKind, IResult};
 
 use ast;
+use ast::convert::{Converter, RecordCountConvention};
+use data::CodeTable;
+#[cfg(feature = "encoding")]
+use encoding::Encoding;
 
 fn u8_char(input: &[u8], ch: u8) -> IResult<&[u8], u8> {
     if let Some(first) = input.first() {
@@ -20,7 +27,7 @@ macro_rules! specialize_u8_char {
 }
 specialize_u8_char!(space_char, b' ');
 
-named!(end_of_line, alt!(call!(nom::eol) | eof!()));
+named!(end_of_line, alt!(eof!() | call!(nom::eol)));
 const FIELD_SEP_CHAR: u8 = b',';
 specialize_u8_char!(field_sep_char, FIELD_SEP_CHAR);
 const RECORD_SEP_CHAR: u8 = b'/';
@@ -52,12 +59,17 @@ named!(
     field_sep<FieldSep>,
     alt!(
         value!(FieldSep::Normal, field_sep_char) |
-        value!(FieldSep::Continuation, tuple!(record_sep, call!(nom::eol), continuation))
+        value!(
+            FieldSep::Continuation,
+            tuple!(opt!(record_sep), call!(nom::eol), continuation)
+        )
     )
 );
 named!(record_sep<u8>, terminated!(record_sep_char, many0!(space_char)));
+// Some banks end records at the line ending without a slash, even within one file.
+named!(record_end<()>, alt!(value!((), record_sep) | value!((), peek!(end_of_line))));
 fn is_field_char(c: u8) -> bool {
-    c != FIELD_SEP_CHAR && c != RECORD_SEP_CHAR
+    c != FIELD_SEP_CHAR && c != RECORD_SEP_CHAR && c != b'\r' && c != b'\n'
 }
 named!(field_inner, take_while1!(is_field_char));
 
@@ -152,6 +164,34 @@ named!(
     )
 );
 
+// Reads at most the declared number of distributions, so a following field isn't taken for one.
+// A count that doesn't match is left for conversion to report.
+fn distributed_avail_dists<'a>(
+    input: &'a [u8],
+    num: &[u8],
+) -> IResult<&'a [u8], Vec<ast::RawDistributedAvailDistribution<'a>>> {
+    let num = match str::from_utf8(num).ok().and_then(|n| n.trim().parse::<usize>().ok()) {
+        Some(num) => num,
+        None => return separated_nonempty_list!(input, field_sep, distributed_avail_distribution_inner),
+    };
+    let (mut rest, first) = try_parse!(input, distributed_avail_distribution_inner);
+    let mut dists = vec![first];
+    while dists.len() < num {
+        let next = match field_sep(rest) {
+            IResult::Done(next, _) => next,
+            _ => break,
+        };
+        match distributed_avail_distribution_inner(next) {
+            IResult::Done(next, dist) => {
+                dists.push(dist);
+                rest = next;
+            }
+            _ => break,
+        }
+    }
+    IResult::Done(rest, dists)
+}
+
 named!(
     funds_type_inner<ast::RawFundsType>,
     alt!(
@@ -182,12 +222,14 @@ named!(
         preceded!(call!(u8_char, b'D'), return_error!(ErrorKind::Custom(107), do_parse!(
             field_sep >>
             num: field_inner >> field_sep >>
-            dists: separated_nonempty_list!(field_sep, distributed_avail_distribution_inner) >>
+            dists: call!(distributed_avail_dists, num) >>
             (ast::RawFundsType::DistributedAvailD {
                 num,
                 dists,
             })
-        )))
+        ))) |
+        // Left for conversion to reject or read as Z.
+        map!(field_inner, ast::RawFundsType::Other)
     )
 );
 
@@ -207,6 +249,24 @@ named!(
     )
 );
 
+// Text runs to the end of the record, so a slash only terminates it when nothing follows on the
+// line. When a 16 has no text, it can still start on an 88.
+named!(
+    detail_text<Option<Vec<&[u8]>>>,
+    alt!(
+        map!(
+            complete!(preceded!(
+                tuple!(opt!(record_sep), call!(nom::eol), continuation),
+                text
+            )),
+            Some
+        ) |
+        value!(None, terminated!(record_sep, peek!(end_of_line))) |
+        value!(None, peek!(end_of_line)) |
+        map!(text, Some)
+    )
+);
+
 named!(
     pub record<ast::RawRecord>,
     alt!(
@@ -215,12 +275,12 @@ named!(
             sender_ident: field_inner >> field_sep >>
             receiver_ident: field_inner >> field_sep >>
             creation_date: field_inner >> field_sep >>
-            creation_time: field_inner >> field_sep >>
+            creation_time: opt!(field_inner) >> field_sep >>
             ident_num: field_inner >> field_sep >>
             physical_record_len: opt!(field_inner) >> field_sep >>
             block_size: opt!(field_inner) >> field_sep >>
-            version_number: field_inner >>
-            record_sep >>
+            version_number: opt!(field_inner) >>
+            record_end >>
             (ast::RawRecord::FileHeader(ast::RawFileHeader {
                 sender_ident,
                 receiver_ident,
@@ -241,7 +301,7 @@ named!(
             as_of_time: opt!(field_inner) >> field_sep >>
             currency: opt!(field_inner) >> field_sep >>
             as_of_date_mod: opt!(field_inner) >>
-            record_sep >>
+            record_end >>
             (ast::RawRecord::GroupHeader(ast::RawGroupHeader {
                 ultimate_receiver_ident,
                 originator_ident,
@@ -257,7 +317,7 @@ named!(
             customer_account_num: field_inner >> field_sep >>
             currency: opt!(field_inner) >> field_sep >>
             infos: separated_nonempty_list!(field_sep, account_info_inner) >>
-            record_sep >>
+            record_end >>
             (ast::RawRecord::AccountIdent(ast::RawAccountIdent {
                 customer_account_num,
                 currency,
@@ -271,7 +331,7 @@ named!(
             funds_type: opt!(funds_type_inner) >> field_sep >>
             bank_ref_num: opt!(field_inner) >> field_sep >>
             customer_ref_num: opt!(field_inner) >> field_sep >>
-            txt: alt!(value!(None, record_sep) | map!(text, Some)) >>
+            txt: detail_text >>
             (ast::RawRecord::TransactionDetail(ast::RawTransactionDetail {
                 type_code,
                 amount,
@@ -285,7 +345,7 @@ named!(
             field_sep >>
             control_total: field_inner >> field_sep >>
             records_num: field_inner >>
-            record_sep >>
+            record_end >>
             (ast::RawRecord::AccountTrailer(ast::RawAccountTrailer {
                 control_total,
                 records_num,
@@ -296,7 +356,7 @@ named!(
             control_total: field_inner >> field_sep >>
             accounts_num: field_inner >> field_sep >>
             records_num: field_inner >>
-            record_sep >>
+            record_end >>
             (ast::RawRecord::GroupTrailer(ast::RawGroupTrailer {
                 control_total,
                 accounts_num,
@@ -308,7 +368,7 @@ named!(
             control_total: field_inner >> field_sep >>
             groups_num: field_inner >> field_sep >>
             records_num: field_inner >>
-            record_sep >>
+            record_end >>
             (ast::RawRecord::FileTrailer(ast::RawFileTrailer {
                 control_total,
                 groups_num,
@@ -318,7 +378,351 @@ named!(
     )
 );
 
+// A 16 Transaction Detail with the funds type before the amount, for `Dialect::FundsBeforeAmount`.
+named!(
+    detail_funds_first<ast::RawRecord>,
+    preceded!(tag!(b"16"), return_error!(ErrorKind::Custom(16), do_parse!(
+        field_sep >>
+        type_code: field_inner >> field_sep >>
+        funds_type: opt!(funds_type_inner) >> field_sep >>
+        amount: opt!(field_inner) >> field_sep >>
+        bank_ref_num: opt!(field_inner) >> field_sep >>
+        customer_ref_num: opt!(field_inner) >> field_sep >>
+        txt: detail_text >>
+        (ast::RawRecord::TransactionDetail(ast::RawTransactionDetail {
+            type_code,
+            amount,
+            funds_type,
+            bank_ref_num,
+            customer_ref_num,
+            text: txt,
+        }))
+    )))
+);
+
+// `record` with the field order of `dialect`.
+pub fn dialect_record(input: &[u8], dialect: Dialect) -> IResult<&[u8], ast::RawRecord> {
+    match dialect {
+        Dialect::FundsBeforeAmount if input.starts_with(b"16") => detail_funds_first(input),
+        _ => record(input),
+    }
+}
+
+// Every 88 continuation adds a physical record (and a line ending) to its logical record.
+fn physical_records(record: &[u8]) -> usize {
+    1 + record.iter().filter(|&&c| c == b'\n').count()
+}
+pub fn counted_record(input: &[u8]) -> IResult<&[u8], (ast::RawRecord, usize)> {
+    counted_dialect_record(input, Dialect::Standard)
+}
+pub fn counted_dialect_record(
+    input: &[u8],
+    dialect: Dialect,
+) -> IResult<&[u8], (ast::RawRecord, usize)> {
+    match dialect_record(input, dialect) {
+        IResult::Done(rest, record) => {
+            let consumed = &input[..input.len() - rest.len()];
+            IResult::Done(rest, (record, physical_records(consumed)))
+        }
+        IResult::Error(e) => IResult::Error(e),
+        IResult::Incomplete(n) => IResult::Incomplete(n),
+    }
+}
+
+// Compatibility option for banks that send amounts in whole units of the currency (dollars)
+// instead of the minor units (cents) the spec requires. Only use `MajorUnits` for files from such
+// a bank, since it multiplies every amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmountScale {
+    MinorUnits,
+    MajorUnits,
+}
+
+impl Default for AmountScale {
+    fn default() -> Self {
+        AmountScale::MinorUnits
+    }
+}
+
+// Known deviations from the spec's field order, which records are parsed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    Standard,
+    // 16 Transaction Detail records with the funds type (and its extra fields) before the amount,
+    // as in `16,175,D,2,0,100,1,200,300,...` for an amount of 300.
+    FundsBeforeAmount,
+}
+
+impl Default for Dialect {
+    fn default() -> Self {
+        Dialect::Standard
+    }
+}
+
+// New options can be added without breaking callers, so build these from `default()` (or
+// `blocked`) rather than with every field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParseOptions {
+    // Fixed-width physical records concatenated without line endings, padded with spaces.
+    pub record_width: Option<usize>,
+    // Without a `record_width`, deblock files whose first line runs past the physical record
+    // length their file header declares.
+    pub detect_record_width: bool,
+    pub amount_scale: AmountScale,
+    // Two-digit years below this are in the 2000s, and the rest are in the 1900s.
+    pub year_pivot: u8,
+    pub dialect: Dialect,
+    // Keep the text behind each transaction detail in `TransactionDetail::raw_record`.
+    pub keep_raw_records: bool,
+    pub record_count_convention: RecordCountConvention,
+    // How many coded fields come before the text of 16 records, for dialects that add them.
+    pub detail_extensions: usize,
+    // Crate-private so that turning on the `encoding` feature doesn't change the public fields.
+    // Set with `ParseOptions::encoding`.
+    #[cfg(feature = "encoding")]
+    pub(crate) encoding: Encoding,
+    // For banks with type codes of their own.
+    pub code_table: CodeTable,
+    // Funds type indicators the spec doesn't define are read as Z (unknown availability), with
+    // a warning, instead of failing the file.
+    pub lenient_funds_types: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            record_width: None,
+            detect_record_width: false,
+            amount_scale: AmountScale::default(),
+            year_pivot: ast::convert::DEFAULT_YEAR_PIVOT,
+            dialect: Dialect::default(),
+            keep_raw_records: false,
+            record_count_convention: RecordCountConvention::default(),
+            detail_extensions: 0,
+            #[cfg(feature = "encoding")]
+            encoding: Encoding::default(),
+            code_table: CodeTable::builtin(),
+            lenient_funds_types: false,
+        }
+    }
+}
+
+impl ParseOptions {
+    pub fn blocked(record_width: usize) -> ParseOptions {
+        ParseOptions {
+            record_width: Some(record_width),
+            ..ParseOptions::default()
+        }
+    }
+
+    // The character encoding the input is in, which is transcoded to UTF-8 before parsing.
+    #[cfg(feature = "encoding")]
+    pub fn encoding(self, encoding: Encoding) -> Self {
+        ParseOptions { encoding, ..self }
+    }
+
+    // The dialect is left to the record parser.
+    pub fn prepare<'a>(&self, input: &'a [u8]) -> Cow<'a, [u8]> {
+        self.deblock(input)
+    }
+
+    pub(crate) fn converter(&self) -> Converter {
+        Converter::new()
+            .record_count_convention(self.record_count_convention)
+            .code_table(self.code_table.clone())
+            .lenient_funds_types(self.lenient_funds_types)
+    }
+
+    fn record_width(&self, input: &[u8]) -> Option<usize> {
+        match self.record_width {
+            None if self.detect_record_width => declared_record_width(input),
+            record_width => record_width,
+        }
+    }
+
+    #[cfg(not(feature = "encoding"))]
+    fn deblock<'a>(&self, input: &'a [u8]) -> Cow<'a, [u8]> {
+        match self.record_width(input) {
+            Some(record_width) => Cow::Owned(deblock(input, record_width)),
+            None => Cow::Borrowed(input),
+        }
+    }
+
+    // Record widths are in bytes of the original encoding, so records are decoded one by one.
+    #[cfg(feature = "encoding")]
+    fn deblock<'a>(&self, input: &'a [u8]) -> Cow<'a, [u8]> {
+        let decoded = self.encoding.decode(input);
+        match self.record_width(&decoded) {
+            Some(record_width) => Cow::Owned(deblock_records(
+                input.chunks(record_width).map(|r| self.encoding.decode(r)),
+            )),
+            None => decoded,
+        }
+    }
+}
+
+// The physical record length from the 01 File Header, if the file is blocked: its first line
+// runs past that length, as its records aren't ended by line breaks. Files with line endings
+// are left alone, whatever they declare.
+pub fn declared_record_width(input: &[u8]) -> Option<usize> {
+    let input = skip_preamble(input);
+    if !input.starts_with(b"01,") {
+        return None;
+    }
+    let line_len = input
+        .iter()
+        .position(|&c| c == b'\r' || c == b'\n')
+        .unwrap_or_else(|| input.len());
+    let header_len = input[..line_len]
+        .iter()
+        .position(|&c| c == RECORD_SEP_CHAR)
+        .unwrap_or(line_len);
+    let width = input[..header_len].split(|&c| c == FIELD_SEP_CHAR).nth(6)?;
+    match str::from_utf8(width).ok()?.trim().parse() {
+        Ok(width) if width > 0 && line_len > width => Some(width),
+        _ => None,
+    }
+}
+
+// Splits blocked input into newline terminated physical records, dropping the padding.
+pub fn deblock(input: &[u8], record_width: usize) -> Vec<u8> {
+    deblock_records(input.chunks(record_width))
+}
+
+fn deblock_records<R: AsRef<[u8]>, I: Iterator<Item = R>>(records: I) -> Vec<u8> {
+    let mut output = Vec::new();
+    for record in records {
+        let record = record.as_ref();
+        let end = record
+            .iter()
+            .rposition(|&c| c != b' ' && c != b'\r' && c != b'\n')
+            .map_or(0, |i| i + 1);
+        if end == 0 {
+            continue;
+        }
+        output.extend_from_slice(&record[..end]);
+        output.push(b'\n');
+    }
+    output
+}
+
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+// Some transports prepend a UTF-8 BOM or blank lines before the file header.
+pub fn skip_preamble(input: &[u8]) -> &[u8] {
+    let input = if input.starts_with(BOM) {
+        &input[BOM.len()..]
+    } else {
+        input
+    };
+    let start = input
+        .iter()
+        .position(|&c| c != b'\r' && c != b'\n' && c != b' ' && c != b'\t')
+        .unwrap_or_else(|| input.len());
+    &input[start..]
+}
+
 named!(
-    pub file<Vec<ast::RawRecord>>,
-    many0!(terminated!(record, end_of_line))
+    pub file_record<(ast::RawRecord, usize)>,
+    terminated!(counted_record, end_of_line)
 );
+pub fn dialect_file_record(
+    input: &[u8],
+    dialect: Dialect,
+) -> IResult<&[u8], (ast::RawRecord, usize)> {
+    terminated!(input, call!(counted_dialect_record, dialect), end_of_line)
+}
+named!(records_list<Vec<(ast::RawRecord, usize)>>, many0!(file_record));
+pub fn file(input: &[u8]) -> IResult<&[u8], Vec<ast::RawRecord>> {
+    counted_file(input).map(|records| records.into_iter().map(|(record, _)| record).collect())
+}
+// `file` with the number of physical records each record was read from.
+pub fn counted_file(input: &[u8]) -> IResult<&[u8], Vec<(ast::RawRecord, usize)>> {
+    records_list(skip_preamble(input))
+}
+
+// A single line of a file, split into its record code and fields without any interpretation.
+// 88 Continuation records are yielded on their own. Text is never split, so the text of a 16
+// Transaction Detail is always the last field of the line it starts on, and all of any
+// continuation after that.
+#[derive(Debug, Clone)]
+pub struct PhysicalRecord<'a> {
+    pub code: &'a str,
+    pub fields: Vec<&'a str>,
+}
+
+named!(physical_line<&str>, map_res!(take_while!(is_text_char), str::from_utf8));
+
+#[derive(Debug, Clone)]
+pub struct Records<'a> {
+    input: &'a [u8],
+    // The text lines of the latest 16 record, as found by `record`, where they're still to come.
+    text: Vec<&'a [u8]>,
+}
+
+pub fn records(input: &[u8]) -> Records {
+    Records {
+        input: skip_preamble(input),
+        text: Vec::new(),
+    }
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Result<PhysicalRecord<'a>, ErrorKind>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.input.is_empty() {
+            return None;
+        }
+        if self.input.starts_with(b"16,") {
+            self.text = match record(self.input) {
+                IResult::Done(_, ast::RawRecord::TransactionDetail(td)) => {
+                    td.text.unwrap_or_default()
+                }
+                _ => Vec::new(),
+            };
+        }
+        let (rest, line) = match physical_line(self.input) {
+            IResult::Done(rest, line) => (rest, line),
+            IResult::Error(e) => {
+                self.input = &[];
+                return Some(Err(e));
+            }
+            IResult::Incomplete(_) => {
+                self.input = &[];
+                return Some(Err(ErrorKind::Complete));
+            }
+        };
+        self.input = if rest.starts_with(b"\r\n") {
+            &rest[2..]
+        } else if rest.is_empty() {
+            rest
+        } else {
+            &rest[1..]
+        };
+
+        let line = line.trim_end_matches(' ');
+        let line = if line.ends_with(RECORD_SEP_CHAR as char) {
+            &line[..line.len() - 1]
+        } else {
+            line
+        };
+        let start = line.as_ptr() as usize;
+        let text = self.text
+            .iter()
+            .map(|t| t.as_ptr() as usize)
+            .find(|&t| t > start && t <= start + line.len())
+            .map(|t| t - start);
+        let (line, text) = match text {
+            // The text follows a field separator.
+            Some(at) => (&line[..at - 1], Some(&line[at..])),
+            None => (line, None),
+        };
+        let mut fields = line.split(FIELD_SEP_CHAR as char);
+        let code = fields.next().unwrap_or("");
+        Some(Ok(PhysicalRecord {
+            code,
+            fields: fields.chain(text).collect(),
+        }))
+    }
+}
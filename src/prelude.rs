@@ -0,0 +1,4 @@
+pub use penny::{Currency, Money};
+
+pub use data::{Account, AccountInfo, DetailCode, File, FileProcessError, FundsType, Group,
+               StatusCode, SummaryCode, TransactionDetail};
@@ -0,0 +1,427 @@
+use std::error::Error;
+use std::fmt::{self, Write};
+use std::io;
+
+use chrono::{NaiveDate, NaiveTime};
+
+use data;
+
+// Optional fields are written empty when absent, so a parsed file is written back with the same
+// fields present. Control totals and record numbers in trailers are always recomputed.
+
+fn date<W: Write>(w: &mut W, date: &NaiveDate) -> fmt::Result {
+    write!(w, "{}", date.format("%y%m%d"))
+}
+fn time<W: Write>(w: &mut W, time: Option<&NaiveTime>) -> fmt::Result {
+    match time {
+        Some(time) => write!(w, "{}", time.format("%H%M")),
+        // End of day
+        None => write!(w, "9999"),
+    }
+}
+fn date_or_time<W: Write>(w: &mut W, date_or_time: &data::BaiDateOrTime) -> fmt::Result {
+    date(w, &date_or_time.clone().date())?;
+    write!(w, ",")?;
+    match *date_or_time {
+        data::BaiDateOrTime::Date(_) => Ok(()),
+        data::BaiDateOrTime::DateTime(ref dt) => time(w, Some(&dt.time())),
+        data::BaiDateOrTime::DateEndOfDay(_) => time(w, None),
+    }
+}
+fn opt<W: Write, T: fmt::Display>(w: &mut W, field: Option<T>) -> fmt::Result {
+    match field {
+        Some(field) => write!(w, "{}", field),
+        None => Ok(()),
+    }
+}
+
+// Passes formatted output through to an `io::Write`, keeping the I/O error that `fmt::Error`
+// has no room for.
+struct IoWriter<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+pub fn to_writer<W: io::Write>(writer: W, f: &data::File) -> io::Result<()> {
+    let mut writer = IoWriter {
+        inner: writer,
+        error: None,
+    };
+    file(&mut writer, f).map_err(|e| match e {
+        WriteError::Fmt(_) => writer
+            .error
+            .take()
+            .unwrap_or_else(|| io::Error::new(io::ErrorKind::Other, "error formatting file")),
+        e => io::Error::new(io::ErrorKind::InvalidData, e),
+    })
+}
+
+// Writing to a `String` can only fail if the control totals overflow.
+pub fn to_string(f: &data::File) -> Result<String, WriteError> {
+    let mut written = String::new();
+    file(&mut written, f)?;
+    Ok(written)
+}
+
+pub fn to_vec(f: &data::File) -> Result<Vec<u8>, WriteError> {
+    to_string(f).map(String::into_bytes)
+}
+
+fn add(total: i64, amount: i64) -> Result<i64, WriteError> {
+    total
+        .checked_add(amount)
+        .ok_or(WriteError::ControlTotalOverflow)
+}
+
+pub fn file<W: Write>(w: &mut W, file: &data::File) -> Result<(), WriteError> {
+    write!(w, "01,{},{},", file.sender.0, file.receiver.0)?;
+    date(w, &file.creation.date())?;
+    write!(w, ",")?;
+    match file.creation {
+        data::BaiDateTime::DateTime(ref dt) => time(w, Some(&dt.time()))?,
+        data::BaiDateTime::DateEndOfDay(_) => time(w, None)?,
+        data::BaiDateTime::Date(_) => {}
+    }
+    write!(w, ",{},", file.ident.0)?;
+    opt(w, file.physical_record_len)?;
+    write!(w, ",")?;
+    opt(w, file.block_size)?;
+    write!(w, ",")?;
+    opt(w, file.format_version)?;
+    write!(w, "/\n")?;
+
+    let mut control_total = 0;
+    let mut records_num = 2;
+    for g in &file.groups {
+        let (group_total, group_records_num) = group(w, g)?;
+        control_total = add(control_total, group_total)?;
+        records_num += group_records_num;
+    }
+    write!(
+        w,
+        "99,{},{},{}/\n",
+        control_total,
+        file.groups.len(),
+        records_num
+    )?;
+    Ok(())
+}
+
+fn group<W: Write>(w: &mut W, group: &data::Group) -> Result<(i64, usize), WriteError> {
+    write!(w, "02,")?;
+    opt(w, group.ultimate_receiver.as_ref().map(|p| &p.0))?;
+    write!(w, ",")?;
+    opt(w, group.originator.as_ref().map(|p| &p.0))?;
+    write!(w, ",{},", u8::from(group.status))?;
+    date_or_time(w, &group.as_of)?;
+    write!(w, ",")?;
+    opt(w, group.currency)?;
+    write!(w, ",")?;
+    opt(w, group.as_of_date_mod.map(u8::from))?;
+    write!(w, "/\n")?;
+
+    let mut control_total = 0;
+    let mut records_num = 2;
+    for a in &group.accounts {
+        let (account_total, account_records_num) = account(w, a)?;
+        control_total = add(control_total, account_total)?;
+        records_num += account_records_num;
+    }
+    write!(
+        w,
+        "98,{},{},{}/\n",
+        control_total,
+        group.accounts.len(),
+        records_num
+    )?;
+    Ok((control_total, records_num))
+}
+
+fn account<W: Write>(w: &mut W, account: &data::Account) -> Result<(i64, usize), WriteError> {
+    let mut control_total = 0;
+    write!(w, "03,{},", account.customer_account.0)?;
+    opt(w, account.currency)?;
+    if account.infos.is_empty() {
+        write!(w, ",,,,")?;
+    }
+    for i in &account.infos {
+        write!(w, ",")?;
+        control_total = add(control_total, account_info(w, i)?)?;
+    }
+    write!(w, "/\n")?;
+
+    let mut records_num = 2;
+    for td in &account.transaction_details {
+        let (detail_total, detail_records_num) = transaction_detail(w, td)?;
+        control_total = add(control_total, detail_total)?;
+        records_num += detail_records_num;
+    }
+    write!(w, "49,{},{}/\n", control_total, records_num)?;
+    Ok((control_total, records_num))
+}
+
+fn account_info<W: Write>(w: &mut W, info: &data::AccountInfo) -> Result<i64, fmt::Error> {
+    match *info {
+        data::AccountInfo::Summary {
+            code,
+            amount,
+            item_count,
+            ref funds,
+        } => {
+            write!(w, "{:03},", u16::from(code))?;
+            opt(w, amount)?;
+            write!(w, ",")?;
+            opt(w, item_count)?;
+            write!(w, ",")?;
+            funds_type(w, funds.as_ref())?;
+            Ok(amount.map_or(0, |a| a as i64))
+        }
+        data::AccountInfo::Status {
+            code,
+            amount,
+            ref funds,
+        } => {
+            write!(w, "{:03},", u16::from(code))?;
+            opt(w, amount)?;
+            write!(w, ",,")?;
+            funds_type(w, funds.as_ref())?;
+            Ok(amount.unwrap_or(0))
+        }
+    }
+}
+
+fn funds_type<W: Write>(w: &mut W, funds: Option<&data::FundsType>) -> fmt::Result {
+    use data::FundsType as FT;
+    match funds {
+        None => Ok(()),
+        Some(&FT::Unknown) => write!(w, "Z"),
+        Some(&FT::ImmediateAvail) => write!(w, "0"),
+        Some(&FT::OneDayAvail) => write!(w, "1"),
+        Some(&FT::TwoOrMoreDaysAvail) => write!(w, "2"),
+        Some(&FT::DistributedAvailS {
+                 immediate,
+                 one_day,
+                 more_than_one_day,
+             }) => {
+            write!(w, "S,")?;
+            opt(w, immediate)?;
+            write!(w, ",")?;
+            opt(w, one_day)?;
+            write!(w, ",")?;
+            opt(w, more_than_one_day)
+        }
+        Some(&FT::ValueDated(ref avail)) => {
+            write!(w, "V,")?;
+            date_or_time(w, avail)
+        }
+        Some(&FT::DistributedAvailD(ref dists)) => {
+            write!(w, "D,{}", dists.len())?;
+            for dist in dists {
+                write!(w, ",{},{}", dist.days, dist.amount)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn transaction_detail<W: Write>(
+    w: &mut W,
+    detail: &data::TransactionDetail,
+) -> Result<(i64, usize), fmt::Error> {
+    write!(w, "16,{:03},", u16::from(detail.code))?;
+    opt(w, detail.amount)?;
+    write!(w, ",")?;
+    funds_type(w, detail.funds.as_ref())?;
+    write!(w, ",")?;
+    opt(w, detail.bank_ref_num.as_ref().map(|r| &r.0))?;
+    write!(w, ",")?;
+    opt(w, detail.customer_ref_num.as_ref().map(|r| &r.0))?;
+    write!(w, ",")?;
+    for extension in &detail.extensions {
+        write!(w, "{},", extension)?;
+    }
+    let records_num = match detail.text {
+        Some(ref text) if !text.is_empty() => {
+            for (i, line) in text.iter().enumerate() {
+                if i > 0 {
+                    write!(w, "88,")?;
+                }
+                write!(w, "{}\n", line)?;
+            }
+            text.len()
+        }
+        _ => {
+            write!(w, "/\n")?;
+            1
+        }
+    };
+    Ok((detail.amount.unwrap_or(0), records_num))
+}
+
+// What to do with characters outside ASCII, which BAI2 doesn't allow, in party names, account
+// numbers, reference numbers and text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonAsciiPolicy {
+    Reject,
+    // Accented Latin letters lose their accents, and anything else becomes '?'.
+    Transliterate,
+    Strip,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WriteOptions {
+    // None writes fields as they are.
+    pub non_ascii: Option<NonAsciiPolicy>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteError {
+    NonAscii { field: &'static str, value: String },
+    // The amounts add up past what a trailer's control total can hold.
+    ControlTotalOverflow,
+    // A balance worked out from the amounts is past what an `i64` can hold.
+    BalanceOverflow,
+    Fmt(fmt::Error),
+}
+
+impl From<fmt::Error> for WriteError {
+    fn from(e: fmt::Error) -> WriteError {
+        WriteError::Fmt(e)
+    }
+}
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            WriteError::NonAscii { field, ref value } => {
+                write!(f, "Non-ASCII character in {}: {:?}", field, value)
+            }
+            WriteError::ControlTotalOverflow => write!(f, "Control total overflows i64"),
+            WriteError::BalanceOverflow => write!(f, "Balance overflows i64"),
+            WriteError::Fmt(ref e) => write!(f, "Error writing file: {}", e),
+        }
+    }
+}
+
+impl Error for WriteError {
+    fn source(&self) -> Option<&(Error + 'static)> {
+        match *self {
+            WriteError::Fmt(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+fn transliterate(c: char) -> &'static str {
+    match c {
+        'À'...'Å' => "A",
+        'à'...'å' => "a",
+        'Æ' => "AE",
+        'æ' => "ae",
+        'Ç' => "C",
+        'ç' => "c",
+        'È'...'Ë' => "E",
+        'è'...'ë' => "e",
+        'Ì'...'Ï' => "I",
+        'ì'...'ï' => "i",
+        'Ñ' => "N",
+        'ñ' => "n",
+        'Ò'...'Ö' | 'Ø' => "O",
+        'ò'...'ö' | 'ø' => "o",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'Ù'...'Ü' => "U",
+        'ù'...'ü' => "u",
+        'Ý' | 'Ÿ' => "Y",
+        'ý' | 'ÿ' => "y",
+        'ß' => "ss",
+        _ => "?",
+    }
+}
+
+fn ascii_field(
+    name: &'static str,
+    value: &mut String,
+    policy: NonAsciiPolicy,
+) -> Result<(), WriteError> {
+    if value.is_ascii() {
+        return Ok(());
+    }
+    let mut ascii = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c.is_ascii() {
+            ascii.push(c);
+        } else {
+            match policy {
+                NonAsciiPolicy::Reject => {
+                    return Err(WriteError::NonAscii {
+                        field: name,
+                        value: value.clone(),
+                    })
+                }
+                NonAsciiPolicy::Transliterate => ascii.push_str(transliterate(c)),
+                NonAsciiPolicy::Strip => {}
+            }
+        }
+    }
+    *value = ascii;
+    Ok(())
+}
+
+fn ascii_file(file: &mut data::File, policy: NonAsciiPolicy) -> Result<(), WriteError> {
+    ascii_field("sender", &mut file.sender.0, policy)?;
+    ascii_field("receiver", &mut file.receiver.0, policy)?;
+    for group in &mut file.groups {
+        if let Some(ref mut party) = group.ultimate_receiver {
+            ascii_field("ultimate receiver", &mut party.0, policy)?;
+        }
+        if let Some(ref mut party) = group.originator {
+            ascii_field("originator", &mut party.0, policy)?;
+        }
+        for account in &mut group.accounts {
+            ascii_field("customer account", &mut account.customer_account.0, policy)?;
+            for detail in &mut account.transaction_details {
+                if let Some(ref mut num) = detail.bank_ref_num {
+                    ascii_field("bank reference number", &mut num.0, policy)?;
+                }
+                if let Some(ref mut num) = detail.customer_ref_num {
+                    ascii_field("customer reference number", &mut num.0, policy)?;
+                }
+                for extension in &mut detail.extensions {
+                    ascii_field("extension", extension, policy)?;
+                }
+                if let Some(ref mut text) = detail.text {
+                    for line in text {
+                        ascii_field("text", line, policy)?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn file_with_options<W: Write>(
+    w: &mut W,
+    f: &data::File,
+    options: &WriteOptions,
+) -> Result<(), WriteError> {
+    match options.non_ascii {
+        Some(policy) => {
+            let mut f = f.clone();
+            ascii_file(&mut f, policy)?;
+            file(w, &f)?;
+        }
+        None => file(w, f)?,
+    }
+    Ok(())
+}
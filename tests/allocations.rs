@@ -0,0 +1,44 @@
+extern crate baimax;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use baimax::data::File;
+
+// Counts allocations, so the owned and borrowed models can be compared by more than time. This is
+// the only test in this binary, so nothing else allocates while it counts.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+const SPEC_EXAMPLE: &str = include_str!("../spec-example.bai");
+
+fn allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    f();
+    ALLOCATIONS.load(Ordering::SeqCst) - before
+}
+
+#[test]
+fn borrowed_allocates_less() {
+    let owned = allocations(|| {
+        File::process(SPEC_EXAMPLE.as_bytes()).unwrap();
+    });
+    let borrowed = allocations(|| {
+        File::process_borrowed(SPEC_EXAMPLE.as_bytes()).unwrap();
+    });
+    assert!(borrowed < owned, "owned: {}, borrowed: {}", owned, borrowed);
+}
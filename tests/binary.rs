@@ -0,0 +1,93 @@
+#![cfg(feature = "serde-serialize")]
+
+extern crate baimax;
+extern crate bincode;
+extern crate penny;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+
+use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+
+use baimax::data;
+use penny::{Currency, Money};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+// Files cached with bincode have to come back exactly as they were serialized. bincode doesn't
+// say what type comes next, so this also checks the model never relies on `deserialize_any`.
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    let encoded = bincode::serialize(value).unwrap();
+    bincode::deserialize(&encoded).unwrap()
+}
+
+fn fixture(path: &str) -> data::File {
+    let mut bytes = Vec::new();
+    File::open(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(path))
+        .and_then(|mut f| f.read_to_end(&mut bytes))
+        .unwrap();
+    data::File::process(&bytes).unwrap()
+}
+
+#[test]
+fn fixtures() {
+    for path in &[
+        "spec-example.bai",
+        "tests/fixtures/missing-slashes.bai",
+        "tests/fixtures/padded-numbers.bai",
+        "tests/fixtures/date-only-creation.bai",
+        "tests/fixtures/value-dated-funds.bai",
+        "tests/fixtures/every-funds-type.bai",
+    ] {
+        let file = fixture(path);
+        assert_eq!(round_trip(&file), file, "{}", path);
+    }
+}
+
+// The variants the fixtures don't have: loan, non-monetary, and bank-defined codes, a deletion
+// group with an as-of modifier, end-of-day times, and currencies other than dollars.
+static UNUSUAL: &'static str = "\
+01,SENDER,RECEIVER,170601,9999,7,80,10,2/
+02,RECEIVER,SENDER,3,170601,9999,EUR,4/
+03,12345,JPY,010,1000,,,701,500,,,720,300,2,Z/
+16,721,250,,,,LOAN DETAIL
+16,890,,,,,NON-MONETARY
+16,999,75,,,,BANK DEFINED DEBIT
+16,930,10,,,,BANK DEFINED CREDIT
+49,2135,6/
+98,2135,1,8/
+99,2135,1,10/
+";
+
+#[test]
+fn unusual_variants() {
+    let file = data::File::process(UNUSUAL.as_bytes()).unwrap();
+    assert_eq!(round_trip(&file), file);
+
+    let mut with_raw = file.clone();
+    with_raw.groups[0].accounts[0].transaction_details[0].raw_record =
+        Some("16,721,250,,,,LOAN DETAIL".to_string());
+    assert_eq!(round_trip(&with_raw), with_raw);
+}
+
+#[test]
+fn penny_types() {
+    #[derive(Serialize, Deserialize)]
+    struct Cached {
+        #[serde(with = "baimax::data::penny_serde::money")]
+        balance: Money,
+        #[serde(with = "baimax::data::penny_serde::option_currency")]
+        currency: Option<Currency>,
+    }
+
+    let cached = round_trip(&Cached {
+        balance: Money::new(-12345, Currency::JPY),
+        currency: Some(Currency::EUR),
+    });
+    assert_eq!(cached.balance.amount(), -12345);
+    assert_eq!(cached.balance.currency(), Currency::JPY);
+    assert_eq!(cached.currency, Some(Currency::EUR));
+}
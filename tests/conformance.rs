@@ -0,0 +1,99 @@
+extern crate baimax;
+
+use std::env;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+
+use baimax::data;
+
+// Each input is processed and its result compared against the `{:#?}` snapshot next to it, so
+// any change to what a file parses to shows up here. Run with BAIMAX_BLESS=1 to rewrite the
+// snapshots after an intended change.
+fn check(input: &str, expected: &str) {
+    compare(input, expected, |bytes| format!("{:#?}\n", data::File::process(bytes)));
+}
+
+// The same, but against the `Display` tree of a file that has to parse.
+fn check_display(input: &str, expected: &str) {
+    compare(input, expected, |bytes| format!("{}\n", data::File::process(bytes).unwrap()));
+}
+
+fn compare<F: FnOnce(&[u8]) -> String>(input: &str, expected: &str, render: F) {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let mut bytes = Vec::new();
+    File::open(dir.join(input))
+        .and_then(|mut f| f.read_to_end(&mut bytes))
+        .unwrap();
+    let actual = render(&bytes);
+
+    let expected_path = dir.join(expected);
+    if env::var_os("BAIMAX_BLESS").is_some() {
+        File::create(&expected_path)
+            .and_then(|mut f| f.write_all(actual.as_bytes()))
+            .unwrap();
+        return;
+    }
+    let mut expected = String::new();
+    File::open(&expected_path)
+        .and_then(|mut f| f.read_to_string(&mut expected))
+        .unwrap();
+    assert!(
+        actual == expected,
+        "{} doesn't match {}:\n{}",
+        input,
+        expected_path.display(),
+        actual
+    );
+}
+
+macro_rules! conformance {
+    ($($name:ident: $input:expr => $expected:expr,)+) => {
+        $(
+            #[test]
+            fn $name() {
+                check($input, $expected);
+            }
+        )+
+    };
+}
+
+conformance! {
+    spec_example: "spec-example.bai" => "tests/fixtures/spec-example.expected",
+    missing_slashes: "tests/fixtures/missing-slashes.bai"
+        => "tests/fixtures/missing-slashes.expected",
+    padded_numbers: "tests/fixtures/padded-numbers.bai"
+        => "tests/fixtures/padded-numbers.expected",
+    date_only_creation: "tests/fixtures/date-only-creation.bai"
+        => "tests/fixtures/date-only-creation.expected",
+    value_dated_funds: "tests/fixtures/value-dated-funds.bai"
+        => "tests/fixtures/value-dated-funds.expected",
+    // Malformed files, where the snapshot is the error.
+    bad_control_total: "tests/fixtures/bad-control-total.bai"
+        => "tests/fixtures/bad-control-total.expected",
+    missing_file_trailer: "tests/fixtures/missing-file-trailer.bai"
+        => "tests/fixtures/missing-file-trailer.expected",
+    orphan_continuation: "tests/fixtures/orphan-continuation.bai"
+        => "tests/fixtures/orphan-continuation.expected",
+    negative_summary: "tests/fixtures/negative-summary.bai"
+        => "tests/fixtures/negative-summary.expected",
+}
+
+macro_rules! display {
+    ($($name:ident: $input:expr => $expected:expr,)+) => {
+        $(
+            #[test]
+            fn $name() {
+                check_display($input, $expected);
+            }
+        )+
+    };
+}
+
+display! {
+    spec_example_display: "spec-example.bai" => "tests/fixtures/spec-example.display",
+    every_funds_type_display: "tests/fixtures/every-funds-type.bai"
+        => "tests/fixtures/every-funds-type.display",
+    value_dated_funds_display: "tests/fixtures/value-dated-funds.bai"
+        => "tests/fixtures/value-dated-funds.display",
+}